@@ -0,0 +1,119 @@
+use num_traits::FromPrimitive;
+use solana_client::client_error::ClientError;
+use solana_program::{
+    instruction::InstructionError, message::Message, program_error::ProgramError, pubkey::Pubkey,
+};
+use solana_sdk::{signer::SignerError, transaction::TransactionError};
+use thiserror::Error;
+use token_vesting::error::VestingError;
+
+/// Errors that may be returned by the vesting CLI. Each kind of error exits with its own code so
+/// that scripts can tell them apart.
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Account {0} not found")]
+    AccountNotFound(Pubkey),
+    #[error("Account {0} is not a valid vesting contract")]
+    InvalidVestingAccount(Pubkey),
+    #[error("Nothing to unlock yet, no schedule of {0} has reached its release time")]
+    NotYetVested(Pubkey),
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<ClientError>),
+    #[error("Transaction failed: {0}")]
+    Transaction(TransactionError),
+    #[error("The vesting program failed: {0}")]
+    Program(VestingError),
+    #[error("Signing failed: {0}")]
+    Signer(#[from] SignerError),
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("Account {0} is not a valid treasury pool or allocation")]
+    InvalidPoolAccount(Pubkey),
+    #[error("Account {0} is not a valid distributor or distribution claim")]
+    InvalidDistributorAccount(Pubkey),
+    #[error("Serialization failed: {0}")]
+    Serialization(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::InvalidInput(_) => 2,
+            CliError::AccountNotFound(_) => 3,
+            CliError::InvalidVestingAccount(_) => 4,
+            CliError::NotYetVested(_) => 5,
+            CliError::Rpc(_) => 6,
+            CliError::Transaction(_) => 7,
+            CliError::Program(_) => 8,
+            CliError::Signer(_) => 9,
+            CliError::Database(_) => 10,
+            CliError::InvalidPoolAccount(_) => 11,
+            CliError::InvalidDistributorAccount(_) => 12,
+            CliError::Serialization(_) => 13,
+        }
+    }
+
+    // Custom error codes are only vesting errors when the failing instruction is one of the
+    // vesting program, the token programs using the same codes for their own errors
+    pub fn from_transaction_error(
+        err: TransactionError,
+        message: &Message,
+        program_id: &Pubkey,
+    ) -> Self {
+        if let TransactionError::InstructionError(index, InstructionError::Custom(code)) = err {
+            let instruction = message.instructions.get(index as usize);
+            let instruction_program_id = instruction.and_then(|instruction| {
+                message
+                    .account_keys
+                    .get(instruction.program_id_index as usize)
+            });
+            if instruction_program_id == Some(program_id) {
+                if let Some(vesting_error) = VestingError::from_u32(code) {
+                    return CliError::Program(vesting_error);
+                }
+            }
+        }
+        CliError::Transaction(err)
+    }
+
+    // Failed transactions surface as RPC errors, pull out the program error when there is one
+    pub fn from_client_error(err: ClientError, message: &Message, program_id: &Pubkey) -> Self {
+        match err.kind().get_transaction_error() {
+            Some(transaction_error) => {
+                Self::from_transaction_error(transaction_error, message, program_id)
+            }
+            None => CliError::Rpc(Box::new(err)),
+        }
+    }
+}
+
+// Without the transaction, a failed transaction cannot be attributed to the vesting program
+impl From<ClientError> for CliError {
+    fn from(err: ClientError) -> Self {
+        match err.kind().get_transaction_error() {
+            Some(transaction_error) => CliError::Transaction(transaction_error),
+            None => CliError::Rpc(Box::new(err)),
+        }
+    }
+}
+
+// Instruction builders only fail on arguments they cannot encode
+impl From<ProgramError> for CliError {
+    fn from(err: ProgramError) -> Self {
+        CliError::InvalidInput(err.to_string())
+    }
+}
+
+impl From<bincode::Error> for CliError {
+    fn from(err: bincode::Error) -> Self {
+        CliError::Serialization(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for CliError {
+    fn from(err: serde_json::Error) -> Self {
+        CliError::Serialization(err.to_string())
+    }
+}
@@ -0,0 +1,485 @@
+use crate::error::CliError;
+use rusqlite::{params, Connection, OptionalExtension};
+use solana_program::{pubkey::Pubkey, system_program};
+use solana_sdk::transaction::VersionedTransaction;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, UiLoadedAddresses, UiTransactionStatusMeta,
+    UiTransactionTokenBalance,
+};
+use std::str::FromStr;
+use token_vesting::instruction::{Schedule, VestingInstruction};
+
+// Amounts and times are stored as integers, which SQLite limits to i64
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    signature TEXT PRIMARY KEY,
+    slot INTEGER NOT NULL,
+    block_time INTEGER,
+    error TEXT
+);
+CREATE TABLE IF NOT EXISTS contracts (
+    vesting_account TEXT PRIMARY KEY,
+    seed TEXT NOT NULL,
+    mint_address TEXT NOT NULL,
+    destination_token_address TEXT NOT NULL,
+    total_amount INTEGER NOT NULL,
+    signature TEXT NOT NULL,
+    slot INTEGER NOT NULL,
+    block_time INTEGER
+);
+CREATE TABLE IF NOT EXISTS schedules (
+    vesting_account TEXT NOT NULL,
+    schedule_index INTEGER NOT NULL,
+    release_time INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    PRIMARY KEY (vesting_account, schedule_index)
+);
+CREATE TABLE IF NOT EXISTS unlocks (
+    signature TEXT NOT NULL,
+    instruction_index INTEGER NOT NULL,
+    vesting_account TEXT NOT NULL,
+    destination_token_address TEXT NOT NULL,
+    amount INTEGER,
+    slot INTEGER NOT NULL,
+    block_time INTEGER,
+    PRIMARY KEY (signature, instruction_index, destination_token_address)
+);
+CREATE TABLE IF NOT EXISTS destination_changes (
+    signature TEXT NOT NULL,
+    instruction_index INTEGER NOT NULL,
+    vesting_account TEXT NOT NULL,
+    old_destination_token_address TEXT NOT NULL,
+    new_destination_token_address TEXT NOT NULL,
+    slot INTEGER NOT NULL,
+    block_time INTEGER,
+    PRIMARY KEY (signature, instruction_index)
+);
+CREATE TABLE IF NOT EXISTS sync_state (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    last_signature TEXT NOT NULL
+);
+";
+
+/// A vesting program transaction, decoded from its instructions and token balances
+pub struct IndexedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub error: Option<String>,
+    pub events: Vec<IndexedEvent>,
+}
+
+pub enum IndexedEvent {
+    Created {
+        vesting_account: Pubkey,
+        seed: [u8; 32],
+        mint_address: Pubkey,
+        destination_token_address: Pubkey,
+        schedules: Vec<Schedule>,
+    },
+    Unlocked {
+        instruction_index: usize,
+        vesting_account: Pubkey,
+        destination_token_address: Pubkey,
+        // Missing when the node did not record the token balances of the transaction
+        amount: Option<u64>,
+    },
+    DestinationChanged {
+        instruction_index: usize,
+        vesting_account: Pubkey,
+        old_destination_token_address: Pubkey,
+        new_destination_token_address: Pubkey,
+    },
+}
+
+impl IndexedTransaction {
+    /// Decodes the top level vesting instructions of a transaction. Returns `None` when the
+    /// transaction cannot be decoded.
+    pub fn decode(
+        program_id: &Pubkey,
+        signature: String,
+        confirmed_transaction: EncodedConfirmedTransactionWithStatusMeta,
+    ) -> Option<Self> {
+        let transaction = confirmed_transaction.transaction.transaction.decode()?;
+        let meta = confirmed_transaction.transaction.meta;
+        let mut indexed = IndexedTransaction {
+            signature,
+            slot: confirmed_transaction.slot,
+            block_time: confirmed_transaction.block_time,
+            error: meta
+                .as_ref()
+                .and_then(|meta| meta.err.as_ref())
+                .map(|err| err.to_string()),
+            events: vec![],
+        };
+        if indexed.error.is_some() {
+            return Some(indexed);
+        }
+
+        let account_keys = account_keys_of(&transaction, meta.as_ref());
+        for (instruction_index, instruction) in
+            transaction.message.instructions().iter().enumerate()
+        {
+            if account_keys.get(instruction.program_id_index as usize) != Some(program_id) {
+                continue;
+            }
+            let account_index = |i: usize| instruction.accounts.get(i).map(|index| *index as usize);
+            let account_key = |i: usize| account_index(i).and_then(|index| account_keys.get(index));
+            let events: Vec<IndexedEvent> = match VestingInstruction::unpack(&instruction.data) {
+                Ok(VestingInstruction::Create {
+                    seeds,
+                    mint_address,
+                    destination_token_address,
+                    schedules,
+                })
+                | Ok(VestingInstruction::CreateWithTimeBasis {
+                    seeds,
+                    mint_address,
+                    destination_token_address,
+                    schedules,
+                    ..
+                })
+                | Ok(VestingInstruction::CreateMilestones {
+                    seeds,
+                    mint_address,
+                    destination_token_address,
+                    schedules,
+                    ..
+                })
+                | Ok(VestingInstruction::CreatePausable {
+                    seeds,
+                    mint_address,
+                    destination_token_address,
+                    schedules,
+                    ..
+                })
+                | Ok(VestingInstruction::CreateWithSplits {
+                    seeds,
+                    mint_address,
+                    destination_token_address,
+                    schedules,
+                    ..
+                }) => Pubkey::create_program_address(&[&seeds], program_id)
+                    .ok()
+                    .map(|vesting_account| IndexedEvent::Created {
+                        vesting_account,
+                        seed: seeds,
+                        mint_address,
+                        destination_token_address,
+                        schedules,
+                    })
+                    .into_iter()
+                    .collect(),
+                // The mint address of a native contract is the default pubkey
+                Ok(VestingInstruction::CreateNative {
+                    seeds,
+                    destination_address,
+                    schedules,
+                    ..
+                }) => Pubkey::create_program_address(&[&seeds], program_id)
+                    .ok()
+                    .map(|vesting_account| IndexedEvent::Created {
+                        vesting_account,
+                        seed: seeds,
+                        mint_address: Pubkey::default(),
+                        destination_token_address: destination_address,
+                        schedules,
+                    })
+                    .into_iter()
+                    .collect(),
+                // Native contracts are unlocked with the system program, the lamports leaving the
+                // vesting account itself
+                Ok(VestingInstruction::Unlock { .. })
+                    if account_key(0) == Some(&system_program::id()) =>
+                {
+                    match (account_key(2), account_key(3)) {
+                        (Some(vesting_account), Some(destination_address)) => {
+                            vec![IndexedEvent::Unlocked {
+                                instruction_index,
+                                vesting_account: *vesting_account,
+                                destination_token_address: *destination_address,
+                                amount: account_index(2).and_then(|vesting_index| {
+                                    released_lamports(meta.as_ref()?, vesting_index)
+                                }),
+                            }]
+                        }
+                        _ => vec![],
+                    }
+                }
+                // The split destination accounts follow the accounts of the unlock instruction,
+                // each of them credited with its own share of the released amount
+                Ok(unlock @ VestingInstruction::Unlock { .. })
+                | Ok(unlock @ VestingInstruction::UnlockWithAssociatedDestination { .. }) => {
+                    let first_split_account = match unlock {
+                        VestingInstruction::Unlock { .. } => 5,
+                        _ => 10,
+                    };
+                    match (account_key(2), account_key(4)) {
+                        (Some(vesting_account), Some(destination_token_address)) => {
+                            let splits: Vec<(Pubkey, Option<u64>)> = instruction
+                                .accounts
+                                .iter()
+                                .skip(first_split_account)
+                                .filter_map(|index| {
+                                    let split_index = *index as usize;
+                                    let received = meta
+                                        .as_ref()
+                                        .and_then(|meta| received_amount(meta, split_index));
+                                    Some((*account_keys.get(split_index)?, received))
+                                })
+                                .collect();
+                            // The destination receives what is left of the released amount
+                            let split_amounts: Option<u64> = splits
+                                .iter()
+                                .try_fold(0u64, |total, (_, amount)| total.checked_add((*amount)?));
+                            let amount = account_index(3)
+                                .and_then(|vesting_token_index| {
+                                    released_amount(meta.as_ref()?, vesting_token_index)
+                                })
+                                .and_then(|released| released.checked_sub(split_amounts?));
+                            let mut events = vec![IndexedEvent::Unlocked {
+                                instruction_index,
+                                vesting_account: *vesting_account,
+                                destination_token_address: *destination_token_address,
+                                amount,
+                            }];
+                            events.extend(splits.into_iter().map(
+                                |(split_token_address, amount)| IndexedEvent::Unlocked {
+                                    instruction_index,
+                                    vesting_account: *vesting_account,
+                                    destination_token_address: split_token_address,
+                                    amount,
+                                },
+                            ));
+                            events
+                        }
+                        _ => vec![],
+                    }
+                }
+                Ok(VestingInstruction::ChangeDestination { .. }) => {
+                    match (account_key(0), account_key(1), account_key(3)) {
+                        (Some(vesting_account), Some(old), Some(new)) => {
+                            vec![IndexedEvent::DestinationChanged {
+                                instruction_index,
+                                vesting_account: *vesting_account,
+                                old_destination_token_address: *old,
+                                new_destination_token_address: *new,
+                            }]
+                        }
+                        _ => vec![],
+                    }
+                }
+                Ok(VestingInstruction::Init { .. })
+                | Ok(VestingInstruction::GetClaimable)
+                | Ok(VestingInstruction::ReleaseMilestone { .. })
+                | Ok(VestingInstruction::Pause)
+                | Ok(VestingInstruction::Resume { .. })
+                | Ok(VestingInstruction::InitPool { .. })
+                | Ok(VestingInstruction::AddAllocation { .. })
+                | Ok(VestingInstruction::RemoveAllocation)
+                | Ok(VestingInstruction::ClaimAllocation { .. })
+                | Ok(VestingInstruction::InitDistributor { .. })
+                | Ok(VestingInstruction::ClaimDistribution { .. })
+                | Ok(VestingInstruction::ChangeSplitDestination { .. })
+                | Err(_) => vec![],
+            };
+            indexed.events.extend(events);
+        }
+        Some(indexed)
+    }
+}
+
+// Versioned transactions may load part of their accounts from address lookup tables
+fn account_keys_of(
+    transaction: &VersionedTransaction,
+    meta: Option<&UiTransactionStatusMeta>,
+) -> Vec<Pubkey> {
+    let mut account_keys = transaction.message.static_account_keys().to_vec();
+    let loaded_addresses: Option<&UiLoadedAddresses> =
+        meta.and_then(|meta| meta.loaded_addresses.as_ref().into());
+    if let Some(loaded_addresses) = loaded_addresses {
+        account_keys.extend(
+            loaded_addresses
+                .writable
+                .iter()
+                .chain(loaded_addresses.readonly.iter())
+                .filter_map(|key| Pubkey::from_str(key).ok()),
+        );
+    }
+    account_keys
+}
+
+fn token_balance(
+    balances: Option<&Vec<UiTransactionTokenBalance>>,
+    account_index: usize,
+) -> Option<u64> {
+    balances?
+        .iter()
+        .find(|balance| balance.account_index as usize == account_index)
+        .and_then(|balance| balance.ui_token_amount.amount.parse::<u64>().ok())
+}
+
+// Amount that left the vesting token account during the transaction
+fn released_amount(meta: &UiTransactionStatusMeta, vesting_token_index: usize) -> Option<u64> {
+    let pre_balance = token_balance(meta.pre_token_balances.as_ref().into(), vesting_token_index)?;
+    let post_balance = token_balance(
+        meta.post_token_balances.as_ref().into(),
+        vesting_token_index,
+    )?;
+    pre_balance.checked_sub(post_balance)
+}
+
+// Amount that a destination token account received during the transaction
+fn received_amount(meta: &UiTransactionStatusMeta, token_index: usize) -> Option<u64> {
+    let pre_balance = token_balance(meta.pre_token_balances.as_ref().into(), token_index)?;
+    let post_balance = token_balance(meta.post_token_balances.as_ref().into(), token_index)?;
+    post_balance.checked_sub(pre_balance)
+}
+
+// Lamports that left the vesting account of a native contract during the transaction
+fn released_lamports(meta: &UiTransactionStatusMeta, vesting_index: usize) -> Option<u64> {
+    let pre_balance = meta.pre_balances.get(vesting_index)?;
+    let post_balance = meta.post_balances.get(vesting_index)?;
+    pre_balance.checked_sub(*post_balance)
+}
+
+pub struct IndexDatabase {
+    connection: Connection,
+}
+
+impl IndexDatabase {
+    pub fn open(path: &str) -> Result<Self, CliError> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(IndexDatabase { connection })
+    }
+
+    /// Newest signature indexed by a previous run
+    pub fn last_signature(&self) -> Result<Option<String>, CliError> {
+        Ok(self
+            .connection
+            .query_row(
+                "SELECT last_signature FROM sync_state WHERE id = 0",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// Records a transaction and its events atomically. Transactions must be inserted from the
+    /// oldest to the newest so that destination changes are applied in order.
+    pub fn insert(&mut self, indexed: &IndexedTransaction) -> Result<(), CliError> {
+        let db_transaction = self.connection.transaction()?;
+        let signature = &indexed.signature;
+        let slot = indexed.slot;
+        let block_time = indexed.block_time;
+        db_transaction.execute(
+            "INSERT OR REPLACE INTO transactions (signature, slot, block_time, error)
+            VALUES (?1, ?2, ?3, ?4)",
+            params![signature, slot, block_time, indexed.error],
+        )?;
+        for event in indexed.events.iter() {
+            match event {
+                IndexedEvent::Created {
+                    vesting_account,
+                    seed,
+                    mint_address,
+                    destination_token_address,
+                    schedules,
+                    ..
+                } => {
+                    // The program rejects contracts whose total amount overflows
+                    let total_amount: u64 = schedules.iter().map(|s| s.amount).sum();
+                    db_transaction.execute(
+                        "INSERT OR REPLACE INTO contracts (vesting_account, seed, mint_address,
+                        destination_token_address, total_amount, signature, slot, block_time)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                        params![
+                            vesting_account.to_string(),
+                            Pubkey::new_from_array(*seed).to_string(),
+                            mint_address.to_string(),
+                            destination_token_address.to_string(),
+                            total_amount,
+                            signature,
+                            slot,
+                            block_time
+                        ],
+                    )?;
+                    db_transaction.execute(
+                        "DELETE FROM schedules WHERE vesting_account = ?1",
+                        params![vesting_account.to_string()],
+                    )?;
+                    for (schedule_index, schedule) in schedules.iter().enumerate() {
+                        db_transaction.execute(
+                            "INSERT INTO schedules (vesting_account, schedule_index, release_time,
+                            amount) VALUES (?1, ?2, ?3, ?4)",
+                            params![
+                                vesting_account.to_string(),
+                                schedule_index,
+                                schedule.release_time,
+                                schedule.amount
+                            ],
+                        )?;
+                    }
+                }
+                IndexedEvent::Unlocked {
+                    instruction_index,
+                    vesting_account,
+                    destination_token_address,
+                    amount,
+                } => {
+                    db_transaction.execute(
+                        "INSERT OR REPLACE INTO unlocks (signature, instruction_index,
+                        vesting_account, destination_token_address, amount, slot, block_time)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        params![
+                            signature,
+                            instruction_index,
+                            vesting_account.to_string(),
+                            destination_token_address.to_string(),
+                            amount,
+                            slot,
+                            block_time
+                        ],
+                    )?;
+                }
+                IndexedEvent::DestinationChanged {
+                    instruction_index,
+                    vesting_account,
+                    old_destination_token_address,
+                    new_destination_token_address,
+                } => {
+                    db_transaction.execute(
+                        "INSERT OR REPLACE INTO destination_changes (signature, instruction_index,
+                        vesting_account, old_destination_token_address,
+                        new_destination_token_address, slot, block_time)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        params![
+                            signature,
+                            instruction_index,
+                            vesting_account.to_string(),
+                            old_destination_token_address.to_string(),
+                            new_destination_token_address.to_string(),
+                            slot,
+                            block_time
+                        ],
+                    )?;
+                    db_transaction.execute(
+                        "UPDATE contracts SET destination_token_address = ?1
+                        WHERE vesting_account = ?2",
+                        params![
+                            new_destination_token_address.to_string(),
+                            vesting_account.to_string()
+                        ],
+                    )?;
+                }
+            }
+        }
+        db_transaction.execute(
+            "INSERT OR REPLACE INTO sync_state (id, last_signature) VALUES (0, ?1)",
+            params![signature],
+        )?;
+        db_transaction.commit()?;
+        Ok(())
+    }
+}
@@ -1,175 +1,802 @@
-use chrono::{DateTime, Duration};
+mod error;
+mod index;
+mod output;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
 use clap::{
-    crate_description, crate_name, crate_version, value_t, App, AppSettings, Arg, SubCommand,
+    crate_description, crate_name, crate_version, App, AppSettings, Arg, ArgMatches, SubCommand,
+};
+use error::CliError;
+use index::{IndexDatabase, IndexedEvent, IndexedTransaction};
+use output::{
+    CliAccountMeta, CliAllocation, CliCrankFailure, CliCrankSummary, CliCrankUnlock,
+    CliCreatedContract, CliCreatedDistributor, CliCreatedPool, CliDestinationSplit,
+    CliDistributionTree, CliDistributionTreeClaim, CliDistributionTreeSummary, CliIndexSummary,
+    CliInstruction, CliPool, CliReport, CliReportPeriod, CliReportRecipient, CliSchedule,
+    CliSignOnly, CliSimulation, CliTokenMovement, CliTransaction, CliVestingContract, OutputFormat,
 };
+use solana_account_decoder::UiAccountEncoding;
 use solana_clap_utils::{
-    input_parsers::{keypair_of, pubkey_of, value_of, values_of},
-    input_validators::{is_amount, is_keypair, is_parsable, is_pubkey, is_slot, is_url},
+    input_parsers::{commitment_of, pubkey_of, value_of, values_of},
+    input_validators::{is_amount, is_parsable, is_pubkey, is_slot, is_url, is_valid_pubkey},
+    keypair::{signer_from_path_with_config, SignerFromPathConfig},
+    nonce::{NonceArgs, NONCE_ARG, NONCE_AUTHORITY_ARG},
+    offline::{OfflineArgs, BLOCKHASH_ARG, DUMP_TRANSACTION_MESSAGE, SIGN_ONLY_ARG},
+};
+use solana_client::{
+    client_error::ClientErrorKind,
+    nonce_utils,
+    rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+    rpc_config::{
+        RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSimulateTransactionAccountsConfig,
+        RpcSimulateTransactionConfig, RpcTransactionConfig,
+    },
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_program::{
+    clock::Clock, instruction::Instruction, message::Message, program_pack::Pack, pubkey::Pubkey,
+    system_program, sysvar,
 };
-use solana_client::rpc_client::RpcClient;
-use solana_program::{msg, program_pack::Pack, pubkey::Pubkey, system_program, sysvar};
+use solana_remote_wallet::remote_wallet::RemoteWalletManager;
 use solana_sdk::{
-    self, commitment_config::CommitmentConfig, signature::Keypair, signature::Signer,
+    self,
+    account::{from_account, Account},
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    packet::PACKET_DATA_SIZE,
+    signature::{Signature, Signer},
     transaction::Transaction,
 };
+use solana_transaction_status::UiTransactionEncoding;
 use spl_associated_token_account::{create_associated_token_account, get_associated_token_address};
-use spl_token;
-use std::convert::TryInto;
+use spl_token::{self, state::Account as TokenAccount};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    convert::TryInto,
+    fs,
+    rc::Rc,
+    str::FromStr,
+};
 use token_vesting::{
-    instruction::{change_destination, create, init, unlock, Schedule},
-    state::{unpack_schedules, VestingScheduleHeader},
+    instruction::{
+        add_allocation, change_destination, change_split_destination, claim_allocation,
+        claim_distribution, create, create_milestones, create_native, create_pausable,
+        create_with_splits, create_with_time_basis, init, init_distributor, init_pool, pause,
+        release_milestone, remove_allocation, resume, unlock, unlock_native,
+        unlock_with_associated_destination, with_split_destinations, Schedule, VestingInstruction,
+    },
+    merkle::{leaf_hash, tree_levels, tree_proof, tree_root},
+    state::{
+        are_valid_splits, claimable_amounts, find_allocation_address,
+        find_distribution_claim_address, AccountType, Allocation, DestinationSplit,
+        DistributionClaim, DistributorHeader, PoolHeader, TimeBasis, VestingContract,
+        MAX_SPLIT_DESTINATIONS,
+    },
 };
 
+// Address of the program deployed on mainnet
+const MAINNET_PROGRAM_ID: &str = "CChTq6PthWU82YZkbveA3WDf7s97BWhBK4Vx9bmsT743";
+
+// Options controlling how a transaction is signed and what is done with it once built
+struct TransactionConfig {
+    dry_run: bool,
+    sign_only: bool,
+    dump_transaction_message: bool,
+    export_encoding: Option<String>,
+    blockhash: Option<Hash>,
+    nonce: Option<Pubkey>,
+    // Defaults to the fee payer
+    nonce_authority: Option<Box<dyn Signer>>,
+    // Used for signers that are not given, taken from the Solana CLI config
+    default_keypair_path: String,
+}
+
+impl TransactionConfig {
+    fn new(
+        matches: &ArgMatches,
+        default_keypair_path: String,
+        wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+    ) -> Result<Self, CliError> {
+        let mut transaction_config = Self {
+            dry_run: matches.is_present("dry_run"),
+            sign_only: matches.is_present(SIGN_ONLY_ARG.name),
+            dump_transaction_message: matches.is_present(DUMP_TRANSACTION_MESSAGE.name),
+            export_encoding: value_of(matches, "export_transaction"),
+            blockhash: value_of(matches, BLOCKHASH_ARG.name),
+            nonce: pubkey_of(matches, NONCE_ARG.name),
+            nonce_authority: None,
+            default_keypair_path,
+        };
+        if matches.is_present(NONCE_AUTHORITY_ARG.name) {
+            transaction_config.nonce_authority = Some(signer_of(
+                matches,
+                NONCE_AUTHORITY_ARG.name,
+                &transaction_config,
+                wallet_manager,
+            )?);
+        }
+        Ok(transaction_config)
+    }
+
+    // The transaction is handed over to other signers instead of being broadcast
+    fn is_offline(&self) -> bool {
+        self.sign_only || self.export_encoding.is_some()
+    }
+}
+
+// Resolve a signer given as a keypair file, a signer URI such as usb://ledger, prompt:// or stdin,
+// or as a pubkey. A pubkey is replaced by its presigned signature when one was given with
+// --signer, or left unsigned in offline mode.
+fn signer_of(
+    matches: &ArgMatches,
+    name: &str,
+    transaction_config: &TransactionConfig,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> Result<Box<dyn Signer>, CliError> {
+    let path = matches
+        .value_of(name)
+        .unwrap_or(&transaction_config.default_keypair_path);
+    let config = SignerFromPathConfig {
+        allow_null_signer: transaction_config.is_offline(),
+    };
+    signer_from_path_with_config(matches, path, name, wallet_manager, &config).map_err(|err| {
+        CliError::InvalidInput(format!("Could not resolve the {} signer: {}", name, err))
+    })
+}
+
+fn required_pubkey_of(matches: &ArgMatches, name: &str) -> Result<Pubkey, CliError> {
+    pubkey_of(matches, name)
+        .ok_or_else(|| CliError::InvalidInput(format!("Missing argument {}", name)))
+}
+
+// Fetch and decode a vesting contract account
+fn get_vesting_state(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    vesting_pubkey: &Pubkey,
+) -> Result<VestingContract, CliError> {
+    let account = rpc_client
+        .get_account_with_commitment(vesting_pubkey, rpc_client.commitment())?
+        .value
+        .ok_or(CliError::AccountNotFound(*vesting_pubkey))?;
+    vesting_state_of(program_id, vesting_pubkey, &account)
+}
+
+fn vesting_state_of(
+    program_id: &Pubkey,
+    vesting_pubkey: &Pubkey,
+    account: &Account,
+) -> Result<VestingContract, CliError> {
+    if account.owner != *program_id {
+        return Err(CliError::InvalidVestingAccount(*vesting_pubkey));
+    }
+    VestingContract::unpack(&account.data)
+        .map_err(|_| CliError::InvalidVestingAccount(*vesting_pubkey))
+}
+
+// Fetch and decode a treasury pool account
+fn get_pool_state(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    pool_pubkey: &Pubkey,
+) -> Result<PoolHeader, CliError> {
+    let account = rpc_client
+        .get_account_with_commitment(pool_pubkey, rpc_client.commitment())?
+        .value
+        .ok_or(CliError::AccountNotFound(*pool_pubkey))?;
+    if account.owner != *program_id {
+        return Err(CliError::InvalidPoolAccount(*pool_pubkey));
+    }
+    PoolHeader::unpack(&account.data).map_err(|_| CliError::InvalidPoolAccount(*pool_pubkey))
+}
+
+fn allocation_state_of(
+    program_id: &Pubkey,
+    allocation_pubkey: &Pubkey,
+    account: &Account,
+) -> Result<Allocation, CliError> {
+    if account.owner != *program_id {
+        return Err(CliError::InvalidPoolAccount(*allocation_pubkey));
+    }
+    Allocation::unpack(&account.data).map_err(|_| CliError::InvalidPoolAccount(*allocation_pubkey))
+}
+
+// Fetch and decode a distributor account
+fn get_distributor_state(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    distributor_pubkey: &Pubkey,
+) -> Result<DistributorHeader, CliError> {
+    let account = rpc_client
+        .get_account_with_commitment(distributor_pubkey, rpc_client.commitment())?
+        .value
+        .ok_or(CliError::AccountNotFound(*distributor_pubkey))?;
+    if account.owner != *program_id {
+        return Err(CliError::InvalidDistributorAccount(*distributor_pubkey));
+    }
+    DistributorHeader::unpack(&account.data)
+        .map_err(|_| CliError::InvalidDistributorAccount(*distributor_pubkey))
+}
+
+fn get_clock(rpc_client: &RpcClient) -> Result<Clock, CliError> {
+    let clock_account = rpc_client.get_account(&sysvar::clock::id())?;
+    from_account(&clock_account).ok_or_else(|| {
+        let err = ClientErrorKind::Custom("Invalid clock sysvar account".to_string());
+        CliError::Rpc(Box::new(err.into()))
+    })
+}
+
+// Build the transaction and sign it with the available signers, then broadcast, simulate or
+// print it
+fn process_transaction(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    instructions: &[Instruction],
+    payer: &dyn Signer,
+    signers: &[&dyn Signer],
+    transaction_config: &TransactionConfig,
+    confirm: bool,
+) -> Result<CliTransaction, CliError> {
+    let mut signers = signers.to_vec();
+    signers.push(payer);
+
+    let message = match transaction_config.nonce {
+        Some(nonce_pubkey) => {
+            let nonce_authority = transaction_config
+                .nonce_authority
+                .as_deref()
+                .unwrap_or(payer);
+            signers.push(nonce_authority);
+            Message::new_with_nonce(
+                instructions.to_vec(),
+                Some(&payer.pubkey()),
+                &nonce_pubkey,
+                &nonce_authority.pubkey(),
+            )
+        }
+        None => Message::new(instructions, Some(&payer.pubkey())),
+    };
+
+    let recent_blockhash = match (transaction_config.blockhash, transaction_config.nonce) {
+        (Some(blockhash), _) => blockhash,
+        // A durable nonce transaction uses the blockhash stored in the nonce account
+        (None, Some(nonce_pubkey)) => {
+            let invalid_nonce = |err: nonce_utils::Error| {
+                CliError::InvalidInput(format!("Invalid nonce account {}: {}", nonce_pubkey, err))
+            };
+            let nonce_account = nonce_utils::get_account_with_commitment(
+                rpc_client,
+                &nonce_pubkey,
+                rpc_client.commitment(),
+            )
+            .map_err(invalid_nonce)?;
+            nonce_utils::data_from_account(&nonce_account)
+                .map_err(invalid_nonce)?
+                .blockhash()
+        }
+        (None, None) => rpc_client.get_recent_blockhash()?.0,
+    };
+
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction.try_partial_sign(&signers, recent_blockhash)?;
+
+    if transaction_config.is_offline() {
+        return Ok(CliTransaction::SignOnly(sign_only_output(
+            &transaction,
+            transaction_config,
+        )?));
+    }
+
+    if transaction_config.dry_run {
+        return Ok(CliTransaction::Simulated(simulate(
+            rpc_client,
+            program_id,
+            &transaction,
+        )?));
+    }
+
+    let signature = if confirm {
+        rpc_client.send_and_confirm_transaction_with_spinner_and_commitment(
+            &transaction,
+            rpc_client.commitment(),
+        )
+    } else {
+        rpc_client.send_transaction(&transaction)
+    }
+    .map_err(|err| CliError::from_client_error(err, &transaction.message, program_id))?;
+    Ok(CliTransaction::Sent {
+        signature: signature.to_string(),
+    })
+}
+
+// Collect the signatures gathered so far, and optionally the transaction itself, so that it can
+// be completed by the remaining signers
+fn sign_only_output(
+    transaction: &Transaction,
+    transaction_config: &TransactionConfig,
+) -> Result<CliSignOnly, CliError> {
+    let message = &transaction.message;
+    let mut signers = vec![];
+    let mut absent_signers = vec![];
+    for (pubkey, signature) in message
+        .account_keys
+        .iter()
+        .zip(transaction.signatures.iter())
+        .take(message.header.num_required_signatures as usize)
+    {
+        if *signature == Signature::default() {
+            absent_signers.push(pubkey.to_string());
+        } else {
+            signers.push(format!("{}={}", pubkey, signature));
+        }
+    }
+
+    let transaction_message = if transaction_config.dump_transaction_message {
+        Some(base64::encode(message.serialize()))
+    } else {
+        None
+    };
+
+    let encoded_transaction = match transaction_config.export_encoding.as_ref() {
+        Some(encoding) => {
+            let serialized_transaction = bincode::serialize(transaction)?;
+            Some(match encoding.as_str() {
+                "base58" => bs58::encode(serialized_transaction).into_string(),
+                _ => base64::encode(serialized_transaction),
+            })
+        }
+        None => None,
+    };
+
+    Ok(CliSignOnly {
+        blockhash: message.recent_blockhash.to_string(),
+        signers,
+        absent_signers,
+        message: transaction_message,
+        transaction: encoded_transaction,
+    })
+}
+
+// Simulate the transaction instead of broadcasting it
+fn simulate(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    transaction: &Transaction,
+) -> Result<CliSimulation, CliError> {
+    let message = &transaction.message;
+
+    let instructions = message
+        .instructions
+        .iter()
+        .map(|instruction| {
+            let instruction_program_id =
+                message.account_keys[instruction.program_id_index as usize];
+            let data = if instruction_program_id == *program_id {
+                match VestingInstruction::unpack(&instruction.data) {
+                    Ok(vesting_instruction) => format!("{:?}", vesting_instruction),
+                    Err(_) => "invalid vesting instruction".to_string(),
+                }
+            } else {
+                format!("{} bytes", instruction.data.len())
+            };
+            let accounts = instruction
+                .accounts
+                .iter()
+                .map(|&account_index| {
+                    let account_index = account_index as usize;
+                    CliAccountMeta {
+                        pubkey: message.account_keys[account_index].to_string(),
+                        is_signer: message.is_signer(account_index),
+                        is_writable: message.is_writable(account_index),
+                    }
+                })
+                .collect();
+            CliInstruction {
+                program_id: instruction_program_id.to_string(),
+                data,
+                accounts,
+            }
+        })
+        .collect();
+
+    // Only writable accounts can see their token balance change
+    let writable_pubkeys: Vec<Pubkey> = message
+        .account_keys
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| message.is_writable(*i))
+        .map(|(_, pubkey)| *pubkey)
+        .collect();
+    let pre_accounts = rpc_client.get_multiple_accounts(&writable_pubkeys)?;
+
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        commitment: Some(rpc_client.commitment()),
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: writable_pubkeys.iter().map(Pubkey::to_string).collect(),
+        }),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = rpc_client
+        .simulate_transaction_with_config(transaction, config)?
+        .value;
+
+    let mut token_movements = vec![];
+    let post_accounts = result.accounts.unwrap_or_default();
+    for ((pubkey, pre_account), post_account) in writable_pubkeys
+        .iter()
+        .zip(pre_accounts.iter())
+        .zip(post_accounts.iter())
+    {
+        let pre_amount = pre_account.as_ref().and_then(token_amount_of).unwrap_or(0);
+        let post_amount = match post_account
+            .as_ref()
+            .and_then(|ui_account| ui_account.decode::<Account>())
+            .as_ref()
+            .and_then(token_amount_of)
+        {
+            Some(amount) => amount,
+            None => continue,
+        };
+        if pre_amount != post_amount {
+            token_movements.push(CliTokenMovement {
+                token_account: pubkey.to_string(),
+                pre_amount,
+                post_amount,
+            });
+        }
+    }
+
+    Ok(CliSimulation {
+        instructions,
+        token_movements,
+        logs: result.logs.unwrap_or_default(),
+        error: result
+            .err
+            .map(|err| CliError::from_transaction_error(err, message, program_id).to_string()),
+    })
+}
+
+fn token_amount_of(account: &Account) -> Option<u64> {
+    if account.owner != spl_token::id() {
+        return None;
+    }
+    TokenAccount::unpack(&account.data)
+        .ok()
+        .map(|token_account| token_account.amount)
+}
+
+// Derive the program account of the seed, ending with its canonical bump seed
+fn seeded_account(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    possible_seed: Option<[u8; 32]>,
+) -> Result<([u8; 32], Pubkey), CliError> {
+    match possible_seed {
+        // A given seed lets every offline signer rebuild the exact same transaction
+        Some(mut seed) => {
+            let (pubkey, bump) = Pubkey::find_program_address(&[&seed[..31]], program_id);
+            seed[31] = bump;
+            Ok((seed, pubkey))
+        }
+        None => {
+            // Find a valid seed for the program account key to be non reversible and unused
+            let mut not_found = true;
+            let mut seed: [u8; 32] = [0; 32];
+            let mut pubkey = Pubkey::new_unique();
+            while not_found {
+                seed = Pubkey::new_unique().to_bytes();
+                let program_id_bump = Pubkey::find_program_address(&[&seed[..31]], program_id);
+                pubkey = program_id_bump.0;
+                seed[31] = program_id_bump.1;
+                not_found = rpc_client
+                    .get_account_with_commitment(&pubkey, rpc_client.commitment())?
+                    .value
+                    .is_some();
+            }
+            Ok((seed, pubkey))
+        }
+    }
+}
+
 // Lock the vesting contract
 fn command_create_svc(
     rpc_client: RpcClient,
     program_id: Pubkey,
-    payer: Keypair,
-    source_token_owner: Keypair,
+    payer: Box<dyn Signer>,
+    source_token_owner: Box<dyn Signer>,
     possible_source_token_pubkey: Option<Pubkey>,
     destination_token_pubkey: Pubkey,
-    mint_address: Pubkey,
+    // No mint for a native contract, whose vesting account holds lamports
+    opt_mint_address: Option<Pubkey>,
+    time_basis: TimeBasis,
+    opt_approver: Option<Pubkey>,
+    opt_pause_authority: Option<Pubkey>,
+    split_destinations: Vec<DestinationSplit>,
     schedules: Vec<Schedule>,
+    possible_vesting_seed: Option<[u8; 32]>,
     confirm: bool,
-) {
-    // If no source token account was given, use the associated source account
-    let source_token_pubkey = match possible_source_token_pubkey {
-        None => get_associated_token_address(&source_token_owner.pubkey(), &mint_address),
-        _ => possible_source_token_pubkey.unwrap(),
-    };
-
-    // Find a valid seed for the vesting program account key to be non reversible and unused
-    let mut not_found = true;
-    let mut vesting_seed: [u8; 32] = [0; 32];
-    let mut vesting_pubkey = Pubkey::new_unique();
-    while not_found {
-        vesting_seed = Pubkey::new_unique().to_bytes();
-        let program_id_bump = Pubkey::find_program_address(&[&vesting_seed[..31]], &program_id);
-        vesting_pubkey = program_id_bump.0;
-        vesting_seed[31] = program_id_bump.1;
-        not_found = match rpc_client.get_account(&vesting_pubkey) {
-            Ok(_) => true,
-            Err(_) => false,
-        }
-    }
+    transaction_config: TransactionConfig,
+) -> Result<CliCreatedContract, CliError> {
+    let (vesting_seed, vesting_pubkey) =
+        seeded_account(&rpc_client, &program_id, possible_vesting_seed)?;
 
-    let vesting_token_pubkey = get_associated_token_address(&vesting_pubkey, &mint_address);
+    let number_of_schedules = schedules.len() as u32;
+    let mut instructions = vec![init(
+        &system_program::id(),
+        &sysvar::rent::id(),
+        &program_id,
+        &payer.pubkey(),
+        &vesting_pubkey,
+        vesting_seed,
+        number_of_schedules,
+    )?];
 
-    let instructions = [
-        init(
-            &system_program::id(),
-            &sysvar::rent::id(),
-            &program_id,
-            &payer.pubkey(),
-            &vesting_pubkey,
-            vesting_seed,
-            schedules.len() as u32,
-        )
-        .unwrap(),
-        create_associated_token_account(
-            &source_token_owner.pubkey(),
-            &vesting_pubkey,
-            &mint_address,
-        ),
-        create(
+    match opt_mint_address {
+        // The source owner funds the vesting account of a native contract directly
+        None => instructions.push(create_native(
             &program_id,
-            &spl_token::id(),
+            &system_program::id(),
             &vesting_pubkey,
-            &vesting_token_pubkey,
             &source_token_owner.pubkey(),
-            &source_token_pubkey,
             &destination_token_pubkey,
-            &mint_address,
+            time_basis,
+            opt_approver.as_ref(),
+            opt_pause_authority.as_ref(),
             schedules,
             vesting_seed,
-        )
-        .unwrap(),
-    ];
-
-    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+        )?),
+        Some(mint_address) => {
+            // If no source token account was given, use the associated source account
+            let source_token_pubkey = match possible_source_token_pubkey {
+                None => get_associated_token_address(&source_token_owner.pubkey(), &mint_address),
+                Some(source_token_pubkey) => source_token_pubkey,
+            };
 
-    let recent_blockhash = rpc_client.get_recent_blockhash().unwrap().0;
-    transaction.sign(&[&payer], recent_blockhash);
+            let vesting_token_pubkey = get_associated_token_address(&vesting_pubkey, &mint_address);
 
-    msg!(
-        "\nThe seed of the contract is: {:?}",
-        Pubkey::new_from_array(vesting_seed)
-    );
-    msg!("Please write it down as it is needed to interact with the contract!");
+            // Contracts on unix timestamps keep the original instruction, known by every deployment
+            let create_instruction = match (time_basis, opt_approver, opt_pause_authority) {
+                _ if !split_destinations.is_empty() => create_with_splits(
+                    &program_id,
+                    &spl_token::id(),
+                    &vesting_pubkey,
+                    &vesting_token_pubkey,
+                    &source_token_owner.pubkey(),
+                    &source_token_pubkey,
+                    &destination_token_pubkey,
+                    &mint_address,
+                    time_basis,
+                    opt_approver.as_ref(),
+                    opt_pause_authority.as_ref(),
+                    split_destinations,
+                    schedules,
+                    vesting_seed,
+                )?,
+                (_, _, Some(pause_authority)) => create_pausable(
+                    &program_id,
+                    &spl_token::id(),
+                    &vesting_pubkey,
+                    &vesting_token_pubkey,
+                    &source_token_owner.pubkey(),
+                    &source_token_pubkey,
+                    &destination_token_pubkey,
+                    &mint_address,
+                    time_basis,
+                    opt_approver.as_ref(),
+                    &pause_authority,
+                    schedules,
+                    vesting_seed,
+                )?,
+                (_, Some(approver), None) => create_milestones(
+                    &program_id,
+                    &spl_token::id(),
+                    &vesting_pubkey,
+                    &vesting_token_pubkey,
+                    &source_token_owner.pubkey(),
+                    &source_token_pubkey,
+                    &destination_token_pubkey,
+                    &mint_address,
+                    time_basis,
+                    &approver,
+                    schedules,
+                    vesting_seed,
+                )?,
+                (TimeBasis::UnixTimestamp, None, None) => create(
+                    &program_id,
+                    &spl_token::id(),
+                    &vesting_pubkey,
+                    &vesting_token_pubkey,
+                    &source_token_owner.pubkey(),
+                    &source_token_pubkey,
+                    &destination_token_pubkey,
+                    &mint_address,
+                    schedules,
+                    vesting_seed,
+                )?,
+                (_, None, None) => create_with_time_basis(
+                    &program_id,
+                    &spl_token::id(),
+                    &vesting_pubkey,
+                    &vesting_token_pubkey,
+                    &source_token_owner.pubkey(),
+                    &source_token_pubkey,
+                    &destination_token_pubkey,
+                    &mint_address,
+                    time_basis,
+                    schedules,
+                    vesting_seed,
+                )?,
+            };
+            instructions.push(create_associated_token_account(
+                &source_token_owner.pubkey(),
+                &vesting_pubkey,
+                &mint_address,
+            ));
+            instructions.push(create_instruction);
+        }
+    }
 
-    msg!("The vesting account pubkey: {:?}", vesting_pubkey,);
+    let transaction = process_transaction(
+        &rpc_client,
+        &program_id,
+        &instructions,
+        payer.as_ref(),
+        &[source_token_owner.as_ref()],
+        &transaction_config,
+        confirm,
+    )?;
 
-    if confirm {
-        rpc_client
-            .send_and_confirm_transaction_with_spinner_and_commitment(
-                &transaction,
-                CommitmentConfig::finalized(),
-            )
-            .unwrap();
-    } else {
-        rpc_client.send_transaction(&transaction).unwrap();
-    }
+    Ok(CliCreatedContract {
+        seed: Pubkey::new_from_array(vesting_seed).to_string(),
+        vesting_account: vesting_pubkey.to_string(),
+        transaction,
+    })
 }
 
 fn command_unlock_svc(
     rpc_client: RpcClient,
     program_id: Pubkey,
     vesting_seed: [u8; 32],
-    payer: Keypair,
-) {
+    opt_destination_pubkey: Option<Pubkey>,
+    payer: Box<dyn Signer>,
+    transaction_config: TransactionConfig,
+) -> Result<CliTransaction, CliError> {
     // Find the non reversible public key for the vesting contract via the seed
     let (vesting_pubkey, _) = Pubkey::find_program_address(&[&vesting_seed[..31]], &program_id);
 
-    let packed_state = rpc_client.get_account_data(&vesting_pubkey).unwrap();
-    let header_state =
-        VestingScheduleHeader::unpack(&packed_state[..VestingScheduleHeader::LEN]).unwrap();
+    let contract = get_vesting_state(&rpc_client, &program_id, &vesting_pubkey)?;
+    let header_state = &contract.header;
     let destination_token_pubkey = header_state.destination_address;
 
+    // A transaction signed offline may be meant for a later release, only check when sending now
+    if !transaction_config.is_offline()
+        && contract.vested_at(contract.current_time(&get_clock(&rpc_client)?)) == 0
+    {
+        return Err(CliError::NotYetVested(vesting_pubkey));
+    }
+
+    if header_state.is_native {
+        let unlock_instruction = unlock_native(
+            &program_id,
+            &system_program::id(),
+            &sysvar::clock::id(),
+            &vesting_pubkey,
+            &destination_token_pubkey,
+            vesting_seed,
+        )?;
+        return process_transaction(
+            &rpc_client,
+            &program_id,
+            &[unlock_instruction],
+            payer.as_ref(),
+            &[],
+            &transaction_config,
+            false,
+        );
+    }
+
     let vesting_token_pubkey =
         get_associated_token_address(&vesting_pubkey, &header_state.mint_address);
 
-    let unlock_instruction = unlock(
-        &program_id,
-        &spl_token::id(),
-        &sysvar::clock::id(),
-        &vesting_pubkey,
-        &vesting_token_pubkey,
-        &destination_token_pubkey,
-        vesting_seed,
-    )
-    .unwrap();
-
-    let mut transaction = Transaction::new_with_payer(&[unlock_instruction], Some(&payer.pubkey()));
+    let destination_exists = rpc_client
+        .get_account_with_commitment(&destination_token_pubkey, rpc_client.commitment())?
+        .value
+        .is_some();
 
-    let recent_blockhash = rpc_client.get_recent_blockhash().unwrap().0;
-    transaction.sign(&[&payer], recent_blockhash);
+    let unlock_instruction = if destination_exists {
+        unlock(
+            &program_id,
+            &spl_token::id(),
+            &sysvar::clock::id(),
+            &vesting_pubkey,
+            &vesting_token_pubkey,
+            &destination_token_pubkey,
+            vesting_seed,
+        )?
+    } else {
+        // The destination was closed or never created, the payer funds a new associated account
+        let destination_pubkey = opt_destination_pubkey.ok_or_else(|| {
+            CliError::InvalidInput(format!(
+                "The destination token account {} does not exist, give the wallet owning it \
+                with --destination_address to create it",
+                destination_token_pubkey
+            ))
+        })?;
+        if get_associated_token_address(&destination_pubkey, &header_state.mint_address)
+            != destination_token_pubkey
+        {
+            return Err(CliError::InvalidInput(format!(
+                "The destination token account {} is not the associated token account of {}",
+                destination_token_pubkey, destination_pubkey
+            )));
+        }
+        unlock_with_associated_destination(
+            &program_id,
+            &spl_token::id(),
+            &sysvar::clock::id(),
+            &system_program::id(),
+            &spl_associated_token_account::id(),
+            &vesting_pubkey,
+            &vesting_token_pubkey,
+            &destination_pubkey,
+            &header_state.mint_address,
+            &payer.pubkey(),
+            vesting_seed,
+        )?
+    };
+    let unlock_instruction =
+        with_split_destinations(unlock_instruction, &header_state.split_destinations);
 
-    rpc_client.send_transaction(&transaction).unwrap();
+    process_transaction(
+        &rpc_client,
+        &program_id,
+        &[unlock_instruction],
+        payer.as_ref(),
+        &[],
+        &transaction_config,
+        false,
+    )
 }
 
 fn command_change_destination(
     rpc_client: RpcClient,
     program_id: Pubkey,
-    destination_token_account_owner: Keypair,
+    destination_token_account_owner: Box<dyn Signer>,
     opt_new_destination_account: Option<Pubkey>,
     opt_new_destination_token_account: Option<Pubkey>,
     vesting_seed: [u8; 32],
-    payer: Keypair,
-) {
+    payer: Box<dyn Signer>,
+    transaction_config: TransactionConfig,
+) -> Result<CliTransaction, CliError> {
     // Find the non reversible public key for the vesting contract via the seed
     let (vesting_pubkey, _) = Pubkey::find_program_address(&[&vesting_seed[..31]], &program_id);
 
-    let packed_state = rpc_client.get_account_data(&vesting_pubkey).unwrap();
-    let state_header =
-        VestingScheduleHeader::unpack(&packed_state[..VestingScheduleHeader::LEN]).unwrap();
+    let state_header = get_vesting_state(&rpc_client, &program_id, &vesting_pubkey)?.header;
     let destination_token_pubkey = state_header.destination_address;
 
-    let new_destination_token_account = match opt_new_destination_token_account {
-        None => get_associated_token_address(
-            &opt_new_destination_account.unwrap(),
-            &state_header.mint_address,
-        ),
-        Some(new_destination_token_account) => new_destination_token_account,
+    let new_destination_token_account = match (
+        opt_new_destination_token_account,
+        opt_new_destination_account,
+    ) {
+        // The destination of a native contract is a wallet
+        (_, Some(new_destination_account)) if state_header.is_native => new_destination_account,
+        (_, None) if state_header.is_native => {
+            return Err(CliError::InvalidInput(
+                "Missing argument new_destination_address".to_string(),
+            ))
+        }
+        (Some(new_destination_token_account), _) => new_destination_token_account,
+        (None, Some(new_destination_account)) => {
+            get_associated_token_address(&new_destination_account, &state_header.mint_address)
+        }
+        (None, None) => {
+            return Err(CliError::InvalidInput(
+                "Missing argument new_destination_address or new_destination_token_address"
+                    .to_string(),
+            ))
+        }
     };
 
     let unlock_instruction = change_destination(
@@ -179,122 +806,2104 @@ fn command_change_destination(
         &destination_token_pubkey,
         &new_destination_token_account,
         vesting_seed,
+    )?;
+
+    process_transaction(
+        &rpc_client,
+        &program_id,
+        &[unlock_instruction],
+        payer.as_ref(),
+        &[destination_token_account_owner.as_ref()],
+        &transaction_config,
+        false,
     )
-    .unwrap();
+}
+
+// Move a split destination of a contract to another token account of its mint, signed by the
+// owner of the current split destination token account
+#[allow(clippy::too_many_arguments)]
+fn command_change_split_destination(
+    rpc_client: RpcClient,
+    program_id: Pubkey,
+    split_token_account_owner: Box<dyn Signer>,
+    split_token_account: Pubkey,
+    new_split_token_account: Pubkey,
+    vesting_seed: [u8; 32],
+    payer: Box<dyn Signer>,
+    transaction_config: TransactionConfig,
+) -> Result<CliTransaction, CliError> {
+    let (vesting_pubkey, _) = Pubkey::find_program_address(&[&vesting_seed[..31]], &program_id);
 
-    let mut transaction = Transaction::new_with_payer(&[unlock_instruction], Some(&payer.pubkey()));
+    let state_header = get_vesting_state(&rpc_client, &program_id, &vesting_pubkey)?.header;
+    if !state_header
+        .split_destinations
+        .iter()
+        .any(|split| split.token_address == split_token_account)
+    {
+        return Err(CliError::InvalidInput(format!(
+            "{} is not a split destination of the contract",
+            split_token_account
+        )));
+    }
 
-    let recent_blockhash = rpc_client.get_recent_blockhash().unwrap().0;
-    transaction.sign(
-        &[&payer, &destination_token_account_owner],
-        recent_blockhash,
-    );
+    let change_split_instruction = change_split_destination(
+        &program_id,
+        &vesting_pubkey,
+        &split_token_account_owner.pubkey(),
+        &split_token_account,
+        &new_split_token_account,
+        vesting_seed,
+    )?;
 
-    rpc_client.send_transaction(&transaction).unwrap();
+    process_transaction(
+        &rpc_client,
+        &program_id,
+        &[change_split_instruction],
+        payer.as_ref(),
+        &[split_token_account_owner.as_ref()],
+        &transaction_config,
+        false,
+    )
 }
 
-fn command_info(
+// Mark a milestone of a contract as releasable, signed by the approver of the contract
+fn command_release_milestone(
     rpc_client: RpcClient,
-    rpc_url: String,
     program_id: Pubkey,
+    approver: Box<dyn Signer>,
     vesting_seed: [u8; 32],
-) {
-    msg!("\n---------------VESTING--CONTRACT--INFO-----------------\n");
-    msg!("RPC URL: {:?}", &rpc_url);
-    msg!("Program ID: {:?}", &program_id);
-    msg!("Vesting Seed: {:?}", Pubkey::new_from_array(vesting_seed));
+    schedule_index: u32,
+    payer: Box<dyn Signer>,
+    transaction_config: TransactionConfig,
+) -> Result<CliTransaction, CliError> {
+    // Find the non reversible public key for the vesting contract via the seed
+    let (vesting_pubkey, _) = Pubkey::find_program_address(&[&vesting_seed[..31]], &program_id);
+
+    let contract = get_vesting_state(&rpc_client, &program_id, &vesting_pubkey)?;
+    if contract.header.approver != Some(approver.pubkey()) {
+        return Err(CliError::InvalidInput(format!(
+            "{} is not the approver of the contract",
+            approver.pubkey()
+        )));
+    }
+    if schedule_index as usize >= contract.schedules.len() {
+        return Err(CliError::InvalidInput(format!(
+            "The contract has no schedule {}",
+            schedule_index
+        )));
+    }
 
+    let release_milestone_instruction = release_milestone(
+        &program_id,
+        &vesting_pubkey,
+        &approver.pubkey(),
+        schedule_index,
+    )?;
+
+    process_transaction(
+        &rpc_client,
+        &program_id,
+        &[release_milestone_instruction],
+        payer.as_ref(),
+        &[approver.as_ref()],
+        &transaction_config,
+        false,
+    )
+}
+
+// Pause a pausable contract, or resume it if it is paused, signed by its pause authority
+#[allow(clippy::too_many_arguments)]
+fn command_set_paused(
+    rpc_client: RpcClient,
+    program_id: Pubkey,
+    pause_authority: Box<dyn Signer>,
+    vesting_seed: [u8; 32],
+    paused: bool,
+    shift_release_times: bool,
+    payer: Box<dyn Signer>,
+    transaction_config: TransactionConfig,
+) -> Result<CliTransaction, CliError> {
     // Find the non reversible public key for the vesting contract via the seed
     let (vesting_pubkey, _) = Pubkey::find_program_address(&[&vesting_seed[..31]], &program_id);
-    msg!("Vesting Account Pubkey: {:?}", &vesting_pubkey);
 
-    let packed_state = rpc_client.get_account_data(&vesting_pubkey).unwrap();
-    let state_header =
-        VestingScheduleHeader::unpack(&packed_state[..VestingScheduleHeader::LEN]).unwrap();
-    let vesting_token_pubkey =
-        get_associated_token_address(&vesting_pubkey, &state_header.mint_address);
-    msg!("Vesting Token Account Pubkey: {:?}", &vesting_token_pubkey);
-    msg!("Initialized: {:?}", &state_header.is_initialized);
-    msg!("Mint Address: {:?}", &state_header.mint_address);
-    msg!(
-        "Destination Token Address: {:?}",
-        &state_header.destination_address
-    );
+    let contract = get_vesting_state(&rpc_client, &program_id, &vesting_pubkey)?;
+    if contract.header.pause_authority != Some(pause_authority.pubkey()) {
+        return Err(CliError::InvalidInput(format!(
+            "{} is not the pause authority of the contract",
+            pause_authority.pubkey()
+        )));
+    }
+    if contract.header.paused_at.is_some() == paused {
+        return Err(CliError::InvalidInput(format!(
+            "The contract is already {}",
+            if paused { "paused" } else { "running" }
+        )));
+    }
+
+    let instruction = if paused {
+        pause(&program_id, &vesting_pubkey, &pause_authority.pubkey())?
+    } else {
+        resume(
+            &program_id,
+            &vesting_pubkey,
+            &pause_authority.pubkey(),
+            shift_release_times,
+        )?
+    };
+
+    process_transaction(
+        &rpc_client,
+        &program_id,
+        &[instruction],
+        payer.as_ref(),
+        &[pause_authority.as_ref()],
+        &transaction_config,
+        false,
+    )
+}
+
+// Create a treasury pool and its associated token account, funded by the payer
+#[allow(clippy::too_many_arguments)]
+fn command_create_pool(
+    rpc_client: RpcClient,
+    program_id: Pubkey,
+    payer: Box<dyn Signer>,
+    mint_address: Pubkey,
+    opt_pool_authority: Option<Pubkey>,
+    time_basis: TimeBasis,
+    possible_pool_seed: Option<[u8; 32]>,
+    transaction_config: TransactionConfig,
+) -> Result<CliCreatedPool, CliError> {
+    let (pool_seed, pool_pubkey) = seeded_account(&rpc_client, &program_id, possible_pool_seed)?;
+    let pool_token_pubkey = get_associated_token_address(&pool_pubkey, &mint_address);
+
+    let instructions = [
+        init_pool(
+            &program_id,
+            &system_program::id(),
+            &sysvar::rent::id(),
+            &payer.pubkey(),
+            &pool_pubkey,
+            &mint_address,
+            &opt_pool_authority.unwrap_or_else(|| payer.pubkey()),
+            time_basis,
+            pool_seed,
+        )?,
+        create_associated_token_account(&payer.pubkey(), &pool_pubkey, &mint_address),
+    ];
+
+    let transaction = process_transaction(
+        &rpc_client,
+        &program_id,
+        &instructions,
+        payer.as_ref(),
+        &[],
+        &transaction_config,
+        true,
+    )?;
 
-    let schedules = unpack_schedules(&packed_state[VestingScheduleHeader::LEN..]).unwrap();
+    Ok(CliCreatedPool {
+        seed: Pubkey::new_from_array(pool_seed).to_string(),
+        pool_account: pool_pubkey.to_string(),
+        pool_token_account: pool_token_pubkey.to_string(),
+        transaction,
+    })
+}
 
-    for i in 0..schedules.len() {
-        msg!("\nSCHEDULE {:?}", i);
-        msg!("Release Height: {:?}", &schedules[i].release_time);
-        msg!("Amount: {:?}", &schedules[i].amount);
+// The destination of an allocation is a token account of the mint of the pool
+fn allocation_destination(
+    pool: &PoolHeader,
+    opt_destination_pubkey: Option<Pubkey>,
+    opt_destination_token_pubkey: Option<Pubkey>,
+) -> Result<Pubkey, CliError> {
+    match (opt_destination_token_pubkey, opt_destination_pubkey) {
+        (Some(destination_token_pubkey), _) => Ok(destination_token_pubkey),
+        (None, Some(destination_pubkey)) => Ok(get_associated_token_address(
+            &destination_pubkey,
+            &pool.mint_address,
+        )),
+        (None, None) => Err(CliError::InvalidInput(
+            "Missing argument destination_address or destination_token_address".to_string(),
+        )),
     }
 }
 
-fn main() {
-    let matches = App::new(crate_name!())
-        .about(crate_description!())
-        .version(crate_version!())
-        .setting(AppSettings::SubcommandRequiredElseHelp)
-        .arg(
-            Arg::with_name("verbose")
-                .long("verbose")
-                .short("v")
-                .takes_value(false)
-                .global(true)
-                .help("Show additional information"),
-        )
-        .arg(
-            Arg::with_name("rpc_url")
-                .long("url")
-                .value_name("URL")
-                .validator(is_url)
-                .takes_value(true)
-                .global(true)
-                .help(
-                    "Specify the url of the rpc client (solana network).",
-                ),
-        )
-        .arg(
-            Arg::with_name("program_id")
-                .long("program_id")
-                .value_name("ADDRESS")
-                .validator(is_pubkey)
+// Allocate tokens of a pool to a beneficiary, signed by the pool authority
+#[allow(clippy::too_many_arguments)]
+fn command_add_allocation(
+    rpc_client: RpcClient,
+    program_id: Pubkey,
+    pool_authority: Box<dyn Signer>,
+    pool_seed: [u8; 32],
+    opt_destination_pubkey: Option<Pubkey>,
+    opt_destination_token_pubkey: Option<Pubkey>,
+    schedules: Vec<Schedule>,
+    payer: Box<dyn Signer>,
+    transaction_config: TransactionConfig,
+) -> Result<CliTransaction, CliError> {
+    let (pool_pubkey, _) = Pubkey::find_program_address(&[&pool_seed[..31]], &program_id);
+
+    let pool = get_pool_state(&rpc_client, &program_id, &pool_pubkey)?;
+    if pool.pool_authority != pool_authority.pubkey() {
+        return Err(CliError::InvalidInput(format!(
+            "{} is not the authority of the pool",
+            pool_authority.pubkey()
+        )));
+    }
+    let pool_token_pubkey = get_associated_token_address(&pool_pubkey, &pool.mint_address);
+    let destination_token_pubkey =
+        allocation_destination(&pool, opt_destination_pubkey, opt_destination_token_pubkey)?;
+
+    // Each beneficiary has a single allocation in a pool, the pool balance being checked again
+    // by the program
+    if !transaction_config.is_offline() {
+        let (allocation_pubkey, _) =
+            find_allocation_address(&program_id, &pool_pubkey, &destination_token_pubkey);
+        if rpc_client
+            .get_account_with_commitment(&allocation_pubkey, rpc_client.commitment())?
+            .value
+            .is_some()
+        {
+            return Err(CliError::InvalidInput(format!(
+                "{} already has an allocation in the pool, remove it first",
+                destination_token_pubkey
+            )));
+        }
+        let pool_token_balance = rpc_client
+            .get_account(&pool_token_pubkey)
+            .ok()
+            .as_ref()
+            .and_then(token_amount_of)
+            .unwrap_or(0);
+        let unallocated_amount = pool_token_balance.saturating_sub(pool.allocated_amount);
+        if schedules.iter().map(|s| s.amount as u128).sum::<u128>() > unallocated_amount as u128 {
+            return Err(CliError::InvalidInput(format!(
+                "The pool only holds {} tokens that are not allocated yet",
+                unallocated_amount
+            )));
+        }
+    }
+
+    let add_allocation_instruction = add_allocation(
+        &program_id,
+        &system_program::id(),
+        &payer.pubkey(),
+        &pool_pubkey,
+        &pool_authority.pubkey(),
+        &pool_token_pubkey,
+        &destination_token_pubkey,
+        schedules,
+    )?;
+
+    process_transaction(
+        &rpc_client,
+        &program_id,
+        &[add_allocation_instruction],
+        payer.as_ref(),
+        &[pool_authority.as_ref()],
+        &transaction_config,
+        false,
+    )
+}
+
+// Remove an allocation of a pool, signed by the pool authority
+#[allow(clippy::too_many_arguments)]
+fn command_remove_allocation(
+    rpc_client: RpcClient,
+    program_id: Pubkey,
+    pool_authority: Box<dyn Signer>,
+    pool_seed: [u8; 32],
+    opt_destination_pubkey: Option<Pubkey>,
+    opt_destination_token_pubkey: Option<Pubkey>,
+    opt_rent_receiver: Option<Pubkey>,
+    payer: Box<dyn Signer>,
+    transaction_config: TransactionConfig,
+) -> Result<CliTransaction, CliError> {
+    let (pool_pubkey, _) = Pubkey::find_program_address(&[&pool_seed[..31]], &program_id);
+
+    let pool = get_pool_state(&rpc_client, &program_id, &pool_pubkey)?;
+    if pool.pool_authority != pool_authority.pubkey() {
+        return Err(CliError::InvalidInput(format!(
+            "{} is not the authority of the pool",
+            pool_authority.pubkey()
+        )));
+    }
+    let destination_token_pubkey =
+        allocation_destination(&pool, opt_destination_pubkey, opt_destination_token_pubkey)?;
+
+    let remove_allocation_instruction = remove_allocation(
+        &program_id,
+        &pool_pubkey,
+        &pool_authority.pubkey(),
+        &destination_token_pubkey,
+        &opt_rent_receiver.unwrap_or_else(|| payer.pubkey()),
+    )?;
+
+    process_transaction(
+        &rpc_client,
+        &program_id,
+        &[remove_allocation_instruction],
+        payer.as_ref(),
+        &[pool_authority.as_ref()],
+        &transaction_config,
+        false,
+    )
+}
+
+// Claim the matured schedules of an allocation, which anyone may do for the beneficiary
+fn command_claim_allocation(
+    rpc_client: RpcClient,
+    program_id: Pubkey,
+    pool_seed: [u8; 32],
+    opt_destination_pubkey: Option<Pubkey>,
+    opt_destination_token_pubkey: Option<Pubkey>,
+    payer: Box<dyn Signer>,
+    transaction_config: TransactionConfig,
+) -> Result<CliTransaction, CliError> {
+    let (pool_pubkey, _) = Pubkey::find_program_address(&[&pool_seed[..31]], &program_id);
+
+    let pool = get_pool_state(&rpc_client, &program_id, &pool_pubkey)?;
+    let destination_token_pubkey =
+        allocation_destination(&pool, opt_destination_pubkey, opt_destination_token_pubkey)?;
+
+    // A transaction signed offline may be meant for a later release, only check when sending now
+    if !transaction_config.is_offline() {
+        let (allocation_pubkey, _) =
+            find_allocation_address(&program_id, &pool_pubkey, &destination_token_pubkey);
+        let account = rpc_client
+            .get_account_with_commitment(&allocation_pubkey, rpc_client.commitment())?
+            .value
+            .ok_or(CliError::AccountNotFound(allocation_pubkey))?;
+        let allocation = allocation_state_of(&program_id, &allocation_pubkey, &account)?;
+        let current_time = pool.time_basis.current_time(&get_clock(&rpc_client)?);
+        if claimable_amounts(&allocation.schedules, current_time).claimable == 0 {
+            return Err(CliError::NotYetVested(allocation_pubkey));
+        }
+    }
+
+    let claim_allocation_instruction = claim_allocation(
+        &program_id,
+        &spl_token::id(),
+        &sysvar::clock::id(),
+        &pool_pubkey,
+        &get_associated_token_address(&pool_pubkey, &pool.mint_address),
+        &destination_token_pubkey,
+        pool_seed,
+    )?;
+
+    process_transaction(
+        &rpc_client,
+        &program_id,
+        &[claim_allocation_instruction],
+        payer.as_ref(),
+        &[],
+        &transaction_config,
+        false,
+    )
+}
+
+fn command_pool_info(
+    rpc_client: RpcClient,
+    program_id: Pubkey,
+    pool_seed: [u8; 32],
+) -> Result<CliPool, CliError> {
+    let (pool_pubkey, _) = Pubkey::find_program_address(&[&pool_seed[..31]], &program_id);
+
+    let pool = get_pool_state(&rpc_client, &program_id, &pool_pubkey)?;
+    let pool_token_pubkey = get_associated_token_address(&pool_pubkey, &pool.mint_address);
+    let pool_token_balance = rpc_client
+        .get_account(&pool_token_pubkey)
+        .ok()
+        .as_ref()
+        .and_then(token_amount_of)
+        .unwrap_or(0);
+    let current_time = pool.time_basis.current_time(&get_clock(&rpc_client)?);
+
+    // Allocation accounts start with the address of their pool, followed by the destination
+    // address and the account type
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &pool_pubkey.to_bytes())),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                64,
+                vec![AccountType::Allocation as u8],
+            )),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(rpc_client.commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let allocations = rpc_client
+        .get_program_accounts_with_config(&program_id, config)?
+        .iter()
+        .filter_map(|(allocation_pubkey, account)| {
+            let allocation = allocation_state_of(&program_id, allocation_pubkey, account).ok()?;
+            Some(CliAllocation {
+                allocation_account: allocation_pubkey.to_string(),
+                destination_token_address: allocation.header.destination_address.to_string(),
+                claimable_now: claimable_amounts(&allocation.schedules, current_time).claimable,
+                schedules: allocation
+                    .schedules
+                    .iter()
+                    .map(|s| CliSchedule {
+                        release_time: s.release_time,
+                        amount: s.amount,
+                    })
+                    .collect(),
+            })
+        })
+        .collect();
+
+    Ok(CliPool {
+        program_id: program_id.to_string(),
+        seed: Pubkey::new_from_array(pool_seed).to_string(),
+        pool_account: pool_pubkey.to_string(),
+        pool_token_account: pool_token_pubkey.to_string(),
+        pool_authority: pool.pool_authority.to_string(),
+        mint_address: pool.mint_address.to_string(),
+        time_basis: time_basis_name(pool.time_basis).to_string(),
+        pool_token_balance,
+        allocated_amount: pool.allocated_amount,
+        current_time,
+        allocations,
+    })
+}
+
+// Hashes of the distribution trees are base58 encoded, like the pubkeys
+fn decode_hash(value: &str) -> Result<[u8; 32], CliError> {
+    bs58::decode(value)
+        .into_vec()
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| CliError::InvalidInput(format!("Invalid hash {}", value)))
+}
+
+fn read_distribution_tree(path: &str) -> Result<CliDistributionTree, CliError> {
+    fs::read_to_string(path)
+        .map_err(|err| err.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|err| err.to_string()))
+        .map_err(|err| CliError::InvalidInput(format!("Cannot read {}: {}", path, err)))
+}
+
+// Build the Merkle tree of a distribution from a CSV file of WALLET,AMOUNT lines, writing the
+// root and the proof of every claimant
+fn command_build_distribution_tree(
+    csv_path: &str,
+    tree_path: &str,
+) -> Result<CliDistributionTreeSummary, CliError> {
+    let csv = fs::read_to_string(csv_path)
+        .map_err(|err| CliError::InvalidInput(format!("Cannot read {}: {}", csv_path, err)))?;
+
+    let mut claims: Vec<(Pubkey, u64)> = vec![];
+    let mut claimants = HashSet::new();
+    for (i, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let claim = line.split_once(',').and_then(|(wallet, amount)| {
+            Some((
+                Pubkey::from_str(wallet.trim()).ok()?,
+                amount.trim().parse::<u64>().ok()?,
+            ))
+        });
+        let (claimant, amount) = match claim {
+            Some(claim) => claim,
+            // The first line may be a header
+            None if i == 0 => continue,
+            None => {
+                return Err(CliError::InvalidInput(format!(
+                    "Invalid line {} of {}, expected WALLET,AMOUNT",
+                    i + 1,
+                    csv_path
+                )))
+            }
+        };
+        // A claimant has a single claim account per distributor
+        if !claimants.insert(claimant) {
+            return Err(CliError::InvalidInput(format!(
+                "{} appears more than once in {}",
+                claimant, csv_path
+            )));
+        }
+        claims.push((claimant, amount));
+    }
+
+    let total_amount = claims
+        .iter()
+        .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+        .ok_or_else(|| CliError::InvalidInput("The total amount overflows u64".to_string()))?;
+    let levels = tree_levels(
+        claims
+            .iter()
+            .map(|(claimant, amount)| leaf_hash(claimant, *amount))
+            .collect(),
+    );
+    let merkle_root = tree_root(&levels)
+        .ok_or_else(|| CliError::InvalidInput(format!("{} holds no claim", csv_path)))?;
+
+    let tree = CliDistributionTree {
+        merkle_root: bs58::encode(merkle_root).into_string(),
+        total_amount,
+        claims: claims
+            .iter()
+            .enumerate()
+            .map(|(i, (claimant, amount))| CliDistributionTreeClaim {
+                claimant: claimant.to_string(),
+                amount: *amount,
+                proof: tree_proof(&levels, i)
+                    .iter()
+                    .map(|hash| bs58::encode(hash).into_string())
+                    .collect(),
+            })
+            .collect(),
+    };
+    let json = serde_json::to_string_pretty(&tree)?;
+    fs::write(tree_path, json)
+        .map_err(|err| CliError::InvalidInput(format!("Cannot write {}: {}", tree_path, err)))?;
+
+    Ok(CliDistributionTreeSummary {
+        merkle_root: tree.merkle_root,
+        total_amount,
+        claimants: claims.len(),
+        tree: tree_path.to_string(),
+    })
+}
+
+// Create a distributor releasing the claims of a distribution tree
+#[allow(clippy::too_many_arguments)]
+fn command_create_distributor(
+    rpc_client: RpcClient,
+    program_id: Pubkey,
+    payer: Box<dyn Signer>,
+    mint_address: Pubkey,
+    tree: CliDistributionTree,
+    time_basis: TimeBasis,
+    start_time: u64,
+    cliff_time: u64,
+    end_time: u64,
+    possible_distributor_seed: Option<[u8; 32]>,
+    transaction_config: TransactionConfig,
+) -> Result<CliCreatedDistributor, CliError> {
+    if !(start_time <= cliff_time && cliff_time <= end_time) {
+        return Err(CliError::InvalidInput(
+            "The cliff time must lie between the start time and the end time".to_string(),
+        ));
+    }
+    let (distributor_seed, distributor_pubkey) =
+        seeded_account(&rpc_client, &program_id, possible_distributor_seed)?;
+    let distributor_token_pubkey = get_associated_token_address(&distributor_pubkey, &mint_address);
+
+    let instructions = [
+        init_distributor(
+            &program_id,
+            &system_program::id(),
+            &sysvar::rent::id(),
+            &payer.pubkey(),
+            &distributor_pubkey,
+            &mint_address,
+            decode_hash(&tree.merkle_root)?,
+            time_basis,
+            start_time,
+            cliff_time,
+            end_time,
+            tree.total_amount,
+            distributor_seed,
+        )?,
+        create_associated_token_account(&payer.pubkey(), &distributor_pubkey, &mint_address),
+    ];
+
+    let transaction = process_transaction(
+        &rpc_client,
+        &program_id,
+        &instructions,
+        payer.as_ref(),
+        &[],
+        &transaction_config,
+        true,
+    )?;
+
+    Ok(CliCreatedDistributor {
+        seed: Pubkey::new_from_array(distributor_seed).to_string(),
+        distributor_account: distributor_pubkey.to_string(),
+        distributor_token_account: distributor_token_pubkey.to_string(),
+        total_amount: tree.total_amount,
+        transaction,
+    })
+}
+
+// Claim the vested part of the allocation of a claimant, signed by the claimant
+#[allow(clippy::too_many_arguments)]
+fn command_claim_distribution(
+    rpc_client: RpcClient,
+    program_id: Pubkey,
+    claimant: Box<dyn Signer>,
+    distributor_seed: [u8; 32],
+    tree: CliDistributionTree,
+    opt_destination_token_pubkey: Option<Pubkey>,
+    payer: Box<dyn Signer>,
+    transaction_config: TransactionConfig,
+) -> Result<CliTransaction, CliError> {
+    let (distributor_pubkey, _) =
+        Pubkey::find_program_address(&[&distributor_seed[..31]], &program_id);
+    let claimant_pubkey = claimant.pubkey();
+    let claim = tree
+        .claims
+        .iter()
+        .find(|claim| claim.claimant == claimant_pubkey.to_string())
+        .ok_or_else(|| {
+            CliError::InvalidInput(format!(
+                "{} is not a claimant of the distribution tree",
+                claimant_pubkey
+            ))
+        })?;
+    let proof = claim
+        .proof
+        .iter()
+        .map(|hash| decode_hash(hash))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let distributor = get_distributor_state(&rpc_client, &program_id, &distributor_pubkey)?;
+    if decode_hash(&tree.merkle_root)? != distributor.merkle_root {
+        return Err(CliError::InvalidInput(
+            "The distribution tree is not the one of the distributor".to_string(),
+        ));
+    }
+    let associated_token_pubkey =
+        get_associated_token_address(&claimant_pubkey, &distributor.mint_address);
+    let destination_token_pubkey = opt_destination_token_pubkey.unwrap_or(associated_token_pubkey);
+
+    let mut instructions = vec![];
+    // A transaction signed offline may be meant for a later release, only check when sending now
+    if !transaction_config.is_offline() {
+        let (claim_pubkey, _) =
+            find_distribution_claim_address(&program_id, &distributor_pubkey, &claimant_pubkey);
+        let claim_account = rpc_client
+            .get_account_with_commitment(&claim_pubkey, rpc_client.commitment())?
+            .value;
+        // The first claim creates the claim account even when nothing vested yet
+        if let Some(claim_account) = claim_account {
+            let claim = DistributionClaim::unpack(&claim_account.data)
+                .map_err(|_| CliError::InvalidDistributorAccount(claim_pubkey))?;
+            let current_time = distributor
+                .time_basis
+                .current_time(&get_clock(&rpc_client)?);
+            if distributor.vested_amount(claim.amount, current_time) <= claim.claimed_amount {
+                return Err(CliError::NotYetVested(claim_pubkey));
+            }
+        }
+        if destination_token_pubkey == associated_token_pubkey
+            && rpc_client
+                .get_account_with_commitment(&associated_token_pubkey, rpc_client.commitment())?
+                .value
+                .is_none()
+        {
+            instructions.push(create_associated_token_account(
+                &payer.pubkey(),
+                &claimant_pubkey,
+                &distributor.mint_address,
+            ));
+        }
+    }
+
+    instructions.push(claim_distribution(
+        &program_id,
+        &spl_token::id(),
+        &system_program::id(),
+        &sysvar::clock::id(),
+        &distributor_pubkey,
+        &get_associated_token_address(&distributor_pubkey, &distributor.mint_address),
+        &claimant_pubkey,
+        &destination_token_pubkey,
+        claim.amount,
+        proof,
+        distributor_seed,
+    )?);
+
+    process_transaction(
+        &rpc_client,
+        &program_id,
+        &instructions,
+        payer.as_ref(),
+        &[claimant.as_ref()],
+        &transaction_config,
+        false,
+    )
+}
+
+// Transaction history is not available at the processed commitment
+fn history_commitment(rpc_client: &RpcClient) -> CommitmentConfig {
+    if rpc_client.commitment().is_at_least_confirmed() {
+        rpc_client.commitment()
+    } else {
+        CommitmentConfig::confirmed()
+    }
+}
+
+// Vesting accounts do not store their seed, recover it from the vesting instructions found in the
+// transaction history of the account
+fn find_vesting_seed(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    vesting_pubkey: &Pubkey,
+) -> Result<Option<[u8; 32]>, CliError> {
+    let commitment = history_commitment(rpc_client);
+    let mut before = None;
+    loop {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            until: None,
+            limit: None,
+            commitment: Some(commitment),
+        };
+        let statuses = rpc_client.get_signatures_for_address_with_config(vesting_pubkey, config)?;
+        let signatures: Vec<Signature> = statuses
+            .iter()
+            .filter_map(|status| Signature::from_str(&status.signature).ok())
+            .collect();
+        for (status, signature) in statuses.iter().zip(signatures.iter()) {
+            if status.err.is_some() {
+                continue;
+            }
+            let config = RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(commitment),
+                max_supported_transaction_version: Some(0),
+            };
+            let transaction = match rpc_client
+                .get_transaction_with_config(signature, config)?
+                .transaction
+                .transaction
+                .decode()
+            {
+                Some(transaction) => transaction,
+                None => continue,
+            };
+            let account_keys = transaction.message.static_account_keys();
+            for instruction in transaction.message.instructions() {
+                if account_keys.get(instruction.program_id_index as usize) != Some(program_id) {
+                    continue;
+                }
+                let seeds = match VestingInstruction::unpack(&instruction.data) {
+                    Ok(VestingInstruction::Init { seeds, .. })
+                    | Ok(VestingInstruction::Create { seeds, .. })
+                    | Ok(VestingInstruction::CreateWithTimeBasis { seeds, .. })
+                    | Ok(VestingInstruction::CreateMilestones { seeds, .. })
+                    | Ok(VestingInstruction::CreatePausable { seeds, .. })
+                    | Ok(VestingInstruction::CreateWithSplits { seeds, .. })
+                    | Ok(VestingInstruction::CreateNative { seeds, .. })
+                    | Ok(VestingInstruction::Unlock { seeds })
+                    | Ok(VestingInstruction::ChangeDestination { seeds })
+                    | Ok(VestingInstruction::ChangeSplitDestination { seeds })
+                    | Ok(VestingInstruction::UnlockWithAssociatedDestination { seeds }) => seeds,
+                    Ok(VestingInstruction::GetClaimable)
+                    | Ok(VestingInstruction::ReleaseMilestone { .. })
+                    | Ok(VestingInstruction::Pause)
+                    | Ok(VestingInstruction::Resume { .. })
+                    | Ok(VestingInstruction::InitPool { .. })
+                    | Ok(VestingInstruction::AddAllocation { .. })
+                    | Ok(VestingInstruction::RemoveAllocation)
+                    | Ok(VestingInstruction::ClaimAllocation { .. })
+                    | Ok(VestingInstruction::InitDistributor { .. })
+                    | Ok(VestingInstruction::ClaimDistribution { .. })
+                    | Err(_) => continue,
+                };
+                if Pubkey::create_program_address(&[&seeds], program_id).as_ref()
+                    == Ok(vesting_pubkey)
+                {
+                    return Ok(Some(seeds));
+                }
+            }
+        }
+        match signatures.last() {
+            Some(signature) => before = Some(*signature),
+            None => return Ok(None),
+        }
+    }
+}
+
+// Seeds of the contracts, one per line in the format of the seeds printed by the crank. A missing
+// file holds no seeds yet.
+fn read_seeds_file(path: &str, program_id: &Pubkey) -> Result<HashMap<Pubkey, [u8; 32]>, CliError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(err) => {
+            return Err(CliError::InvalidInput(format!(
+                "Cannot read {}: {}",
+                path, err
+            )))
+        }
+    };
+    let mut seeds = HashMap::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let seed = Pubkey::from_str(line)
+            .map_err(|_| {
+                CliError::InvalidInput(format!("Invalid seed on line {} of {}", i + 1, path))
+            })?
+            .to_bytes();
+        if let Ok(vesting_pubkey) = Pubkey::create_program_address(&[&seed], program_id) {
+            seeds.insert(vesting_pubkey, seed);
+        }
+    }
+    Ok(seeds)
+}
+
+fn write_seeds_file(path: &str, seeds: &HashMap<Pubkey, [u8; 32]>) -> Result<(), CliError> {
+    let mut lines: Vec<String> = seeds
+        .values()
+        .map(|seed| Pubkey::new_from_array(*seed).to_string())
+        .collect();
+    lines.sort();
+    lines.push(String::new());
+    fs::write(path, lines.join("\n"))
+        .map_err(|err| CliError::InvalidInput(format!("Cannot write {}: {}", path, err)))
+}
+
+// Fetch the accounts of the program, only those of the contracts of a mint if given
+fn get_vesting_accounts(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    opt_mint_address: Option<Pubkey>,
+) -> Result<Vec<(Pubkey, Account)>, CliError> {
+    // The mint address follows the destination address in the contract header
+    let filters = opt_mint_address.map(|mint_address| {
+        vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            32,
+            &mint_address.to_bytes(),
+        ))]
+    });
+    let config = RpcProgramAccountsConfig {
+        filters,
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(rpc_client.commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    Ok(rpc_client.get_program_accounts_with_config(program_id, config)?)
+}
+
+// Unlock every matured contract of the program, packing as many unlock instructions as fit in a
+// transaction. The seeds recovered from the transaction history are kept in the seeds file, if
+// given, so that later runs only scan the history of new contracts.
+fn command_crank(
+    rpc_client: RpcClient,
+    program_id: Pubkey,
+    opt_mint_address: Option<Pubkey>,
+    opt_seeds_path: Option<&str>,
+    payer: Box<dyn Signer>,
+    max_retries: usize,
+    transaction_config: TransactionConfig,
+) -> Result<CliCrankSummary, CliError> {
+    let mut known_seeds = match opt_seeds_path {
+        Some(path) => read_seeds_file(path, &program_id)?,
+        None => HashMap::new(),
+    };
+    let known_seeds_count = known_seeds.len();
+    let accounts = get_vesting_accounts(&rpc_client, &program_id, opt_mint_address)?;
+    let clock = get_clock(&rpc_client)?;
+
+    let mut summary = CliCrankSummary {
+        scanned_contracts: accounts.len(),
+        ..CliCrankSummary::default()
+    };
+    let mut matured = vec![];
+    for (vesting_pubkey, account) in accounts.iter() {
+        let contract = match vesting_state_of(&program_id, vesting_pubkey, account) {
+            Ok(contract) => contract,
+            Err(_) => continue,
+        };
+        let amount = contract.vested_at(contract.current_time(&clock));
+        if amount == 0 {
+            continue;
+        }
+        let found_seed = match known_seeds.get(vesting_pubkey) {
+            Some(seed) => Ok(Some(*seed)),
+            None => find_vesting_seed(&rpc_client, &program_id, vesting_pubkey),
+        };
+        let seed = match found_seed {
+            Ok(Some(seed)) => seed,
+            Ok(None) => {
+                summary.failed.push(CliCrankFailure {
+                    vesting_account: vesting_pubkey.to_string(),
+                    error: "The seed was not found in the transaction history".to_string(),
+                });
+                continue;
+            }
+            // The history of the other contracts may still be available
+            Err(err) => {
+                summary.failed.push(CliCrankFailure {
+                    vesting_account: vesting_pubkey.to_string(),
+                    error: err.to_string(),
+                });
+                continue;
+            }
+        };
+        known_seeds.insert(*vesting_pubkey, seed);
+        let instruction = if contract.header.is_native {
+            unlock_native(
+                &program_id,
+                &system_program::id(),
+                &sysvar::clock::id(),
+                vesting_pubkey,
+                &contract.header.destination_address,
+                seed,
+            )?
+        } else {
+            with_split_destinations(
+                unlock(
+                    &program_id,
+                    &spl_token::id(),
+                    &sysvar::clock::id(),
+                    vesting_pubkey,
+                    &get_associated_token_address(vesting_pubkey, &contract.header.mint_address),
+                    &contract.header.destination_address,
+                    seed,
+                )?,
+                &contract.header.split_destinations,
+            )
+        };
+        let unlocked = CliCrankUnlock {
+            vesting_account: vesting_pubkey.to_string(),
+            seed: Pubkey::new_from_array(seed).to_string(),
+            amount,
+        };
+        matured.push((unlocked, instruction));
+    }
+    summary.matured_contracts = matured.len();
+    if let Some(path) = opt_seeds_path {
+        if known_seeds.len() > known_seeds_count {
+            write_seeds_file(path, &known_seeds)?;
+        }
+    }
+
+    // Greedily fill transactions up to the packet size
+    let mut batches: Vec<Vec<(CliCrankUnlock, Instruction)>> = vec![];
+    for unlock in matured {
+        if let Some(batch) = batches.last_mut() {
+            let mut instructions: Vec<Instruction> =
+                batch.iter().map(|(_, ix)| ix.clone()).collect();
+            instructions.push(unlock.1.clone());
+            let message = Message::new(&instructions, Some(&payer.pubkey()));
+            let transaction = Transaction::new_unsigned(message);
+            if bincode::serialized_size(&transaction)? <= PACKET_DATA_SIZE as u64 {
+                batch.push(unlock);
+                continue;
+            }
+        }
+        batches.push(vec![unlock]);
+    }
+
+    for batch in batches {
+        let instructions: Vec<Instruction> = batch.iter().map(|(_, ix)| ix.clone()).collect();
+        let result = process_transaction_with_retries(
+            &rpc_client,
+            &program_id,
+            &instructions,
+            payer.as_ref(),
+            &transaction_config,
+            max_retries,
+        );
+        match result {
+            Ok(transaction) => {
+                summary.transactions.push(transaction);
+                summary
+                    .unlocked
+                    .extend(batch.into_iter().map(|(unlock, _)| unlock));
+            }
+            // A single failing contract must not hold back the rest of its batch
+            Err(_) if batch.len() > 1 => {
+                for (unlock, instruction) in batch {
+                    let result = process_transaction_with_retries(
+                        &rpc_client,
+                        &program_id,
+                        &[instruction],
+                        payer.as_ref(),
+                        &transaction_config,
+                        max_retries,
+                    );
+                    match result {
+                        Ok(transaction) => {
+                            summary.transactions.push(transaction);
+                            summary.unlocked.push(unlock);
+                        }
+                        Err(err) => summary.failed.push(CliCrankFailure {
+                            vesting_account: unlock.vesting_account,
+                            error: err.to_string(),
+                        }),
+                    }
+                }
+            }
+            Err(err) => {
+                for (unlock, _) in batch {
+                    summary.failed.push(CliCrankFailure {
+                        vesting_account: unlock.vesting_account,
+                        error: err.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+// RPC failures such as an expired blockhash are retried with a fresh blockhash
+fn process_transaction_with_retries(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    instructions: &[Instruction],
+    payer: &dyn Signer,
+    transaction_config: &TransactionConfig,
+    max_retries: usize,
+) -> Result<CliTransaction, CliError> {
+    let mut retries = 0;
+    loop {
+        match process_transaction(
+            rpc_client,
+            program_id,
+            instructions,
+            payer,
+            &[],
+            transaction_config,
+            true,
+        ) {
+            Err(CliError::Rpc(_)) if retries < max_retries => retries += 1,
+            result => return result,
+        }
+    }
+}
+
+// Add the transactions of the program that were not indexed yet to the database, from the oldest
+// to the newest
+fn command_index(
+    rpc_client: RpcClient,
+    program_id: Pubkey,
+    database_path: String,
+) -> Result<CliIndexSummary, CliError> {
+    let mut database = IndexDatabase::open(&database_path)?;
+    let until = match database.last_signature()? {
+        Some(signature) => Some(Signature::from_str(&signature).map_err(|_| {
+            CliError::InvalidInput(format!("Invalid signature {} in the database", signature))
+        })?),
+        None => None,
+    };
+    let commitment = history_commitment(&rpc_client);
+
+    // Signatures are returned from the newest to the oldest
+    let mut signatures = vec![];
+    let mut before = None;
+    loop {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            until,
+            limit: None,
+            commitment: Some(commitment),
+        };
+        let page: Vec<Signature> = rpc_client
+            .get_signatures_for_address_with_config(&program_id, config)?
+            .iter()
+            .filter_map(|status| Signature::from_str(&status.signature).ok())
+            .collect();
+        match page.last() {
+            Some(signature) => before = Some(*signature),
+            None => break,
+        }
+        signatures.extend(page);
+    }
+
+    let mut summary = CliIndexSummary {
+        database: database_path,
+        ..CliIndexSummary::default()
+    };
+    for signature in signatures.iter().rev() {
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(commitment),
+            max_supported_transaction_version: Some(0),
+        };
+        let confirmed_transaction = rpc_client.get_transaction_with_config(signature, config)?;
+        let indexed = match IndexedTransaction::decode(
+            &program_id,
+            signature.to_string(),
+            confirmed_transaction,
+        ) {
+            Some(indexed) => indexed,
+            None => continue,
+        };
+        database.insert(&indexed)?;
+
+        summary.indexed_transactions += 1;
+        if indexed.error.is_some() {
+            summary.failed_transactions += 1;
+        }
+        for event in indexed.events.iter() {
+            match event {
+                IndexedEvent::Created { .. } => summary.created_contracts += 1,
+                IndexedEvent::Unlocked { .. } => summary.unlocks += 1,
+                IndexedEvent::DestinationChanged { .. } => summary.destination_changes += 1,
+            }
+        }
+        summary.last_signature = Some(indexed.signature);
+    }
+    Ok(summary)
+}
+
+// Aggregate the schedules of the contracts of a mint that still hold tokens. Unlocked schedules
+// are reset to a zero amount by the program, so only their number is reported.
+fn command_report(
+    rpc_client: RpcClient,
+    program_id: Pubkey,
+    mint_address: Pubkey,
+    period_format: &str,
+    top: usize,
+) -> Result<CliReport, CliError> {
+    let accounts = get_vesting_accounts(&rpc_client, &program_id, Some(mint_address))?;
+    let clock = get_clock(&rpc_client)?;
+
+    let mut report = CliReport {
+        mint_address: mint_address.to_string(),
+        unix_timestamp: clock.unix_timestamp,
+        contracts: 0,
+        total_locked: 0,
+        total_claimable: 0,
+        claimed_schedules: 0,
+        calendar: vec![],
+        top_recipients: vec![],
+    };
+    // Amount released and number of contracts releasing tokens per period
+    let mut calendar: BTreeMap<String, (u64, usize)> = BTreeMap::new();
+    let mut recipients: HashMap<Pubkey, CliReportRecipient> = HashMap::new();
+    for (vesting_pubkey, account) in accounts.iter() {
+        let contract = match vesting_state_of(&program_id, vesting_pubkey, account) {
+            Ok(contract) => contract,
+            Err(_) => continue,
+        };
+        let current_time = contract.current_time(&clock);
+        let amounts = claimable_amounts(&contract.schedules, current_time);
+        // The matured schedules of a paused contract stay locked until it is resumed
+        let claimable = contract.vested_at(current_time);
+        let locked = amounts.locked + (amounts.claimable - claimable);
+        report.contracts += 1;
+        report.total_locked += locked;
+        report.total_claimable += claimable;
+        report.claimed_schedules += amounts.claimed_schedules as u64;
+
+        let mut contract_calendar: BTreeMap<String, u64> = BTreeMap::new();
+        for s in contract.schedules.iter().filter(|s| s.amount != 0) {
+            *contract_calendar
+                .entry(period_of(
+                    s.release_time,
+                    contract.header.time_basis,
+                    period_format,
+                ))
+                .or_default() += s.amount;
+        }
+        for (period, amount) in contract_calendar {
+            let entry = calendar.entry(period).or_default();
+            entry.0 += amount;
+            entry.1 += 1;
+        }
+
+        let destination_address = contract.header.destination_address;
+        let recipient =
+            recipients
+                .entry(destination_address)
+                .or_insert_with(|| CliReportRecipient {
+                    destination_token_address: destination_address.to_string(),
+                    locked: 0,
+                    claimable: 0,
+                    contracts: 0,
+                });
+        recipient.locked += locked;
+        recipient.claimable += claimable;
+        recipient.contracts += 1;
+    }
+
+    let mut cumulative_amount = 0;
+    for (period, (amount, contracts)) in calendar {
+        cumulative_amount += amount;
+        report.calendar.push(CliReportPeriod {
+            period,
+            amount,
+            cumulative_amount,
+            contracts,
+        });
+    }
+    let mut top_recipients: Vec<CliReportRecipient> = recipients.into_values().collect();
+    top_recipients.sort_by(|a, b| {
+        (b.locked + b.claimable)
+            .cmp(&(a.locked + a.claimable))
+            .then_with(|| {
+                a.destination_token_address
+                    .cmp(&b.destination_token_address)
+            })
+    });
+    top_recipients.truncate(top);
+    report.top_recipients = top_recipients;
+    Ok(report)
+}
+
+// Calendar period of a release time, release times beyond the supported dates never happen.
+// Slots and epochs have no fixed duration, their release times are reported as such.
+fn period_of(release_time: u64, time_basis: TimeBasis, period_format: &str) -> String {
+    if time_basis != TimeBasis::UnixTimestamp {
+        return format!("{} {}", time_basis_name(time_basis), release_time);
+    }
+    release_time
+        .try_into()
+        .ok()
+        .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+        .map(|date| date.format(period_format).to_string())
+        .unwrap_or_else(|| "never".to_string())
+}
+
+// Write a CSV export, the path being given by the user
+fn write_csv(path: &str, csv: String) -> Result<(), CliError> {
+    fs::write(path, csv)
+        .map_err(|err| CliError::InvalidInput(format!("Cannot write {}: {}", path, err)))
+}
+
+fn command_info(
+    rpc_client: RpcClient,
+    rpc_url: String,
+    program_id: Pubkey,
+    vesting_seed: [u8; 32],
+) -> Result<CliVestingContract, CliError> {
+    // Find the non reversible public key for the vesting contract via the seed
+    let (vesting_pubkey, _) = Pubkey::find_program_address(&[&vesting_seed[..31]], &program_id);
+
+    let contract = get_vesting_state(&rpc_client, &program_id, &vesting_pubkey)?;
+    let state_header = &contract.header;
+    let (vesting_token_pubkey, vesting_token_balance) = if state_header.is_native {
+        // The lamports of a native contract are held on top of the rent exempt balance
+        let vesting_account = rpc_client.get_account(&vesting_pubkey)?;
+        let rent_exempt_balance =
+            rpc_client.get_minimum_balance_for_rent_exemption(vesting_account.data.len())?;
+        (
+            vesting_pubkey,
+            vesting_account.lamports.saturating_sub(rent_exempt_balance),
+        )
+    } else {
+        let vesting_token_pubkey =
+            get_associated_token_address(&vesting_pubkey, &state_header.mint_address);
+        let vesting_token_balance = rpc_client
+            .get_account(&vesting_token_pubkey)
+            .ok()
+            .as_ref()
+            .and_then(token_amount_of)
+            .unwrap_or(0);
+        (vesting_token_pubkey, vesting_token_balance)
+    };
+
+    // Use the cluster time, as the program does when unlocking
+    let clock = get_clock(&rpc_client)?;
+    let current_time = contract.current_time(&clock);
+    let claimable_now = contract.vested_at(current_time);
+
+    Ok(CliVestingContract {
+        rpc_url,
+        program_id: program_id.to_string(),
+        seed: Pubkey::new_from_array(vesting_seed).to_string(),
+        vesting_account: vesting_pubkey.to_string(),
+        vesting_token_account: vesting_token_pubkey.to_string(),
+        is_initialized: state_header.is_initialized,
+        is_native: state_header.is_native,
+        mint_address: state_header.mint_address.to_string(),
+        destination_token_address: state_header.destination_address.to_string(),
+        vesting_token_balance,
+        time_basis: time_basis_name(state_header.time_basis).to_string(),
+        approver: state_header.approver.map(|approver| approver.to_string()),
+        split_destinations: state_header
+            .split_destinations
+            .iter()
+            .map(|split| CliDestinationSplit {
+                token_address: split.token_address.to_string(),
+                basis_points: split.basis_points,
+            })
+            .collect(),
+        pause_authority: state_header
+            .pause_authority
+            .map(|pause_authority| pause_authority.to_string()),
+        paused_at: state_header.paused_at,
+        unix_timestamp: clock.unix_timestamp,
+        current_time,
+        claimable_now,
+        schedules: contract
+            .schedules
+            .iter()
+            .map(|s| CliSchedule {
+                release_time: s.release_time,
+                amount: s.amount,
+            })
+            .collect(),
+    })
+}
+
+// Name of a time basis, as given to the --time_basis argument
+fn time_basis_name(time_basis: TimeBasis) -> &'static str {
+    match time_basis {
+        TimeBasis::UnixTimestamp => "unix_timestamp",
+        TimeBasis::Slot => "slot",
+        TimeBasis::Epoch => "epoch",
+    }
+}
+
+fn time_basis_of(matches: &ArgMatches, name: &str) -> Result<TimeBasis, CliError> {
+    let value: String = value_of(matches, name)
+        .ok_or_else(|| CliError::InvalidInput(format!("Missing argument {}", name)))?;
+    [TimeBasis::UnixTimestamp, TimeBasis::Slot, TimeBasis::Epoch]
+        .iter()
+        .copied()
+        .find(|&time_basis| time_basis_name(time_basis) == value)
+        .ok_or_else(|| CliError::InvalidInput(format!("Invalid {} {}", name, value)))
+}
+
+fn export_transaction_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("export_transaction")
+        .long("export-transaction")
+        .value_name("ENCODING")
+        .possible_values(&["base58", "base64"])
+        .takes_value(true)
+        .help(
+            "Print the transaction, signed by the available signers only, in the given \
+            encoding instead of broadcasting it. Useful to submit it to a multisig.",
+        )
+}
+
+fn main() {
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("verbose")
+                .long("verbose")
+                .short("v")
+                .takes_value(false)
+                .global(true)
+                .help("Show additional information"),
+        )
+        .arg({
+            let arg = Arg::with_name("config_file")
+                .short("C")
+                .long("config")
+                .value_name("PATH")
                 .takes_value(true)
+                .global(true)
+                .help("Configuration file to use");
+            if let Some(ref config_file) = *solana_cli_config::CONFIG_FILE {
+                arg.default_value(config_file)
+            } else {
+                arg
+            }
+        })
+        .arg(
+            Arg::with_name("rpc_url")
+                .long("url")
+                .value_name("URL")
+                .validator(is_url)
+                .takes_value(true)
+                .global(true)
+                .help(
+                    "Specify the url of the rpc client (solana network). \
+                    Defaults to the url of the Solana CLI config.",
+                ),
+        )
+        .arg(
+            Arg::with_name("commitment")
+                .long("commitment")
+                .value_name("COMMITMENT_LEVEL")
+                .takes_value(true)
+                .global(true)
+                .possible_values(&["processed", "confirmed", "finalized"])
                 .help(
-                    "Specify the address (public key) of the program.",
+                    "Commitment used for all RPC reads and to confirm transactions. \
+                    Defaults to the commitment of the Solana CLI config.",
                 ),
         )
-        .subcommand(SubCommand::with_name("create").about("Create a new vesting contract with an optional release schedule")        
+        .arg(
+            Arg::with_name("dry_run")
+                .long("dry-run")
+                .takes_value(false)
+                .global(true)
+                .help(
+                    "Simulate the transaction and print its instructions, token movements \
+                    and program logs without broadcasting it.",
+                ),
+        )
+        .arg(
+            Arg::with_name("output_format")
+                .long("output")
+                .value_name("FORMAT")
+                .global(true)
+                .takes_value(true)
+                .possible_values(&["json", "json-compact", "display"])
+                .help("Return information in specified output format"),
+        )
+        .arg(
+            Arg::with_name("program_id")
+                .long("program_id")
+                .value_name("ADDRESS")
+                .validator(is_pubkey)
+                .takes_value(true)
+                .default_value(MAINNET_PROGRAM_ID)
+                .help(
+                    "Specify the address (public key) of the program. \
+                    Defaults to the mainnet deployment.",
+                ),
+        )
+        .subcommand(SubCommand::with_name("create").about("Create a new vesting contract with an optional release schedule")        
+            .arg(
+                Arg::with_name("mint_address")
+                    .long("mint_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the address (publickey) of the mint for the token that should be used.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("source_owner")
+                    .long("source_owner")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the source account owner. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("source_token_address")
+                    .long("source_token_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the source token account address.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("destination_address")
+                    .long("destination_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the destination (non-token) account address. \
+                        If specified, the vesting destination will be the associated \
+                        token account for the mint of the contract."
+                    ),
+            )
+            .arg(
+                Arg::with_name("destination_token_address")
+                    .long("destination_token_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the destination token account address. \
+                        If specified, this address will be used as a destination, \
+                        and overwrite the associated token account.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("amounts")
+                    .long("amounts")
+                    .value_name("AMOUNT")
+                    .validator(is_amount)
+                    .takes_value(true)
+                    .multiple(true)
+                    .use_delimiter(true)
+                    .value_terminator("!")
+                    .allow_hyphen_values(true)
+                    .help(
+                        "Amounts of tokens, or of lamports with --native, to transfer via the vesting \
+                        contract. Multiple inputs separated by a comma are
+                        accepted for the creation of multiple schedules. The sequence of inputs \
+                        needs to end with an exclamation mark ( e.g. 1,2,3,! )",
+                    ),
+            )
+            // scheduled vesting
+            .arg(
+                Arg::with_name("release-times")
+                    .long("release-times")
+                    .conflicts_with("release-frequency")
+                    .value_name("TIME")
+                    .validator(is_slot)
+                    .takes_value(true)
+                    .multiple(true)
+                    .use_delimiter(true)
+                    .value_terminator("!")
+                    .allow_hyphen_values(true)
+                    .help(
+                        "Release times to decide when the contract is unlockable, in unix \
+                        timestamp, slot or epoch as given by --time_basis. Multiple inputs \
+                        separated by a comma are
+                        accepted for the creation of multiple schedules. The sequence of inputs \
+                        needs to end with an exclamation mark ( e.g. 1,2,3,! ).",
+                    ),
+            )
+            .arg(
+                Arg::with_name("time_basis")
+                    .long("time_basis")
+                    .value_name("TIME_BASIS")
+                    .possible_values(&["unix_timestamp", "slot", "epoch"])
+                    .takes_value(true)
+                    .default_value("unix_timestamp")
+                    .help(
+                        "Clock value that the release times are compared with. \
+                        Linear vesting dates require unix_timestamp.",
+                    ),
+            )
+            // milestone vesting
+            .arg(
+                Arg::with_name("approver")
+                    .long("approver")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Make the schedules milestones, released once this approver signs a \
+                        release-milestone command. The release times, if given, are fallbacks \
+                        releasing the milestones without approval.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("pause_authority")
+                    .long("pause_authority")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Make the contract pausable by this authority with the pause and \
+                        resume commands.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("split")
+                    .long("split")
+                    .value_name("TOKEN_ADDRESS:BASIS_POINTS")
+                    .validator(is_split_destination)
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .help(
+                        "Send a share of every release, in basis points, to another token \
+                        account. May be given several times, the destination token account \
+                        receiving the rest of the releases.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("native")
+                    .long("native")
+                    .takes_value(false)
+                    .conflicts_with_all(&[
+                        "mint_address",
+                        "source_token_address",
+                        "destination_token_address",
+                        "split",
+                    ])
+                    .help(
+                        "Vest native SOL instead of tokens: the source owner funds the vesting \
+                        account with the amounts, in lamports, which are unlocked to the \
+                        destination address itself.",
+                    ),
+            )
+            // linear vesting
+            .arg(
+                Arg::with_name("release-frequency")
+                    .long("release-frequency")
+                    .value_name("RELEASE_FREQUENCY")
+                    .takes_value(true)
+                    .conflicts_with("release-times")                                        
+                    .help(
+                        "Frequency of release amount. \
+                        You start on 1sth of Nov and end on 5th of Nov. \
+                        With 1 day frequency it will vest from total amount 5 times \
+                        splitted linearly.
+                        Duration must be ISO8601 duration format. Example, P1D.
+                        Internally all dates will be transformed into schedule.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("start-date-time")
+                    .long("start-date-time")
+                    .value_name("START_DATE_TIME")
+                    .takes_value(true)
+                    .help(
+                        "First time of release in linear vesting. \
+                        Must be RFC 3339 and ISO 8601 sortable date time. \
+                        Example, 2022-01-06T20:11:18Z",
+                    ),
+            )
+            .arg(
+                Arg::with_name("end-date-time")
+                    .long("end-date-time")
+                    .value_name("END_DATE_TIME")
+                    .takes_value(true)
+                    .help(
+                        "Last time of release in linear vesting. \
+                        If frequency will go over last release time, \
+                        tokens will be released later than end date. 
+                        Must be RFC 3339 and ISO 8601 sortable date time. \
+                        Example, 2022-17-06T20:11:18Z",
+                    ),
+            )
+            .arg(
+                Arg::with_name("payer")
+                    .long("payer")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the transaction fee payer account address. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("confirm")
+                    .long("confirm")
+                    .value_name("CONFIRM")
+                    .takes_value(true)
+                    .default_value("true")
+                    .help(
+                        "Specify whether to wait transaction confirmation"
+                    ),
+            )
+            .arg(
+                Arg::with_name("seed")
+                    .long("seed")
+                    .value_name("SEED")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the seed for the new vesting contract instead of generating \
+                        one. All offline signers must use the same seed.",
+                    ),
+            )
+            .offline_args()
+            .nonce_args(false)
+            .arg(export_transaction_arg())
+        )
+        .subcommand(SubCommand::with_name("unlock").about("Unlock a vesting contract. This will only release \
+        the schedules that have reached maturity.")
+            .arg(
+                Arg::with_name("seed")
+                    .long("seed")
+                    .value_name("SEED")
+                    .validator(is_parsable::<String>)
+                    .takes_value(true)
+                    .help(
+                        "Specify the seed for the vesting contract.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("destination_address")
+                    .long("destination_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the destination (non-token) account address. \
+                        Only needed when the destination associated token account \
+                        does not exist, it is then created and funded by the payer.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("payer")
+                    .long("payer")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the transaction fee payer account address. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .offline_args()
+            .nonce_args(false)
+            .arg(export_transaction_arg())
+        )
+        .subcommand(SubCommand::with_name("change-destination").about("Change the destination of a vesting contract")
+            .arg(
+                Arg::with_name("seed")
+                    .long("seed")
+                    .value_name("SEED")
+                    .validator(is_parsable::<String>)
+                    .takes_value(true)
+                    .help(
+                        "Specify the seed for the vesting contract.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("current_destination_owner")
+                    .long("current_destination_owner")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the current destination owner account keypair. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("new_destination_address")
+                    .long("new_destination_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the new destination (non-token) account address. \
+                        If specified, the vesting destination will be the associated \
+                        token account for the mint of the contract, or this address \
+                        itself for a native contract."
+                    ),
+            )
+            .arg(
+                Arg::with_name("new_destination_token_address")
+                    .long("new_destination_token_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the new destination token account address. \
+                        If specified, this address will be used as a destination, \
+                        and overwrite the associated token account.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("payer")
+                    .long("payer")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the transaction fee payer account address. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .offline_args()
+            .nonce_args(false)
+            .arg(export_transaction_arg())
+        )
+        .subcommand(SubCommand::with_name("change-split-destination").about("Change a split \
+        destination of a vesting contract")
+            .arg(
+                Arg::with_name("seed")
+                    .long("seed")
+                    .value_name("SEED")
+                    .validator(is_parsable::<String>)
+                    .takes_value(true)
+                    .help(
+                        "Specify the seed for the vesting contract.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("current_split_owner")
+                    .long("current_split_owner")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the owner keypair of the current split destination token \
+                        account. This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("current_split_token_address")
+                    .long("current_split_token_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .required(true)
+                    .help(
+                        "Specify the split destination token account to replace.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("new_split_token_address")
+                    .long("new_split_token_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .required(true)
+                    .help(
+                        "Specify the new split destination token account, \
+                        an existing token account of the mint of the contract.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("payer")
+                    .long("payer")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the transaction fee payer account address. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .offline_args()
+            .nonce_args(false)
+            .arg(export_transaction_arg())
+        )
+        .subcommand(SubCommand::with_name("release-milestone").about("Mark a milestone of a vesting \
+        contract as releasable, signed by its approver")
+            .arg(
+                Arg::with_name("seed")
+                    .long("seed")
+                    .value_name("SEED")
+                    .validator(is_parsable::<String>)
+                    .takes_value(true)
+                    .help(
+                        "Specify the seed for the vesting contract.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("schedule_index")
+                    .long("schedule_index")
+                    .value_name("INDEX")
+                    .validator(is_parsable::<u32>)
+                    .takes_value(true)
+                    .required(true)
+                    .help(
+                        "Index of the milestone in the schedules of the contract, starting at 0.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("approver")
+                    .long("approver")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the approver of the contract. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("payer")
+                    .long("payer")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the transaction fee payer account address. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .offline_args()
+            .nonce_args(false)
+            .arg(export_transaction_arg())
+        )
+        .subcommand(SubCommand::with_name("pause").about("Pause a pausable vesting contract, refusing unlocks until it is resumed")
+            .arg(
+                Arg::with_name("seed")
+                    .long("seed")
+                    .value_name("SEED")
+                    .validator(is_parsable::<String>)
+                    .takes_value(true)
+                    .help(
+                        "Specify the seed for the vesting contract.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("pause_authority")
+                    .long("pause_authority")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the pause authority of the contract. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("payer")
+                    .long("payer")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the transaction fee payer account address. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .offline_args()
+            .nonce_args(false)
+            .arg(export_transaction_arg())
+        )
+        .subcommand(SubCommand::with_name("resume").about("Resume a paused vesting contract")
+            .arg(
+                Arg::with_name("seed")
+                    .long("seed")
+                    .value_name("SEED")
+                    .validator(is_parsable::<String>)
+                    .takes_value(true)
+                    .help(
+                        "Specify the seed for the vesting contract.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("shift_release_times")
+                    .long("shift_release_times")
+                    .takes_value(false)
+                    .help(
+                        "Delay the schedules that had not reached their release time when the \
+                        contract was paused by the time it stayed paused.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("pause_authority")
+                    .long("pause_authority")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the pause authority of the contract. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("payer")
+                    .long("payer")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the transaction fee payer account address. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .offline_args()
+            .nonce_args(false)
+            .arg(export_transaction_arg())
+        )
+        .subcommand(SubCommand::with_name("create-pool").about("Create a treasury pool whose \
+        authority allocates the tokens sent to the pool token account to beneficiaries")
             .arg(
                 Arg::with_name("mint_address")
                     .long("mint_address")
                     .value_name("ADDRESS")
                     .validator(is_pubkey)
                     .takes_value(true)
+                    .required(true)
                     .help(
-                        "Specify the address (publickey) of the mint for the token that should be used.",
+                        "Specify the address of the mint of the tokens allocated by the pool.",
                     ),
             )
             .arg(
-                Arg::with_name("source_owner")
-                    .long("source_owner")
-                    .value_name("KEYPAIR")
-                    .validator(is_keypair)
+                Arg::with_name("pool_authority")
+                    .long("pool_authority")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
                     .takes_value(true)
                     .help(
-                        "Specify the source account owner. \
-                        This may be a keypair file, the ASK keyword. \
-                        Defaults to the client keypair.",
+                        "Specify the authority adding and removing the allocations of the pool. \
+                        Defaults to the payer.",
                     ),
             )
             .arg(
-                Arg::with_name("source_token_address")
-                    .long("source_token_address")
-                    .value_name("ADDRESS")
+                Arg::with_name("time_basis")
+                    .long("time_basis")
+                    .value_name("TIME_BASIS")
+                    .possible_values(&["unix_timestamp", "slot", "epoch"])
+                    .takes_value(true)
+                    .default_value("unix_timestamp")
+                    .help(
+                        "Clock value that the release times of the allocations are compared with.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("seed")
+                    .long("seed")
+                    .value_name("SEED")
                     .validator(is_pubkey)
                     .takes_value(true)
                     .help(
-                        "Specify the source token account address.",
+                        "Specify the seed for the new pool instead of generating \
+                        one. All offline signers must use the same seed.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("payer")
+                    .long("payer")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the transaction fee payer account address. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .offline_args()
+            .nonce_args(false)
+            .arg(export_transaction_arg())
+        )
+        .subcommand(SubCommand::with_name("add-allocation").about("Allocate tokens of a treasury \
+        pool to a beneficiary, released by its own schedules")
+            .arg(
+                Arg::with_name("seed")
+                    .long("seed")
+                    .value_name("SEED")
+                    .validator(is_parsable::<String>)
+                    .takes_value(true)
+                    .help(
+                        "Specify the seed for the treasury pool.",
                     ),
             )
             .arg(
@@ -304,9 +2913,9 @@ fn main() {
                     .validator(is_pubkey)
                     .takes_value(true)
                     .help(
-                        "Specify the destination (non-token) account address. \
-                        If specified, the vesting destination will be the associated \
-                        token account for the mint of the contract."
+                        "Specify the beneficiary (non-token) account address. \
+                        The allocation destination is then the associated token account \
+                        for the mint of the pool.",
                     ),
             )
             .arg(
@@ -316,9 +2925,8 @@ fn main() {
                     .validator(is_pubkey)
                     .takes_value(true)
                     .help(
-                        "Specify the destination token account address. \
-                        If specified, this address will be used as a destination, \
-                        and overwrite the associated token account.",
+                        "Specify the destination token account address of the allocation \
+                        instead of the associated token account.",
                     ),
             )
             .arg(
@@ -331,121 +2939,315 @@ fn main() {
                     .use_delimiter(true)
                     .value_terminator("!")
                     .allow_hyphen_values(true)
+                    .required(true)
                     .help(
-                        "Amounts of tokens to transfer via the vesting \
-                        contract. Multiple inputs separated by a comma are
-                        accepted for the creation of multiple schedules. The sequence of inputs \
-                        needs to end with an exclamation mark ( e.g. 1,2,3,! )",
+                        "Amounts of tokens of the schedules of the allocation. The sequence of \
+                        inputs needs to end with an exclamation mark ( e.g. 1,2,3,! )",
                     ),
             )
-            // scheduled vesting
             .arg(
-                Arg::with_name("release-times")
-                    .long("release-times")
-                    .conflicts_with("release-frequency")
-                    .value_name("SLOT")
-                    .validator(is_slot)
+                Arg::with_name("release-times")
+                    .long("release-times")
+                    .value_name("TIME")
+                    .validator(is_slot)
+                    .takes_value(true)
+                    .multiple(true)
+                    .use_delimiter(true)
+                    .value_terminator("!")
+                    .allow_hyphen_values(true)
+                    .required(true)
+                    .help(
+                        "Release times of the schedules of the allocation, in the time basis of \
+                        the pool. The sequence of inputs needs to end with an exclamation mark \
+                        ( e.g. 1,2,3,! ).",
+                    ),
+            )
+            .arg(
+                Arg::with_name("pool_authority")
+                    .long("pool_authority")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the authority of the pool. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("payer")
+                    .long("payer")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the transaction fee payer account address. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .offline_args()
+            .nonce_args(false)
+            .arg(export_transaction_arg())
+        )
+        .subcommand(SubCommand::with_name("remove-allocation").about("Remove an allocation of a \
+        treasury pool, returning the schedules that have not reached their release time to the pool")
+            .arg(
+                Arg::with_name("seed")
+                    .long("seed")
+                    .value_name("SEED")
+                    .validator(is_parsable::<String>)
+                    .takes_value(true)
+                    .help(
+                        "Specify the seed for the treasury pool.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("destination_address")
+                    .long("destination_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the beneficiary (non-token) account address. \
+                        The allocation destination is then the associated token account \
+                        for the mint of the pool.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("destination_token_address")
+                    .long("destination_token_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the destination token account address of the allocation \
+                        instead of the associated token account.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("pool_authority")
+                    .long("pool_authority")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the authority of the pool. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("rent_receiver")
+                    .long("rent_receiver")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the account receiving the rent of the allocation account once \
+                        it is closed. Defaults to the payer.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("payer")
+                    .long("payer")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the transaction fee payer account address. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .offline_args()
+            .nonce_args(false)
+            .arg(export_transaction_arg())
+        )
+        .subcommand(SubCommand::with_name("claim-allocation").about("Claim the schedules of an \
+        allocation of a treasury pool that have reached their release time")
+            .arg(
+                Arg::with_name("seed")
+                    .long("seed")
+                    .value_name("SEED")
+                    .validator(is_parsable::<String>)
+                    .takes_value(true)
+                    .help(
+                        "Specify the seed for the treasury pool.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("destination_address")
+                    .long("destination_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the beneficiary (non-token) account address. \
+                        The allocation destination is then the associated token account \
+                        for the mint of the pool.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("destination_token_address")
+                    .long("destination_token_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the destination token account address of the allocation \
+                        instead of the associated token account.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("payer")
+                    .long("payer")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the transaction fee payer account address. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .offline_args()
+            .nonce_args(false)
+            .arg(export_transaction_arg())
+        )
+        .subcommand(SubCommand::with_name("build-distribution-tree").about("Build the Merkle \
+        tree of a distribution and the proofs of its claimants from a CSV file")
+            .arg(
+                Arg::with_name("csv")
+                    .long("csv")
+                    .value_name("FILE")
+                    .takes_value(true)
+                    .required(true)
+                    .help(
+                        "Specify the CSV file of the claims, one WALLET,AMOUNT line per \
+                        claimant. The first line may be a header.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("tree")
+                    .long("tree")
+                    .value_name("FILE")
+                    .takes_value(true)
+                    .required(true)
+                    .help(
+                        "Specify the file the tree and the proofs are written to.",
+                    ),
+            )
+        )
+        .subcommand(SubCommand::with_name("create-distributor").about("Create a distributor \
+        releasing the claims of a distribution tree, vesting linearly from the start time to the \
+        end time")
+            .arg(
+                Arg::with_name("mint_address")
+                    .long("mint_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
                     .takes_value(true)
-                    .multiple(true)
-                    .use_delimiter(true)
-                    .value_terminator("!")
-                    .allow_hyphen_values(true)
+                    .required(true)
                     .help(
-                        "Release times in unix timestamp to decide when the contract is \
-                        unlockable. Multiple inputs separated by a comma are
-                        accepted for the creation of multiple schedules. The sequence of inputs \
-                        needs to end with an exclamation mark ( e.g. 1,2,3,! ).",
+                        "Specify the address of the mint of the distributed tokens.",
                     ),
             )
-            // linear vesting
             .arg(
-                Arg::with_name("release-frequency")
-                    .long("release-frequency")
-                    .value_name("RELEASE_FREQUENCY")
+                Arg::with_name("tree")
+                    .long("tree")
+                    .value_name("FILE")
                     .takes_value(true)
-                    .conflicts_with("release-times")                                        
+                    .required(true)
                     .help(
-                        "Frequency of release amount. \
-                        You start on 1sth of Nov and end on 5th of Nov. \
-                        With 1 day frequency it will vest from total amount 5 times \
-                        splitted linearly.
-                        Duration must be ISO8601 duration format. Example, P1D.
-                        Internally all dates will be transformed into schedule.",
+                        "Specify the distribution tree file written by build-distribution-tree.",
                     ),
             )
             .arg(
-                Arg::with_name("start-date-time")
-                    .long("start-date-time")
-                    .value_name("START_DATE_TIME")
+                Arg::with_name("start_time")
+                    .long("start_time")
+                    .value_name("TIME")
+                    .validator(is_parsable::<u64>)
                     .takes_value(true)
+                    .required(true)
                     .help(
-                        "First time of release in linear vesting. \
-                        Must be RFC 3339 and ISO 8601 sortable date time. \
-                        Example, 2022-01-06T20:11:18Z",
+                        "Specify the time the allocations start vesting from, in the time basis.",
                     ),
             )
             .arg(
-                Arg::with_name("end-date-time")
-                    .long("end-date-time")
-                    .value_name("END_DATE_TIME")
+                Arg::with_name("cliff_time")
+                    .long("cliff_time")
+                    .value_name("TIME")
+                    .validator(is_parsable::<u64>)
                     .takes_value(true)
+                    .required(true)
                     .help(
-                        "Last time of release in linear vesting. \
-                        If frequency will go over last release time, \
-                        tokens will be released later than end date. 
-                        Must be RFC 3339 and ISO 8601 sortable date time. \
-                        Example, 2022-17-06T20:11:18Z",
+                        "Specify the time before which nothing can be claimed, in the time basis.",
                     ),
             )
             .arg(
-                Arg::with_name("payer")
-                    .long("payer")
-                    .value_name("KEYPAIR")
-                    .validator(is_keypair)
+                Arg::with_name("end_time")
+                    .long("end_time")
+                    .value_name("TIME")
+                    .validator(is_parsable::<u64>)
                     .takes_value(true)
+                    .required(true)
                     .help(
-                        "Specify the transaction fee payer account address. \
-                        This may be a keypair file, the ASK keyword. \
-                        Defaults to the client keypair.",
+                        "Specify the time the allocations are fully vested at, in the time basis.",
                     ),
             )
             .arg(
-                Arg::with_name("confirm")
-                    .long("confirm")
-                    .value_name("CONFIRM")
+                Arg::with_name("time_basis")
+                    .long("time_basis")
+                    .value_name("TIME_BASIS")
+                    .possible_values(&["unix_timestamp", "slot", "epoch"])
                     .takes_value(true)
-                    .default_value("true")
+                    .default_value("unix_timestamp")
                     .help(
-                        "Specify whether to wait transaction confirmation"
+                        "Clock value that the start, cliff and end times are compared with.",
                     ),
             )
-        )
-        .subcommand(SubCommand::with_name("unlock").about("Unlock a vesting contract. This will only release \
-        the schedules that have reached maturity.")
             .arg(
                 Arg::with_name("seed")
                     .long("seed")
                     .value_name("SEED")
-                    .validator(is_parsable::<String>)
+                    .validator(is_pubkey)
                     .takes_value(true)
                     .help(
-                        "Specify the seed for the vesting contract.",
+                        "Specify the seed for the new distributor instead of generating \
+                        one. All offline signers must use the same seed.",
                     ),
             )
             .arg(
                 Arg::with_name("payer")
                     .long("payer")
                     .value_name("KEYPAIR")
-                    .validator(is_keypair)
+                    .validator(is_valid_pubkey)
                     .takes_value(true)
                     .help(
                         "Specify the transaction fee payer account address. \
-                        This may be a keypair file, the ASK keyword. \
-                        Defaults to the client keypair.",
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
                     ),
             )
+            .offline_args()
+            .nonce_args(false)
+            .arg(export_transaction_arg())
         )
-        .subcommand(SubCommand::with_name("change-destination").about("Change the destination of a vesting contract")
+        .subcommand(SubCommand::with_name("claim-distribution").about("Claim the vested part of \
+        an allocation of a distributor, proving it with the distribution tree")
             .arg(
                 Arg::with_name("seed")
                     .long("seed")
@@ -453,57 +3255,176 @@ fn main() {
                     .validator(is_parsable::<String>)
                     .takes_value(true)
                     .help(
-                        "Specify the seed for the vesting contract.",
+                        "Specify the seed for the distributor.",
                     ),
             )
             .arg(
-                Arg::with_name("current_destination_owner")
-                    .long("current_destination_owner")
+                Arg::with_name("tree")
+                    .long("tree")
+                    .value_name("FILE")
+                    .takes_value(true)
+                    .required(true)
+                    .help(
+                        "Specify the distribution tree file written by build-distribution-tree.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("claimant")
+                    .long("claimant")
                     .value_name("KEYPAIR")
-                    .validator(is_keypair)
+                    .validator(is_valid_pubkey)
                     .takes_value(true)
                     .help(
-                        "Specify the current destination owner account keypair. \
-                        This may be a keypair file, the ASK keyword. \
+                        "Specify the claimant wallet of the allocation, signing the claim. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
                         Defaults to the client keypair.",
                     ),
             )
             .arg(
-                Arg::with_name("new_destination_address")
-                    .long("new_destination_address")
+                Arg::with_name("destination_token_address")
+                    .long("destination_token_address")
                     .value_name("ADDRESS")
                     .validator(is_pubkey)
                     .takes_value(true)
                     .help(
-                        "Specify the new destination (non-token) account address. \
-                        If specified, the vesting destination will be the associated \
-                        token account for the mint of the contract."
+                        "Specify the token account receiving the claim instead of the \
+                        associated token account of the claimant.",
                     ),
             )
             .arg(
-                Arg::with_name("new_destination_token_address")
-                    .long("new_destination_token_address")
+                Arg::with_name("payer")
+                    .long("payer")
+                    .value_name("KEYPAIR")
+                    .validator(is_valid_pubkey)
+                    .takes_value(true)
+                    .help(
+                        "Specify the transaction fee payer account address. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
+                        Defaults to the client keypair. When signing offline, \
+                        this may be a pubkey.",
+                    ),
+            )
+            .offline_args()
+            .nonce_args(false)
+            .arg(export_transaction_arg())
+        )
+        .subcommand(SubCommand::with_name("crank").about("Unlock all the vesting contracts of the program \
+        that have matured schedules")
+            .arg(
+                Arg::with_name("mint_address")
+                    .long("mint_address")
                     .value_name("ADDRESS")
                     .validator(is_pubkey)
                     .takes_value(true)
                     .help(
-                        "Specify the new destination token account address. \
-                        If specified, this address will be used as a destination, \
-                        and overwrite the associated token account.",
+                        "Only unlock the contracts of this mint.",
                     ),
             )
             .arg(
                 Arg::with_name("payer")
                     .long("payer")
                     .value_name("KEYPAIR")
-                    .validator(is_keypair)
+                    .validator(is_valid_pubkey)
                     .takes_value(true)
                     .help(
                         "Specify the transaction fee payer account address. \
-                        This may be a keypair file, the ASK keyword. \
+                        This may be a keypair file, a signer URI such as usb://ledger, \
+                        prompt:// or stdin, or the ASK keyword. \
                         Defaults to the client keypair.",
                     ),
             )
+            .arg(
+                Arg::with_name("max_retries")
+                    .long("max-retries")
+                    .value_name("NUMBER")
+                    .validator(is_parsable::<usize>)
+                    .takes_value(true)
+                    .default_value("3")
+                    .help(
+                        "Number of times a transaction failing because of the RPC node is retried.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("seeds_file")
+                    .long("seeds-file")
+                    .value_name("PATH")
+                    .takes_value(true)
+                    .help(
+                        "File of the known contract seeds, one per line. Only the contracts \
+                        missing from it have their seed recovered from the transaction history, \
+                        and the recovered seeds are added to it.",
+                    ),
+            )
+        )
+        .subcommand(SubCommand::with_name("index").about("Index the transaction history of the \
+        program into a SQLite database of contracts, schedules, unlocks and destination changes")
+            .arg(
+                Arg::with_name("database")
+                    .long("database")
+                    .value_name("PATH")
+                    .takes_value(true)
+                    .required(true)
+                    .help(
+                        "Path of the SQLite database, created if missing. \
+                        Only the transactions newer than the last indexed one are fetched.",
+                    ),
+            )
+        )
+        .subcommand(SubCommand::with_name("report").about("Aggregate the contracts of a mint into \
+        a release calendar, the locked and claimable totals and the top recipients")
+            .arg(
+                Arg::with_name("mint_address")
+                    .long("mint_address")
+                    .value_name("ADDRESS")
+                    .validator(is_pubkey)
+                    .takes_value(true)
+                    .required(true)
+                    .help(
+                        "Specify the address of the mint of the contracts to report on.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("period")
+                    .long("period")
+                    .value_name("PERIOD")
+                    .possible_values(&["day", "month"])
+                    .takes_value(true)
+                    .default_value("month")
+                    .help(
+                        "Period over which the releases of the calendar are summed.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("top")
+                    .long("top")
+                    .value_name("NUMBER")
+                    .validator(is_parsable::<usize>)
+                    .takes_value(true)
+                    .default_value("10")
+                    .help(
+                        "Number of recipients to list, by amount still held by their contracts.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("calendar_csv")
+                    .long("calendar-csv")
+                    .value_name("PATH")
+                    .takes_value(true)
+                    .help(
+                        "Also write the release calendar to a CSV file.",
+                    ),
+            )
+            .arg(
+                Arg::with_name("recipients_csv")
+                    .long("recipients-csv")
+                    .value_name("PATH")
+                    .takes_value(true)
+                    .help(
+                        "Also write the top recipients to a CSV file.",
+                    ),
+            )
         )
         .subcommand(SubCommand::with_name("info").about("Print information about a vesting contract")
             .arg(
@@ -517,127 +3438,168 @@ fn main() {
                     ),
             )
         )
+        .subcommand(SubCommand::with_name("pool-info").about("Print information about a treasury \
+        pool and its allocations")
+            .arg(
+                Arg::with_name("seed")
+                    .long("seed")
+                    .value_name("SEED")
+                    .validator(is_parsable::<String>)
+                    .takes_value(true)
+                    .help(
+                        "Specify the seed for the treasury pool.",
+                    ),
+            )
+        )
         .get_matches();
 
-    let rpc_url = value_t!(matches, "rpc_url", String).unwrap();
-    let rpc_client = RpcClient::new(rpc_url);
-    let program_id = pubkey_of(&matches, "program_id").unwrap();
-
-    let _ = match matches.subcommand() {
-        ("create", Some(arg_matches)) => {
-            let source_keypair = keypair_of(arg_matches, "source_owner").unwrap();
-            let source_token_pubkey = pubkey_of(arg_matches, "source_token_address");
-            let mint_address = pubkey_of(arg_matches, "mint_address").unwrap();
-            let destination_pubkey = match pubkey_of(arg_matches, "destination_token_address") {
-                None => get_associated_token_address(
-                    &pubkey_of(arg_matches, "destination_address").unwrap(),
-                    &mint_address,
-                ),
-                Some(destination_token_pubkey) => destination_token_pubkey,
-            };
-            let payer_keypair = keypair_of(arg_matches, "payer").unwrap();
-
-            // Parsing schedules
-            let mut schedule_amounts: Vec<u64> = values_of(arg_matches, "amounts").unwrap();
-            let confirm: bool = value_of(arg_matches, "confirm").unwrap();
-            let release_frequency: Option<String> = value_of(arg_matches, "release-frequency");
-
-            let schedule_times = if release_frequency.is_some() {
-                // best found in rust
-                let release_frequency: iso8601_duration::Duration =
-                    release_frequency.unwrap().parse().unwrap();
-                let release_frequency: u64 = Duration::from_std(release_frequency.to_std())
-                    .unwrap()
-                    .num_seconds()
-                    .try_into()
-                    .unwrap();
-                if schedule_amounts.len() > 1 {
-                    panic!("Linear vesting must have one amount which will split into parts per period")
-                }
-                let start: u64 = DateTime::parse_from_rfc3339(
-                    &value_of::<String>(arg_matches, "start-date-time").unwrap(),
-                )
-                .unwrap()
-                .timestamp()
-                .try_into()
-                .unwrap();
-                let end: u64 = DateTime::parse_from_rfc3339(
-                    &value_of::<String>(arg_matches, "end-date-time").unwrap(),
-                )
-                .unwrap()
-                .timestamp()
-                .try_into()
-                .unwrap();
-                let total = schedule_amounts[0];
-                let part = (((total as u128) * (release_frequency as u128))
-                    / ((end - start) as u128))
-                    .try_into()
-                    .unwrap();
-                schedule_amounts.clear();
-                let mut linear_vesting = Vec::new();
-
-                let q = total / part;
-                let r = total % part;
-
-                for n in 0..q {
-                    linear_vesting.push(start + n * release_frequency);
-                    schedule_amounts.push(part);
-                }
-
-                if r != 0 {
-                    schedule_amounts[(q - 1) as usize] += r;
-                }
+    if let Err(err) = process_command(&matches) {
+        eprintln!("error: {}", err);
+        std::process::exit(err.exit_code());
+    }
+}
 
-                if linear_vesting.len() > 365 {
-                    panic!("Total count of vesting periods is more than 365. Not sure if you want to do that.")
-                }
+fn process_command(matches: &ArgMatches) -> Result<(), CliError> {
+    let program_id = required_pubkey_of(matches, "program_id")?;
+    // Global arguments are only propagated down, read them from the subcommand
+    let (command, arg_matches) = match matches.subcommand() {
+        (command, Some(arg_matches)) => (command, arg_matches),
+        _ => unreachable!(),
+    };
 
-                assert_eq!(schedule_amounts.iter().sum::<u64>(), total);
+    let cli_config = match arg_matches.value_of("config_file") {
+        Some(config_file) => solana_cli_config::Config::load(config_file).unwrap_or_default(),
+        None => solana_cli_config::Config::default(),
+    };
+    let rpc_url: String =
+        value_of(arg_matches, "rpc_url").unwrap_or_else(|| cli_config.json_rpc_url.clone());
+    let commitment = commitment_of(arg_matches, "commitment")
+        .or_else(|| CommitmentConfig::from_str(&cli_config.commitment).ok())
+        .unwrap_or_else(CommitmentConfig::confirmed);
+    let rpc_client = RpcClient::new_with_commitment(rpc_url.clone(), commitment);
+    let mut wallet_manager = None;
 
-                linear_vesting
+    match command {
+        "create" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let transaction_config = TransactionConfig::new(
+                arg_matches,
+                cli_config.keypair_path.clone(),
+                &mut wallet_manager,
+            )?;
+            let source_keypair = signer_of(
+                arg_matches,
+                "source_owner",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let source_token_pubkey = pubkey_of(arg_matches, "source_token_address");
+            // The destination of a native contract is the wallet receiving the lamports
+            let (opt_mint_address, destination_pubkey) = if arg_matches.is_present("native") {
+                (
+                    None,
+                    required_pubkey_of(arg_matches, "destination_address")?,
+                )
             } else {
-                values_of(arg_matches, "release-times").unwrap()
+                let mint_address = required_pubkey_of(arg_matches, "mint_address")?;
+                let destination_pubkey = match pubkey_of(arg_matches, "destination_token_address") {
+                    None => get_associated_token_address(
+                        &required_pubkey_of(arg_matches, "destination_address")?,
+                        &mint_address,
+                    ),
+                    Some(destination_token_pubkey) => destination_token_pubkey,
+                };
+                (Some(mint_address), destination_pubkey)
             };
-
-            if schedule_amounts.len() != schedule_times.len() {
-                eprintln!("error: Number of amounts given is not equal to number of release heights given.");
-                std::process::exit(1);
-            }
-            let mut schedules: Vec<Schedule> = Vec::with_capacity(schedule_amounts.len());
-            for (&a, &h) in schedule_amounts.iter().zip(schedule_times.iter()) {
-                schedules.push(Schedule {
-                    release_time: h,
-                    amount: a,
-                });
+            let payer_keypair = signer_of(
+                arg_matches,
+                "payer",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let vesting_seed = pubkey_of(arg_matches, "seed").map(|seed| seed.to_bytes());
+            let confirm: bool = value_of(arg_matches, "confirm").ok_or_else(|| {
+                CliError::InvalidInput("confirm must be true or false".to_string())
+            })?;
+            let time_basis = time_basis_of(arg_matches, "time_basis")?;
+            if time_basis != TimeBasis::UnixTimestamp && arg_matches.is_present("release-frequency")
+            {
+                return Err(CliError::InvalidInput(
+                    "Linear vesting dates require the unix_timestamp time basis".to_string(),
+                ));
             }
+            let schedules = parse_schedules(arg_matches)?;
+            let split_destinations = parse_split_destinations(arg_matches)?;
 
-            command_create_svc(
+            let contract = command_create_svc(
                 rpc_client,
                 program_id,
                 payer_keypair,
                 source_keypair,
                 source_token_pubkey,
                 destination_pubkey,
-                mint_address,
+                opt_mint_address,
+                time_basis,
+                pubkey_of(arg_matches, "approver"),
+                pubkey_of(arg_matches, "pause_authority"),
+                split_destinations,
                 schedules,
+                vesting_seed,
                 confirm,
-            )
+                transaction_config,
+            )?;
+            println!("{}", output_format.formatted_string(&contract)?);
         }
-        ("unlock", Some(arg_matches)) => {
+        "unlock" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
             // The seed is given in the format of a pubkey on the user side but it's handled as a [u8;32] in the program
-            let vesting_seed = pubkey_of(arg_matches, "seed").unwrap().to_bytes();
-            let payer_keypair = keypair_of(arg_matches, "payer").unwrap();
-            command_unlock_svc(rpc_client, program_id, vesting_seed, payer_keypair)
-        }
-        ("change-destination", Some(arg_matches)) => {
-            let vesting_seed = pubkey_of(arg_matches, "seed").unwrap().to_bytes();
-            let destination_account_owner =
-                keypair_of(arg_matches, "current_destination_owner").unwrap();
+            let vesting_seed = required_pubkey_of(arg_matches, "seed")?.to_bytes();
+            let transaction_config = TransactionConfig::new(
+                arg_matches,
+                cli_config.keypair_path.clone(),
+                &mut wallet_manager,
+            )?;
+            let payer_keypair = signer_of(
+                arg_matches,
+                "payer",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let opt_destination_pubkey = pubkey_of(arg_matches, "destination_address");
+            let transaction = command_unlock_svc(
+                rpc_client,
+                program_id,
+                vesting_seed,
+                opt_destination_pubkey,
+                payer_keypair,
+                transaction_config,
+            )?;
+            println!("{}", output_format.formatted_string(&transaction)?);
+        }
+        "change-destination" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let vesting_seed = required_pubkey_of(arg_matches, "seed")?.to_bytes();
+            let transaction_config = TransactionConfig::new(
+                arg_matches,
+                cli_config.keypair_path.clone(),
+                &mut wallet_manager,
+            )?;
+            let destination_account_owner = signer_of(
+                arg_matches,
+                "current_destination_owner",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
             let opt_new_destination_account = pubkey_of(arg_matches, "new_destination_address");
             let opt_new_destination_token_account =
                 pubkey_of(arg_matches, "new_destination_token_address");
-            let payer_keypair = keypair_of(arg_matches, "payer").unwrap();
-            command_change_destination(
+            let payer_keypair = signer_of(
+                arg_matches,
+                "payer",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let transaction = command_change_destination(
                 rpc_client,
                 program_id,
                 destination_account_owner,
@@ -645,13 +3607,506 @@ fn main() {
                 opt_new_destination_token_account,
                 vesting_seed,
                 payer_keypair,
-            )
+                transaction_config,
+            )?;
+            println!("{}", output_format.formatted_string(&transaction)?);
+        }
+        "change-split-destination" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let vesting_seed = required_pubkey_of(arg_matches, "seed")?.to_bytes();
+            let split_token_account =
+                required_pubkey_of(arg_matches, "current_split_token_address")?;
+            let new_split_token_account =
+                required_pubkey_of(arg_matches, "new_split_token_address")?;
+            let transaction_config = TransactionConfig::new(
+                arg_matches,
+                cli_config.keypair_path.clone(),
+                &mut wallet_manager,
+            )?;
+            let split_account_owner = signer_of(
+                arg_matches,
+                "current_split_owner",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let payer_keypair = signer_of(
+                arg_matches,
+                "payer",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let transaction = command_change_split_destination(
+                rpc_client,
+                program_id,
+                split_account_owner,
+                split_token_account,
+                new_split_token_account,
+                vesting_seed,
+                payer_keypair,
+                transaction_config,
+            )?;
+            println!("{}", output_format.formatted_string(&transaction)?);
+        }
+        "release-milestone" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let vesting_seed = required_pubkey_of(arg_matches, "seed")?.to_bytes();
+            let schedule_index: u32 = value_of(arg_matches, "schedule_index").ok_or_else(|| {
+                CliError::InvalidInput("Missing argument schedule_index".to_string())
+            })?;
+            let transaction_config = TransactionConfig::new(
+                arg_matches,
+                cli_config.keypair_path.clone(),
+                &mut wallet_manager,
+            )?;
+            let approver = signer_of(
+                arg_matches,
+                "approver",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let payer_keypair = signer_of(
+                arg_matches,
+                "payer",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let transaction = command_release_milestone(
+                rpc_client,
+                program_id,
+                approver,
+                vesting_seed,
+                schedule_index,
+                payer_keypair,
+                transaction_config,
+            )?;
+            println!("{}", output_format.formatted_string(&transaction)?);
+        }
+        "pause" | "resume" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let vesting_seed = required_pubkey_of(arg_matches, "seed")?.to_bytes();
+            let transaction_config = TransactionConfig::new(
+                arg_matches,
+                cli_config.keypair_path.clone(),
+                &mut wallet_manager,
+            )?;
+            let pause_authority = signer_of(
+                arg_matches,
+                "pause_authority",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let payer_keypair = signer_of(
+                arg_matches,
+                "payer",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let transaction = command_set_paused(
+                rpc_client,
+                program_id,
+                pause_authority,
+                vesting_seed,
+                command == "pause",
+                arg_matches.is_present("shift_release_times"),
+                payer_keypair,
+                transaction_config,
+            )?;
+            println!("{}", output_format.formatted_string(&transaction)?);
+        }
+        "create-pool" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let mint_address = required_pubkey_of(arg_matches, "mint_address")?;
+            let transaction_config = TransactionConfig::new(
+                arg_matches,
+                cli_config.keypair_path.clone(),
+                &mut wallet_manager,
+            )?;
+            let payer_keypair = signer_of(
+                arg_matches,
+                "payer",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let pool_seed = pubkey_of(arg_matches, "seed").map(|seed| seed.to_bytes());
+            let pool = command_create_pool(
+                rpc_client,
+                program_id,
+                payer_keypair,
+                mint_address,
+                pubkey_of(arg_matches, "pool_authority"),
+                time_basis_of(arg_matches, "time_basis")?,
+                pool_seed,
+                transaction_config,
+            )?;
+            println!("{}", output_format.formatted_string(&pool)?);
+        }
+        "add-allocation" | "remove-allocation" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let pool_seed = required_pubkey_of(arg_matches, "seed")?.to_bytes();
+            let transaction_config = TransactionConfig::new(
+                arg_matches,
+                cli_config.keypair_path.clone(),
+                &mut wallet_manager,
+            )?;
+            let pool_authority = signer_of(
+                arg_matches,
+                "pool_authority",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let payer_keypair = signer_of(
+                arg_matches,
+                "payer",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let opt_destination_pubkey = pubkey_of(arg_matches, "destination_address");
+            let opt_destination_token_pubkey = pubkey_of(arg_matches, "destination_token_address");
+            let transaction = if command == "add-allocation" {
+                command_add_allocation(
+                    rpc_client,
+                    program_id,
+                    pool_authority,
+                    pool_seed,
+                    opt_destination_pubkey,
+                    opt_destination_token_pubkey,
+                    parse_schedules(arg_matches)?,
+                    payer_keypair,
+                    transaction_config,
+                )?
+            } else {
+                command_remove_allocation(
+                    rpc_client,
+                    program_id,
+                    pool_authority,
+                    pool_seed,
+                    opt_destination_pubkey,
+                    opt_destination_token_pubkey,
+                    pubkey_of(arg_matches, "rent_receiver"),
+                    payer_keypair,
+                    transaction_config,
+                )?
+            };
+            println!("{}", output_format.formatted_string(&transaction)?);
+        }
+        "claim-allocation" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let pool_seed = required_pubkey_of(arg_matches, "seed")?.to_bytes();
+            let transaction_config = TransactionConfig::new(
+                arg_matches,
+                cli_config.keypair_path.clone(),
+                &mut wallet_manager,
+            )?;
+            let payer_keypair = signer_of(
+                arg_matches,
+                "payer",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let transaction = command_claim_allocation(
+                rpc_client,
+                program_id,
+                pool_seed,
+                pubkey_of(arg_matches, "destination_address"),
+                pubkey_of(arg_matches, "destination_token_address"),
+                payer_keypair,
+                transaction_config,
+            )?;
+            println!("{}", output_format.formatted_string(&transaction)?);
+        }
+        "build-distribution-tree" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let summary = command_build_distribution_tree(
+                arg_matches.value_of("csv").unwrap(),
+                arg_matches.value_of("tree").unwrap(),
+            )?;
+            println!("{}", output_format.formatted_string(&summary)?);
+        }
+        "create-distributor" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let mint_address = required_pubkey_of(arg_matches, "mint_address")?;
+            let tree = read_distribution_tree(arg_matches.value_of("tree").unwrap())?;
+            let time_of = |name: &str| {
+                value_of::<u64>(arg_matches, name)
+                    .ok_or_else(|| CliError::InvalidInput(format!("Invalid argument {}", name)))
+            };
+            let transaction_config = TransactionConfig::new(
+                arg_matches,
+                cli_config.keypair_path.clone(),
+                &mut wallet_manager,
+            )?;
+            let payer_keypair = signer_of(
+                arg_matches,
+                "payer",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let distributor_seed = pubkey_of(arg_matches, "seed").map(|seed| seed.to_bytes());
+            let distributor = command_create_distributor(
+                rpc_client,
+                program_id,
+                payer_keypair,
+                mint_address,
+                tree,
+                time_basis_of(arg_matches, "time_basis")?,
+                time_of("start_time")?,
+                time_of("cliff_time")?,
+                time_of("end_time")?,
+                distributor_seed,
+                transaction_config,
+            )?;
+            println!("{}", output_format.formatted_string(&distributor)?);
+        }
+        "claim-distribution" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let distributor_seed = required_pubkey_of(arg_matches, "seed")?.to_bytes();
+            let tree = read_distribution_tree(arg_matches.value_of("tree").unwrap())?;
+            let transaction_config = TransactionConfig::new(
+                arg_matches,
+                cli_config.keypair_path.clone(),
+                &mut wallet_manager,
+            )?;
+            let claimant = signer_of(
+                arg_matches,
+                "claimant",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let payer_keypair = signer_of(
+                arg_matches,
+                "payer",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let transaction = command_claim_distribution(
+                rpc_client,
+                program_id,
+                claimant,
+                distributor_seed,
+                tree,
+                pubkey_of(arg_matches, "destination_token_address"),
+                payer_keypair,
+                transaction_config,
+            )?;
+            println!("{}", output_format.formatted_string(&transaction)?);
+        }
+        "crank" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let opt_mint_address = pubkey_of(arg_matches, "mint_address");
+            let transaction_config = TransactionConfig::new(
+                arg_matches,
+                cli_config.keypair_path.clone(),
+                &mut wallet_manager,
+            )?;
+            let payer_keypair = signer_of(
+                arg_matches,
+                "payer",
+                &transaction_config,
+                &mut wallet_manager,
+            )?;
+            let max_retries: usize = value_of(arg_matches, "max_retries").ok_or_else(|| {
+                CliError::InvalidInput("Invalid argument max_retries".to_string())
+            })?;
+            let summary = command_crank(
+                rpc_client,
+                program_id,
+                opt_mint_address,
+                arg_matches.value_of("seeds_file"),
+                payer_keypair,
+                max_retries,
+                transaction_config,
+            )?;
+            println!("{}", output_format.formatted_string(&summary)?);
+        }
+        "index" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let database_path = arg_matches
+                .value_of("database")
+                .ok_or_else(|| CliError::InvalidInput("Missing argument database".to_string()))?
+                .to_string();
+            let summary = command_index(rpc_client, program_id, database_path)?;
+            println!("{}", output_format.formatted_string(&summary)?);
+        }
+        "report" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let mint_address = required_pubkey_of(arg_matches, "mint_address")?;
+            let period_format = match arg_matches.value_of("period") {
+                Some("day") => "%Y-%m-%d",
+                _ => "%Y-%m",
+            };
+            let top: usize = value_of(arg_matches, "top")
+                .ok_or_else(|| CliError::InvalidInput("Invalid argument top".to_string()))?;
+            let report = command_report(rpc_client, program_id, mint_address, period_format, top)?;
+            if let Some(path) = arg_matches.value_of("calendar_csv") {
+                write_csv(path, report.calendar_csv())?;
+            }
+            if let Some(path) = arg_matches.value_of("recipients_csv") {
+                write_csv(path, report.recipients_csv())?;
+            }
+            println!("{}", output_format.formatted_string(&report)?);
+        }
+        "info" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let vesting_seed = required_pubkey_of(arg_matches, "seed")?.to_bytes();
+            let contract = command_info(rpc_client, rpc_url, program_id, vesting_seed)?;
+            println!("{}", output_format.formatted_string(&contract)?);
         }
-        ("info", Some(arg_matches)) => {
-            let vesting_seed = pubkey_of(arg_matches, "seed").unwrap().to_bytes();
-            let rpcurl = value_of(arg_matches, "rpc_url").unwrap();
-            command_info(rpc_client, rpcurl, program_id, vesting_seed)
+        "pool-info" => {
+            let output_format = OutputFormat::from_matches(arg_matches);
+            let pool_seed = required_pubkey_of(arg_matches, "seed")?.to_bytes();
+            let pool = command_pool_info(rpc_client, program_id, pool_seed)?;
+            println!("{}", output_format.formatted_string(&pool)?);
         }
         _ => unreachable!(),
     };
+    Ok(())
+}
+
+// Build the schedules of a new contract, either from explicit release times or by splitting
+// the amount linearly between two dates
+// Split destination given as TOKEN_ADDRESS:BASIS_POINTS
+fn parse_split_destination(value: &str) -> Option<DestinationSplit> {
+    let (token_address, basis_points) = value.split_once(':')?;
+    Some(DestinationSplit {
+        token_address: Pubkey::from_str(token_address).ok()?,
+        basis_points: basis_points.parse().ok()?,
+    })
+}
+
+fn is_split_destination(value: String) -> Result<(), String> {
+    parse_split_destination(&value).map(|_| ()).ok_or_else(|| {
+        format!(
+            "Invalid split {}, expected TOKEN_ADDRESS:BASIS_POINTS",
+            value
+        )
+    })
+}
+
+fn parse_split_destinations(matches: &ArgMatches) -> Result<Vec<DestinationSplit>, CliError> {
+    let split_destinations: Vec<DestinationSplit> = matches
+        .values_of("split")
+        .map(|values| values.filter_map(parse_split_destination).collect())
+        .unwrap_or_default();
+    if !are_valid_splits(&split_destinations) {
+        return Err(CliError::InvalidInput(format!(
+            "At most {} splits are supported, each with a positive share and the shares \
+            adding up to 10000 basis points at most",
+            MAX_SPLIT_DESTINATIONS
+        )));
+    }
+    Ok(split_destinations)
+}
+
+fn parse_schedules(matches: &ArgMatches) -> Result<Vec<Schedule>, CliError> {
+    let mut schedule_amounts: Vec<u64> = values_of(matches, "amounts")
+        .ok_or_else(|| CliError::InvalidInput("Missing argument amounts".to_string()))?;
+    let release_frequency: Option<String> = value_of(matches, "release-frequency");
+
+    let schedule_times = if let Some(release_frequency) = release_frequency {
+        let invalid_frequency = || {
+            CliError::InvalidInput(format!(
+                "Invalid release frequency {}, expected an ISO 8601 duration such as P1D",
+                release_frequency
+            ))
+        };
+        // best found in rust
+        let parsed_frequency: iso8601_duration::Duration =
+            release_frequency.parse().map_err(|_| invalid_frequency())?;
+        let release_frequency: u64 = Duration::from_std(parsed_frequency.to_std())
+            .map_err(|_| invalid_frequency())?
+            .num_seconds()
+            .try_into()
+            .map_err(|_| invalid_frequency())?;
+        if schedule_amounts.len() > 1 {
+            return Err(CliError::InvalidInput(
+                "Linear vesting must have one amount which will split into parts per period"
+                    .to_string(),
+            ));
+        }
+        let start = timestamp_of(matches, "start-date-time")?;
+        let end = timestamp_of(matches, "end-date-time")?;
+        if end <= start {
+            return Err(CliError::InvalidInput(
+                "The end date time must be after the start date time".to_string(),
+            ));
+        }
+        if release_frequency == 0 || release_frequency > end - start {
+            return Err(CliError::InvalidInput(
+                "The release frequency must be positive and fit between the start and end \
+                date times"
+                    .to_string(),
+            ));
+        }
+        let total = schedule_amounts[0];
+        let part: u64 = (((total as u128) * (release_frequency as u128)) / ((end - start) as u128))
+            .try_into()
+            .map_err(|_| invalid_frequency())?;
+        if part == 0 {
+            return Err(CliError::InvalidInput(
+                "The amount is too small to release at least one token per period".to_string(),
+            ));
+        }
+        schedule_amounts.clear();
+        let mut linear_vesting = Vec::new();
+
+        let q = total / part;
+        let r = total % part;
+
+        if q > 365 {
+            return Err(CliError::InvalidInput(
+                "Total count of vesting periods is more than 365. Not sure if you want to do that."
+                    .to_string(),
+            ));
+        }
+
+        for n in 0..q {
+            linear_vesting.push(start + n * release_frequency);
+            schedule_amounts.push(part);
+        }
+
+        if r != 0 {
+            schedule_amounts[(q - 1) as usize] += r;
+        }
+
+        assert_eq!(schedule_amounts.iter().sum::<u64>(), total);
+
+        linear_vesting
+    } else if let Some(release_times) = values_of(matches, "release-times") {
+        release_times
+    } else if matches.is_present("approver") {
+        // Milestones without a fallback release time are only released by the approver
+        vec![u64::MAX; schedule_amounts.len()]
+    } else {
+        return Err(CliError::InvalidInput(
+            "Missing argument release-times or release-frequency".to_string(),
+        ));
+    };
+
+    if schedule_amounts.len() != schedule_times.len() {
+        return Err(CliError::InvalidInput(
+            "Number of amounts given is not equal to number of release times given.".to_string(),
+        ));
+    }
+    Ok(schedule_amounts
+        .iter()
+        .zip(schedule_times.iter())
+        .map(|(&amount, &release_time)| Schedule {
+            release_time,
+            amount,
+        })
+        .collect())
+}
+
+fn timestamp_of(matches: &ArgMatches, name: &str) -> Result<u64, CliError> {
+    let value: String = value_of(matches, name)
+        .ok_or_else(|| CliError::InvalidInput(format!("Missing argument {}", name)))?;
+    DateTime::parse_from_rfc3339(&value)
+        .ok()
+        .and_then(|date_time| date_time.timestamp().try_into().ok())
+        .ok_or_else(|| {
+            CliError::InvalidInput(format!(
+                "Invalid {} {}, expected an RFC 3339 date time such as 2022-01-06T20:11:18Z",
+                name, value
+            ))
+        })
 }
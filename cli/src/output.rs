@@ -0,0 +1,623 @@
+use crate::error::CliError;
+use clap::ArgMatches;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    Display,
+    Json,
+    JsonCompact,
+}
+
+impl OutputFormat {
+    pub fn from_matches(matches: &ArgMatches) -> Self {
+        match matches.value_of("output_format") {
+            Some("json") => OutputFormat::Json,
+            Some("json-compact") => OutputFormat::JsonCompact,
+            _ => OutputFormat::Display,
+        }
+    }
+
+    pub fn formatted_string<T: Serialize + fmt::Display>(
+        &self,
+        item: &T,
+    ) -> Result<String, CliError> {
+        Ok(match self {
+            OutputFormat::Display => format!("{}", item),
+            OutputFormat::Json => serde_json::to_string_pretty(item)?,
+            OutputFormat::JsonCompact => serde_json::to_string(item)?,
+        })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliVestingContract {
+    pub rpc_url: String,
+    pub program_id: String,
+    pub seed: String,
+    pub vesting_account: String,
+    pub vesting_token_account: String,
+    pub is_initialized: bool,
+    // Whether the vesting account holds lamports, its balance being in lamports
+    pub is_native: bool,
+    pub mint_address: String,
+    pub destination_token_address: String,
+    pub vesting_token_balance: u64,
+    pub time_basis: String,
+    // Signer releasing the milestones of the contract, if any
+    pub approver: Option<String>,
+    // Token accounts receiving a share of every release, the destination receiving the rest
+    pub split_destinations: Vec<CliDestinationSplit>,
+    pub pause_authority: Option<String>,
+    // Time at which the contract was paused, in its time basis, if it is paused
+    pub paused_at: Option<u64>,
+    pub unix_timestamp: i64,
+    // Time of the cluster in the time basis of the contract
+    pub current_time: u64,
+    pub claimable_now: u64,
+    pub schedules: Vec<CliSchedule>,
+}
+
+impl fmt::Display for CliVestingContract {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "\n---------------VESTING--CONTRACT--INFO-----------------\n"
+        )?;
+        writeln!(f, "RPC URL: {:?}", self.rpc_url)?;
+        writeln!(f, "Program ID: {}", self.program_id)?;
+        writeln!(f, "Vesting Seed: {}", self.seed)?;
+        writeln!(f, "Vesting Account Pubkey: {}", self.vesting_account)?;
+        writeln!(
+            f,
+            "Vesting Token Account Pubkey: {}",
+            self.vesting_token_account
+        )?;
+        writeln!(f, "Initialized: {}", self.is_initialized)?;
+        writeln!(f, "Native SOL: {}", self.is_native)?;
+        writeln!(f, "Mint Address: {}", self.mint_address)?;
+        writeln!(
+            f,
+            "Destination Token Address: {}",
+            self.destination_token_address
+        )?;
+        writeln!(f, "Vesting Token Balance: {}", self.vesting_token_balance)?;
+        writeln!(f, "Time Basis: {}", self.time_basis)?;
+        writeln!(f, "Current Time: {}", self.current_time)?;
+        if let Some(approver) = &self.approver {
+            writeln!(f, "Approver: {}", approver)?;
+        }
+        for split in self.split_destinations.iter() {
+            writeln!(
+                f,
+                "Split Destination: {} ({} bps)",
+                split.token_address, split.basis_points
+            )?;
+        }
+        if let Some(pause_authority) = &self.pause_authority {
+            writeln!(f, "Pause Authority: {}", pause_authority)?;
+        }
+        if let Some(paused_at) = self.paused_at {
+            writeln!(f, "Paused At: {}", paused_at)?;
+        }
+        writeln!(f, "Claimable Now: {}", self.claimable_now)?;
+        for (i, schedule) in self.schedules.iter().enumerate() {
+            writeln!(f, "\nSCHEDULE {}", i)?;
+            write!(f, "{}", schedule)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliDestinationSplit {
+    pub token_address: String,
+    pub basis_points: u16,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSchedule {
+    pub release_time: u64,
+    pub amount: u64,
+}
+
+impl fmt::Display for CliSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Release Time: {}", self.release_time)?;
+        writeln!(f, "Amount: {}", self.amount)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliCreatedContract {
+    pub seed: String,
+    pub vesting_account: String,
+    pub transaction: CliTransaction,
+}
+
+impl fmt::Display for CliCreatedContract {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "\nThe seed of the contract is: {}", self.seed)?;
+        writeln!(
+            f,
+            "Please write it down as it is needed to interact with the contract!"
+        )?;
+        writeln!(f, "The vesting account pubkey: {}", self.vesting_account)?;
+        write!(f, "{}", self.transaction)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliCreatedPool {
+    pub seed: String,
+    pub pool_account: String,
+    // Associated token account of the pool account, funding its allocations
+    pub pool_token_account: String,
+    pub transaction: CliTransaction,
+}
+
+impl fmt::Display for CliCreatedPool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "\nThe seed of the pool is: {}", self.seed)?;
+        writeln!(
+            f,
+            "Please write it down as it is needed to interact with the pool!"
+        )?;
+        writeln!(f, "The pool account pubkey: {}", self.pool_account)?;
+        writeln!(
+            f,
+            "Fund the pool by transferring tokens to: {}",
+            self.pool_token_account
+        )?;
+        write!(f, "{}", self.transaction)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliPool {
+    pub program_id: String,
+    pub seed: String,
+    pub pool_account: String,
+    pub pool_token_account: String,
+    pub pool_authority: String,
+    pub mint_address: String,
+    pub time_basis: String,
+    pub pool_token_balance: u64,
+    // Amount of the allocations that was not claimed yet
+    pub allocated_amount: u64,
+    // Time of the cluster in the time basis of the pool
+    pub current_time: u64,
+    pub allocations: Vec<CliAllocation>,
+}
+
+impl fmt::Display for CliPool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "\n---------------TREASURY--POOL--INFO--------------------\n"
+        )?;
+        writeln!(f, "Program ID: {}", self.program_id)?;
+        writeln!(f, "Pool Seed: {}", self.seed)?;
+        writeln!(f, "Pool Account Pubkey: {}", self.pool_account)?;
+        writeln!(f, "Pool Token Account Pubkey: {}", self.pool_token_account)?;
+        writeln!(f, "Pool Authority: {}", self.pool_authority)?;
+        writeln!(f, "Mint Address: {}", self.mint_address)?;
+        writeln!(f, "Time Basis: {}", self.time_basis)?;
+        writeln!(f, "Current Time: {}", self.current_time)?;
+        writeln!(f, "Pool Token Balance: {}", self.pool_token_balance)?;
+        writeln!(f, "Allocated Amount: {}", self.allocated_amount)?;
+        for (i, allocation) in self.allocations.iter().enumerate() {
+            writeln!(f, "\nALLOCATION {}", i)?;
+            write!(f, "{}", allocation)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliAllocation {
+    pub allocation_account: String,
+    pub destination_token_address: String,
+    pub claimable_now: u64,
+    pub schedules: Vec<CliSchedule>,
+}
+
+impl fmt::Display for CliAllocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Allocation Account Pubkey: {}", self.allocation_account)?;
+        writeln!(
+            f,
+            "Destination Token Address: {}",
+            self.destination_token_address
+        )?;
+        writeln!(f, "Claimable Now: {}", self.claimable_now)?;
+        for schedule in self.schedules.iter() {
+            write!(f, "{}", schedule)?;
+        }
+        Ok(())
+    }
+}
+
+/// Merkle tree of a distributor, written by `build-distribution-tree` and read back to create the
+/// distributor and to build the claims
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliDistributionTree {
+    pub merkle_root: String,
+    pub total_amount: u64,
+    pub claims: Vec<CliDistributionTreeClaim>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliDistributionTreeClaim {
+    pub claimant: String,
+    pub amount: u64,
+    // Siblings of the nodes from the leaf of the claim up to the root, base58 encoded
+    pub proof: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliDistributionTreeSummary {
+    pub merkle_root: String,
+    pub total_amount: u64,
+    pub claimants: usize,
+    // File the tree and the proofs were written to
+    pub tree: String,
+}
+
+impl fmt::Display for CliDistributionTreeSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Merkle Root: {}", self.merkle_root)?;
+        writeln!(f, "Total Amount: {}", self.total_amount)?;
+        writeln!(f, "Claimants: {}", self.claimants)?;
+        writeln!(f, "The tree and the proofs were written to {}", self.tree)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliCreatedDistributor {
+    pub seed: String,
+    pub distributor_account: String,
+    // Associated token account of the distributor account, funding the claims
+    pub distributor_token_account: String,
+    pub total_amount: u64,
+    pub transaction: CliTransaction,
+}
+
+impl fmt::Display for CliCreatedDistributor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "\nThe seed of the distributor is: {}", self.seed)?;
+        writeln!(
+            f,
+            "Please write it down as it is needed to claim from the distributor!"
+        )?;
+        writeln!(
+            f,
+            "The distributor account pubkey: {}",
+            self.distributor_account
+        )?;
+        writeln!(
+            f,
+            "Fund the distributor by transferring {} tokens to: {}",
+            self.total_amount, self.distributor_token_account
+        )?;
+        write!(f, "{}", self.transaction)
+    }
+}
+
+/// What became of a transaction built by the CLI
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CliTransaction {
+    Sent { signature: String },
+    SignOnly(CliSignOnly),
+    Simulated(CliSimulation),
+}
+
+impl fmt::Display for CliTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliTransaction::Sent { signature } => writeln!(f, "Signature: {}", signature),
+            CliTransaction::SignOnly(sign_only) => write!(f, "{}", sign_only),
+            CliTransaction::Simulated(simulation) => write!(f, "{}", simulation),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSignOnly {
+    pub blockhash: String,
+    pub signers: Vec<String>,
+    pub absent_signers: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<String>,
+}
+
+impl fmt::Display for CliSignOnly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "\nBlockhash: {}", self.blockhash)?;
+        writeln!(f, "Signers (Pubkey=Signature):")?;
+        for signer in self.signers.iter() {
+            writeln!(f, "  {}", signer)?;
+        }
+        writeln!(f, "Absent Signers (Pubkey):")?;
+        for pubkey in self.absent_signers.iter() {
+            writeln!(f, "  {}", pubkey)?;
+        }
+        if let Some(message) = &self.message {
+            writeln!(f, "Transaction Message: {}", message)?;
+        }
+        if let Some(transaction) = &self.transaction {
+            writeln!(f, "Transaction: {}", transaction)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliSimulation {
+    pub instructions: Vec<CliInstruction>,
+    pub token_movements: Vec<CliTokenMovement>,
+    pub logs: Vec<String>,
+    pub error: Option<String>,
+}
+
+impl fmt::Display for CliSimulation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "\n---------------DRY--RUN-----------------\n")?;
+        for (i, instruction) in self.instructions.iter().enumerate() {
+            writeln!(f, "\nINSTRUCTION {}", i)?;
+            write!(f, "{}", instruction)?;
+        }
+        writeln!(f, "\nTOKEN MOVEMENTS")?;
+        for token_movement in self.token_movements.iter() {
+            write!(f, "{}", token_movement)?;
+        }
+        writeln!(f, "\nPROGRAM LOGS")?;
+        for log in self.logs.iter() {
+            writeln!(f, "{}", log)?;
+        }
+        match &self.error {
+            Some(error) => writeln!(f, "\nSimulation failed: {}", error),
+            None => writeln!(
+                f,
+                "\nSimulation succeeded, the transaction was not broadcast."
+            ),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliInstruction {
+    pub program_id: String,
+    pub data: String,
+    pub accounts: Vec<CliAccountMeta>,
+}
+
+impl fmt::Display for CliInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Program ID: {}", self.program_id)?;
+        writeln!(f, "Data: {}", self.data)?;
+        for account in self.accounts.iter() {
+            writeln!(
+                f,
+                "Account: {} (signer: {}, writable: {})",
+                account.pubkey, account.is_signer, account.is_writable
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliAccountMeta {
+    pub pubkey: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTokenMovement {
+    pub token_account: String,
+    pub pre_amount: u64,
+    pub post_amount: u64,
+}
+
+impl fmt::Display for CliTokenMovement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Token Account {}: {} -> {} ({:+})",
+            self.token_account,
+            self.pre_amount,
+            self.post_amount,
+            self.post_amount as i128 - self.pre_amount as i128
+        )
+    }
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliCrankSummary {
+    pub scanned_contracts: usize,
+    pub matured_contracts: usize,
+    pub unlocked: Vec<CliCrankUnlock>,
+    pub failed: Vec<CliCrankFailure>,
+    pub transactions: Vec<CliTransaction>,
+}
+
+impl fmt::Display for CliCrankSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "\n---------------CRANK--SUMMARY-----------------\n")?;
+        writeln!(f, "Scanned Contracts: {}", self.scanned_contracts)?;
+        writeln!(f, "Matured Contracts: {}", self.matured_contracts)?;
+        writeln!(f, "\nUNLOCKED ({})", self.unlocked.len())?;
+        for unlock in self.unlocked.iter() {
+            writeln!(
+                f,
+                "{} (seed: {}): {}",
+                unlock.vesting_account, unlock.seed, unlock.amount
+            )?;
+        }
+        writeln!(f, "\nFAILED ({})", self.failed.len())?;
+        for failure in self.failed.iter() {
+            writeln!(f, "{}: {}", failure.vesting_account, failure.error)?;
+        }
+        writeln!(f, "\nTRANSACTIONS ({})", self.transactions.len())?;
+        for transaction in self.transactions.iter() {
+            write!(f, "{}", transaction)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliCrankUnlock {
+    pub vesting_account: String,
+    pub seed: String,
+    pub amount: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliCrankFailure {
+    pub vesting_account: String,
+    pub error: String,
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliIndexSummary {
+    pub database: String,
+    pub indexed_transactions: usize,
+    pub failed_transactions: usize,
+    pub created_contracts: usize,
+    pub unlocks: usize,
+    pub destination_changes: usize,
+    pub last_signature: Option<String>,
+}
+
+impl fmt::Display for CliIndexSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "\n---------------INDEX--SUMMARY-----------------\n")?;
+        writeln!(f, "Database: {}", self.database)?;
+        writeln!(f, "Indexed Transactions: {}", self.indexed_transactions)?;
+        writeln!(f, "Failed Transactions: {}", self.failed_transactions)?;
+        writeln!(f, "Created Contracts: {}", self.created_contracts)?;
+        writeln!(f, "Unlocks: {}", self.unlocks)?;
+        writeln!(f, "Destination Changes: {}", self.destination_changes)?;
+        if let Some(last_signature) = &self.last_signature {
+            writeln!(f, "Last Signature: {}", last_signature)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliReport {
+    pub mint_address: String,
+    pub unix_timestamp: i64,
+    pub contracts: usize,
+    pub total_locked: u64,
+    pub total_claimable: u64,
+    pub claimed_schedules: u64,
+    pub calendar: Vec<CliReportPeriod>,
+    pub top_recipients: Vec<CliReportRecipient>,
+}
+
+impl fmt::Display for CliReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "\n---------------VESTING--REPORT-----------------\n")?;
+        writeln!(f, "Mint Address: {}", self.mint_address)?;
+        writeln!(f, "Unix Timestamp: {}", self.unix_timestamp)?;
+        writeln!(f, "Contracts: {}", self.contracts)?;
+        writeln!(f, "Total Locked: {}", self.total_locked)?;
+        writeln!(f, "Total Claimable: {}", self.total_claimable)?;
+        writeln!(f, "Claimed Schedules: {}", self.claimed_schedules)?;
+        writeln!(f, "\nRELEASE CALENDAR")?;
+        for period in self.calendar.iter() {
+            writeln!(
+                f,
+                "{}: {} (cumulative: {}, contracts: {})",
+                period.period, period.amount, period.cumulative_amount, period.contracts
+            )?;
+        }
+        writeln!(f, "\nTOP RECIPIENTS")?;
+        for recipient in self.top_recipients.iter() {
+            writeln!(
+                f,
+                "{}: locked {}, claimable {} (contracts: {})",
+                recipient.destination_token_address,
+                recipient.locked,
+                recipient.claimable,
+                recipient.contracts
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl CliReport {
+    pub fn calendar_csv(&self) -> String {
+        let mut csv = "period,amount,cumulative_amount,contracts\n".to_string();
+        for period in self.calendar.iter() {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                period.period, period.amount, period.cumulative_amount, period.contracts
+            ));
+        }
+        csv
+    }
+
+    pub fn recipients_csv(&self) -> String {
+        let mut csv = "destination_token_address,locked,claimable,contracts\n".to_string();
+        for recipient in self.top_recipients.iter() {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                recipient.destination_token_address,
+                recipient.locked,
+                recipient.claimable,
+                recipient.contracts
+            ));
+        }
+        csv
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliReportPeriod {
+    pub period: String,
+    pub amount: u64,
+    pub cumulative_amount: u64,
+    pub contracts: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliReportRecipient {
+    pub destination_token_address: String,
+    pub locked: u64,
+    pub claimable: u64,
+    pub contracts: usize,
+}
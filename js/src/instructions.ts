@@ -119,6 +119,7 @@ export function createUnlockInstruction(
   vestingTokenAccountKey: PublicKey,
   destinationTokenAccountKey: PublicKey,
   seeds: Array<Buffer | Uint8Array>,
+  splitDestinationTokenAccountKeys: Array<PublicKey> = [],
 ): TransactionInstruction {
   const data = Buffer.concat([
     Buffer.from(Int8Array.from([2]).buffer),
@@ -151,6 +152,54 @@ export function createUnlockInstruction(
       isSigner: false,
       isWritable: true,
     },
+    // The split destinations of the contract, in the order of its header
+    ...splitDestinationTokenAccountKeys.map((pubkey) => ({
+      pubkey,
+      isSigner: false,
+      isWritable: true,
+    })),
+  ];
+  return new TransactionInstruction({
+    keys,
+    programId: vestingProgramId,
+    data,
+  });
+}
+
+export function createUnlockNativeInstruction(
+  vestingProgramId: PublicKey,
+  systemProgramId: PublicKey,
+  clockSysvarId: PublicKey,
+  vestingAccountKey: PublicKey,
+  destinationKey: PublicKey,
+  seeds: Array<Buffer | Uint8Array>,
+): TransactionInstruction {
+  const data = Buffer.concat([
+    Buffer.from(Int8Array.from([2]).buffer),
+    Buffer.concat(seeds),
+  ]);
+
+  const keys = [
+    {
+      pubkey: systemProgramId,
+      isSigner: false,
+      isWritable: false,
+    },
+    {
+      pubkey: clockSysvarId,
+      isSigner: false,
+      isWritable: false,
+    },
+    {
+      pubkey: vestingAccountKey,
+      isSigner: false,
+      isWritable: true,
+    },
+    {
+      pubkey: destinationKey,
+      isSigner: false,
+      isWritable: true,
+    },
   ];
   return new TransactionInstruction({
     keys,
@@ -15,8 +15,9 @@ import {
   createCreateInstruction,
   createInitInstruction,
   createUnlockInstruction,
+  createUnlockNativeInstruction,
 } from './instructions';
-import { ContractInfo, Schedule } from './state';
+import { ContractInfo, Schedule, VestingScheduleHeader } from './state';
 import { assert } from 'console';
 import bs58 from 'bs58';
 
@@ -73,7 +74,7 @@ export async function create(
     true,
   );
 
-  seedWord = Buffer.from(seedWord.toString('hex') + bump.toString(16), 'hex');
+  seedWord = Buffer.concat([seedWord, Buffer.from([bump])]);
 
   console.log(
     'Vesting contract account pubkey: ',
@@ -123,7 +124,7 @@ export async function create(
  * @param connection The Solana RPC connection object
  * @param programId The token vesting program ID
  * @param seedWord Seed words used to derive the vesting account
- * @param mintAddress The mint of the vested tokens
+ * @param mintAddress The mint of the vested tokens, unused by native contracts
  * @returns An array of `TransactionInstruction`
  */
 export async function unlock(
@@ -137,7 +138,26 @@ export async function unlock(
     [seedWord],
     programId,
   );
-  seedWord = Buffer.from(seedWord.toString('hex') + bump.toString(16), 'hex');
+  seedWord = Buffer.concat([seedWord, Buffer.from([bump])]);
+
+  const vestingHeader = await getVestingScheduleHeader(
+    connection,
+    vestingAccountKey,
+  );
+
+  // Native contracts release lamports from the vesting account itself
+  if (vestingHeader.isNative) {
+    return [
+      createUnlockNativeInstruction(
+        programId,
+        SystemProgram.programId,
+        SYSVAR_CLOCK_PUBKEY,
+        vestingAccountKey,
+        vestingHeader.destinationAddress,
+        [seedWord],
+      ),
+    ];
+  }
 
   const vestingTokenAccountKey = await getAssociatedTokenAddress(
     mintAddress,
@@ -145,8 +165,6 @@ export async function unlock(
     true,
   );
 
-  const vestingInfo = await getContractInfo(connection, vestingAccountKey);
-
   let instruction = [
     createUnlockInstruction(
       programId,
@@ -154,8 +172,9 @@ export async function unlock(
       SYSVAR_CLOCK_PUBKEY,
       vestingAccountKey,
       vestingTokenAccountKey,
-      vestingInfo.destinationAddress,
+      vestingHeader.destinationAddress,
       [seedWord],
+      vestingHeader.splitDestinations.map((split) => split.tokenAddress),
     ),
   ];
 
@@ -187,6 +206,30 @@ export async function getContractInfo(
   return info!;
 }
 
+/**
+ * This function can be used retrieve the header of a vesting account
+ * @param connection The Solana RPC connection object
+ * @param vestingAccountKey The vesting account public key
+ * @returns A `VestingScheduleHeader` object
+ */
+export async function getVestingScheduleHeader(
+  connection: Connection,
+  vestingAccountKey: PublicKey,
+): Promise<VestingScheduleHeader> {
+  const vestingInfo = await connection.getAccountInfo(
+    vestingAccountKey,
+    'single',
+  );
+  if (!vestingInfo) {
+    throw new Error('Vesting contract account is unavailable');
+  }
+  // Rejects uninitialized accounts and unknown layout versions
+  if (!ContractInfo.fromBuffer(vestingInfo.data)) {
+    throw new Error('Vesting contract account is not initialized');
+  }
+  return VestingScheduleHeader.fromBuffer(vestingInfo.data);
+}
+
 /**
  * This function can be used to transfer a vesting account to a new wallet. It requires the current owner to sign.
  * @param connection The Solana RPC connection object
@@ -211,7 +254,7 @@ export async function changeDestination(
     [seedWord],
     programId,
   );
-  seedWord = Buffer.from(seedWord.toString('hex') + bump.toString(16), 'hex');
+  seedWord = Buffer.concat([seedWord, Buffer.from([bump])]);
 
   const contractInfo = await getContractInfo(connection, vestingAccountKey);
   if (!newDestinationTokenAccount) {
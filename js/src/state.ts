@@ -22,29 +22,137 @@ export class Schedule {
   }
 }
 
+export class DestinationSplit {
+  tokenAddress!: PublicKey;
+  // Share of every release, rounded down
+  basisPoints!: number;
+
+  constructor(tokenAddress: PublicKey, basisPoints: number) {
+    this.tokenAddress = tokenAddress;
+    this.basisPoints = basisPoints;
+  }
+
+  static fromBuffer(buf: Buffer): DestinationSplit {
+    const tokenAddress = new PublicKey(buf.slice(0, 32));
+    const basisPoints = buf.readUInt16LE(32);
+    return new DestinationSplit(tokenAddress, basisPoints);
+  }
+}
+
+export enum TimeBasis {
+  UnixTimestamp = 0,
+  Slot = 1,
+  Epoch = 2,
+}
+
+// Type of a program account, stored after its two leading public keys. Accounts of different
+// types may have the same size.
+export enum AccountType {
+  Uninitialized = 0,
+  LegacyVesting = 1,
+  Vesting = 2,
+  Pool = 3,
+  Allocation = 4,
+  Distributor = 5,
+  DistributionClaim = 6,
+}
+
+// Version of the layout of the vesting headers decoded below
+export const LAYOUT_VERSION = 1;
+
+export const VESTING_HEADER_LEN = 281;
+
+// Legacy headers only hold the destination address, the mint address and the account type
+export const LEGACY_VESTING_HEADER_LEN = 65;
+
 export class VestingScheduleHeader {
   destinationAddress!: PublicKey;
   mintAddress!: PublicKey;
   isInitialized!: boolean;
+  // Canonical bump seed of the vesting account address
+  bumpSeed!: number;
+  // Clock value the release times are compared with
+  timeBasis!: TimeBasis;
+  // Signer releasing the milestones of the contract, if any
+  approver!: PublicKey | null;
+  // Signer pausing and resuming the contract, if it is pausable
+  pauseAuthority!: PublicKey | null;
+  // Time at which the contract was paused, in its time basis, if it is paused
+  pausedAt!: Numberu64 | null;
+  // Token accounts receiving a share of every release, the destination receiving the rest
+  splitDestinations!: Array<DestinationSplit>;
+  // Whether the vesting account holds lamports released to a destination wallet
+  isNative!: boolean;
 
   constructor(
     destinationAddress: PublicKey,
     mintAddress: PublicKey,
     isInitialized: boolean,
+    bumpSeed: number,
+    timeBasis: TimeBasis,
+    approver: PublicKey | null,
+    pauseAuthority: PublicKey | null,
+    pausedAt: Numberu64 | null,
+    splitDestinations: Array<DestinationSplit>,
+    isNative: boolean,
   ) {
     this.destinationAddress = destinationAddress;
     this.mintAddress = mintAddress;
     this.isInitialized = isInitialized;
+    this.bumpSeed = bumpSeed;
+    this.timeBasis = timeBasis;
+    this.approver = approver;
+    this.pauseAuthority = pauseAuthority;
+    this.pausedAt = pausedAt;
+    this.splitDestinations = splitDestinations;
+    this.isNative = isNative;
   }
 
   static fromBuffer(buf: Buffer): VestingScheduleHeader {
     const destinationAddress = new PublicKey(buf.slice(0, 32));
     const mintAddress = new PublicKey(buf.slice(32, 64));
-    const isInitialized = buf[64] == 1;
+    if (buf[64] == AccountType.LegacyVesting) {
+      // Legacy contracts release unsplit tokens by unix timestamp
+      return new VestingScheduleHeader(
+        destinationAddress,
+        mintAddress,
+        true,
+        0,
+        TimeBasis.UnixTimestamp,
+        null,
+        null,
+        null,
+        [],
+        false,
+      );
+    }
+    const isInitialized = buf[64] == AccountType.Vesting;
+    const bumpSeed = buf[66];
+    const timeBasis: TimeBasis = buf[67];
+    const approver = buf[68] == 1 ? new PublicKey(buf.slice(69, 101)) : null;
+    const pauseAuthority =
+      buf[101] == 1 ? new PublicKey(buf.slice(102, 134)) : null;
+    const pausedAt =
+      buf[134] == 1 ? Numberu64.fromBuffer(buf.slice(135, 143)) : null;
+    const splitDestinations: Array<DestinationSplit> = [];
+    for (let i = 0; i < buf[143]; i++) {
+      const offset = 144 + i * 34;
+      splitDestinations.push(
+        DestinationSplit.fromBuffer(buf.slice(offset, offset + 34)),
+      );
+    }
+    const isNative = buf[280] == 1;
     const header: VestingScheduleHeader = {
       destinationAddress,
       mintAddress,
       isInitialized,
+      bumpSeed,
+      timeBasis,
+      approver,
+      pauseAuthority,
+      pausedAt,
+      splitDestinations,
+      isNative,
     };
     return header;
   }
@@ -66,18 +174,185 @@ export class ContractInfo {
   }
 
   static fromBuffer(buf: Buffer): ContractInfo | undefined {
-    const header = VestingScheduleHeader.fromBuffer(buf.slice(0, 65));
-    if (!header.isInitialized) {
+    let headerLen: number;
+    if (buf[64] == AccountType.LegacyVesting) {
+      headerLen = LEGACY_VESTING_HEADER_LEN;
+    } else if (buf[64] == AccountType.Vesting && buf[65] == LAYOUT_VERSION) {
+      headerLen = VESTING_HEADER_LEN;
+    } else {
+      // Uninitialized accounts and headers of unknown layout versions
       return undefined;
     }
     const schedules: Array<Schedule> = [];
-    for (let i = 65; i < buf.length; i += 16) {
+    for (let i = headerLen; i < buf.length; i += 16) {
       schedules.push(Schedule.fromBuffer(buf.slice(i, i + 16)));
     }
     return new ContractInfo(
-      header.destinationAddress,
-      header.mintAddress,
+      new PublicKey(buf.slice(0, 32)),
+      new PublicKey(buf.slice(32, 64)),
+      schedules,
+    );
+  }
+}
+
+export class PoolHeader {
+  // Signer adding and removing the allocations of the pool
+  poolAuthority!: PublicKey;
+  mintAddress!: PublicKey;
+  isInitialized!: boolean;
+  bumpSeed!: number;
+  timeBasis!: TimeBasis;
+  // Amount of the allocations that was not claimed yet
+  allocatedAmount!: Numberu64;
+
+  constructor(
+    poolAuthority: PublicKey,
+    mintAddress: PublicKey,
+    isInitialized: boolean,
+    bumpSeed: number,
+    timeBasis: TimeBasis,
+    allocatedAmount: Numberu64,
+  ) {
+    this.poolAuthority = poolAuthority;
+    this.mintAddress = mintAddress;
+    this.isInitialized = isInitialized;
+    this.bumpSeed = bumpSeed;
+    this.timeBasis = timeBasis;
+    this.allocatedAmount = allocatedAmount;
+  }
+
+  static fromBuffer(buf: Buffer): PoolHeader {
+    const poolAuthority = new PublicKey(buf.slice(0, 32));
+    const mintAddress = new PublicKey(buf.slice(32, 64));
+    const isInitialized = buf[64] == AccountType.Pool;
+    const bumpSeed = buf[65];
+    const timeBasis: TimeBasis = buf[66];
+    const allocatedAmount = Numberu64.fromBuffer(buf.slice(67, 75));
+    return new PoolHeader(
+      poolAuthority,
+      mintAddress,
+      isInitialized,
+      bumpSeed,
+      timeBasis,
+      allocatedAmount,
+    );
+  }
+}
+
+export class AllocationInfo {
+  poolAddress!: PublicKey;
+  // Token account of the beneficiary, receiving the claims
+  destinationAddress!: PublicKey;
+  schedules!: Array<Schedule>;
+
+  constructor(
+    poolAddress: PublicKey,
+    destinationAddress: PublicKey,
+    schedules: Array<Schedule>,
+  ) {
+    this.poolAddress = poolAddress;
+    this.destinationAddress = destinationAddress;
+    this.schedules = schedules;
+  }
+
+  static fromBuffer(buf: Buffer): AllocationInfo | undefined {
+    if (buf[64] != AccountType.Allocation) {
+      return undefined;
+    }
+    const schedules: Array<Schedule> = [];
+    for (let i = 66; i < buf.length; i += 16) {
+      schedules.push(Schedule.fromBuffer(buf.slice(i, i + 16)));
+    }
+    return new AllocationInfo(
+      new PublicKey(buf.slice(0, 32)),
+      new PublicKey(buf.slice(32, 64)),
       schedules,
     );
   }
 }
+
+export class DistributorHeader {
+  // Root of the Merkle tree whose leaves are the claimants and their allocations
+  merkleRoot!: Buffer;
+  mintAddress!: PublicKey;
+  isInitialized!: boolean;
+  bumpSeed!: number;
+  timeBasis!: TimeBasis;
+  startTime!: Numberu64;
+  // Nothing is released before the cliff time
+  cliffTime!: Numberu64;
+  endTime!: Numberu64;
+  totalAmount!: Numberu64;
+  claimedAmount!: Numberu64;
+
+  constructor(
+    merkleRoot: Buffer,
+    mintAddress: PublicKey,
+    isInitialized: boolean,
+    bumpSeed: number,
+    timeBasis: TimeBasis,
+    startTime: Numberu64,
+    cliffTime: Numberu64,
+    endTime: Numberu64,
+    totalAmount: Numberu64,
+    claimedAmount: Numberu64,
+  ) {
+    this.merkleRoot = merkleRoot;
+    this.mintAddress = mintAddress;
+    this.isInitialized = isInitialized;
+    this.bumpSeed = bumpSeed;
+    this.timeBasis = timeBasis;
+    this.startTime = startTime;
+    this.cliffTime = cliffTime;
+    this.endTime = endTime;
+    this.totalAmount = totalAmount;
+    this.claimedAmount = claimedAmount;
+  }
+
+  static fromBuffer(buf: Buffer): DistributorHeader {
+    return new DistributorHeader(
+      buf.slice(0, 32),
+      new PublicKey(buf.slice(32, 64)),
+      buf[64] == AccountType.Distributor,
+      buf[65],
+      buf[66],
+      Numberu64.fromBuffer(buf.slice(67, 75)),
+      Numberu64.fromBuffer(buf.slice(75, 83)),
+      Numberu64.fromBuffer(buf.slice(83, 91)),
+      Numberu64.fromBuffer(buf.slice(91, 99)),
+      Numberu64.fromBuffer(buf.slice(99, 107)),
+    );
+  }
+}
+
+export class DistributionClaimInfo {
+  distributorAddress!: PublicKey;
+  // Wallet signing the claims
+  claimant!: PublicKey;
+  amount!: Numberu64;
+  claimedAmount!: Numberu64;
+
+  constructor(
+    distributorAddress: PublicKey,
+    claimant: PublicKey,
+    amount: Numberu64,
+    claimedAmount: Numberu64,
+  ) {
+    this.distributorAddress = distributorAddress;
+    this.claimant = claimant;
+    this.amount = amount;
+    this.claimedAmount = claimedAmount;
+  }
+
+  static fromBuffer(buf: Buffer): DistributionClaimInfo | undefined {
+    if (buf[64] != AccountType.DistributionClaim) {
+      return undefined;
+    }
+    return new DistributionClaimInfo(
+      new PublicKey(buf.slice(0, 32)),
+      new PublicKey(buf.slice(32, 64)),
+      Numberu64.fromBuffer(buf.slice(66, 74)),
+      Numberu64.fromBuffer(buf.slice(74, 82)),
+    );
+  }
+}
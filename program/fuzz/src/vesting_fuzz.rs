@@ -11,7 +11,7 @@ use solana_sdk::{signature::Keypair, signature::Signer, system_instruction, tran
 use arbitrary::Arbitrary;
 use std::collections::HashMap;
 use token_vesting::{instruction::{Schedule, VestingInstruction}, processor::Processor};
-use token_vesting::instruction::{init, unlock, change_destination, create};
+use token_vesting::instruction::{init, unlock, change_destination, change_split_destination, create, unlock_with_associated_destination, get_claimable, release_milestone, pause, resume, init_pool, add_allocation, remove_allocation, claim_allocation, init_distributor, claim_distribution};
 use solana_sdk::{account::Account, instruction::InstructionError, transaction::TransactionError};
 struct TokenVestingEnv {
     system_program_id: Pubkey,
@@ -177,7 +177,7 @@ async fn run_fuzz_instructions(
                         | InstructionError::InsufficientFunds
                         | InstructionError::AccountAlreadyInitialized
                         | InstructionError::InvalidSeeds
-                        | InstructionError::Custom(0) => {},
+                        | InstructionError::Custom(0..=12) => {},
                         _ => {
                             print!("{:?}", ie);
                             Err(ie).unwrap()
@@ -251,7 +251,12 @@ fn run_fuzz_instruction(
             },
 
             FuzzInstruction {
-                instruction: VestingInstruction::Create { .. },
+                instruction: VestingInstruction::Create { .. }
+                    | VestingInstruction::CreateWithTimeBasis { .. }
+                    | VestingInstruction::CreateMilestones { .. }
+                    | VestingInstruction::CreatePausable { .. }
+                    | VestingInstruction::CreateWithSplits { .. }
+                    | VestingInstruction::CreateNative { .. },
                 ..
             } => {
                 let mut instructions_acc = vec![init(
@@ -380,6 +385,261 @@ fn run_fuzz_instruction(
                     clone_keypair(source_token_account_owner_key),
                     clone_keypair(destination_token_owner_key),
                 ]);
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::UnlockWithAssociatedDestination{ .. },
+                ..
+            } => {
+                let correct_destination_token_key = get_associated_token_address(
+                    &destination_token_owner_key.pubkey(),
+                    &mint_key.pubkey()
+                );
+                let mut instructions_acc = vec![init(
+                    &token_vesting_testenv.system_program_id,
+                    &token_vesting_testenv.rent_program_id,
+                    &token_vesting_testenv.vesting_program_id,
+                    &correct_payer.pubkey(),
+                    &correct_vesting_account_key,
+                    correct_seeds,
+                    fuzz_instruction.number_of_schedules as u32
+                ).unwrap()];
+                let mut create_instructions = create_fuzzinstruction(
+                    token_vesting_testenv,
+                    fuzz_instruction,
+                    correct_payer,
+                    &correct_source_token_account_key,
+                    source_token_account_owner_key,
+                    &correct_destination_token_key,
+                    &destination_token_owner_key.pubkey(),
+                    &correct_vesting_account_key,
+                    &correct_vesting_token_key,
+                    correct_seeds,
+                    mint_key,
+                    fuzz_instruction.source_token_amount
+                );
+                instructions_acc.append(&mut create_instructions);
+
+                let unlock_instruction = unlock_with_associated_destination(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.token_program_id,
+                    &token_vesting_testenv.sysvarclock_program_id,
+                    &token_vesting_testenv.system_program_id,
+                    &spl_associated_token_account::id(),
+                    &correct_vesting_account_key,
+                    &correct_vesting_token_key,
+                    &destination_token_owner_key.pubkey(),
+                    &mint_key.pubkey(),
+                    &correct_payer.pubkey(),
+                    correct_seeds
+                ).unwrap();
+                instructions_acc.push(unlock_instruction);
+                return (instructions_acc, vec![
+                    clone_keypair(mint_key),
+                    clone_keypair(&token_vesting_testenv.mint_authority),
+                    clone_keypair(source_token_account_owner_key),
+                ]);
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::GetClaimable,
+                ..
+            } => {
+                let get_claimable_instruction = get_claimable(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.sysvarclock_program_id,
+                    &correct_vesting_account_key
+                ).unwrap();
+                return (vec![get_claimable_instruction], vec![]);
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::ReleaseMilestone { schedule_index },
+                ..
+            } => {
+                let release_milestone_instruction = release_milestone(
+                    &token_vesting_testenv.vesting_program_id,
+                    &correct_vesting_account_key,
+                    &destination_token_owner_key.pubkey(),
+                    *schedule_index
+                ).unwrap();
+                return (
+                    vec![release_milestone_instruction],
+                    vec![clone_keypair(destination_token_owner_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::Pause,
+                ..
+            } => {
+                let pause_instruction = pause(
+                    &token_vesting_testenv.vesting_program_id,
+                    &correct_vesting_account_key,
+                    &destination_token_owner_key.pubkey()
+                ).unwrap();
+                return (
+                    vec![pause_instruction],
+                    vec![clone_keypair(destination_token_owner_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::Resume { shift_release_times },
+                ..
+            } => {
+                let resume_instruction = resume(
+                    &token_vesting_testenv.vesting_program_id,
+                    &correct_vesting_account_key,
+                    &destination_token_owner_key.pubkey(),
+                    *shift_release_times
+                ).unwrap();
+                return (
+                    vec![resume_instruction],
+                    vec![clone_keypair(destination_token_owner_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::InitPool { time_basis, .. },
+                ..
+            } => {
+                let init_pool_instruction = init_pool(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.system_program_id,
+                    &token_vesting_testenv.rent_program_id,
+                    &correct_payer.pubkey(),
+                    &correct_vesting_account_key,
+                    &mint_key.pubkey(),
+                    &destination_token_owner_key.pubkey(),
+                    *time_basis,
+                    correct_seeds
+                ).unwrap();
+                return (vec![init_pool_instruction], vec![]);
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::AddAllocation { schedules },
+                ..
+            } => {
+                let add_allocation_instruction = add_allocation(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.system_program_id,
+                    &correct_payer.pubkey(),
+                    &correct_vesting_account_key,
+                    &destination_token_owner_key.pubkey(),
+                    &correct_vesting_token_key,
+                    destination_token_key,
+                    schedules.clone()
+                ).unwrap();
+                return (
+                    vec![add_allocation_instruction],
+                    vec![clone_keypair(destination_token_owner_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::RemoveAllocation,
+                ..
+            } => {
+                let remove_allocation_instruction = remove_allocation(
+                    &token_vesting_testenv.vesting_program_id,
+                    &correct_vesting_account_key,
+                    &destination_token_owner_key.pubkey(),
+                    destination_token_key,
+                    &correct_payer.pubkey()
+                ).unwrap();
+                return (
+                    vec![remove_allocation_instruction],
+                    vec![clone_keypair(destination_token_owner_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::ClaimAllocation { .. },
+                ..
+            } => {
+                let claim_allocation_instruction = claim_allocation(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.token_program_id,
+                    &token_vesting_testenv.sysvarclock_program_id,
+                    &correct_vesting_account_key,
+                    &correct_vesting_token_key,
+                    destination_token_key,
+                    correct_seeds
+                ).unwrap();
+                return (vec![claim_allocation_instruction], vec![]);
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::InitDistributor {
+                    merkle_root,
+                    time_basis,
+                    start_time,
+                    cliff_time,
+                    end_time,
+                    total_amount,
+                    ..
+                },
+                ..
+            } => {
+                let init_distributor_instruction = init_distributor(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.system_program_id,
+                    &token_vesting_testenv.rent_program_id,
+                    &correct_payer.pubkey(),
+                    &correct_vesting_account_key,
+                    &mint_key.pubkey(),
+                    *merkle_root,
+                    *time_basis,
+                    *start_time,
+                    *cliff_time,
+                    *end_time,
+                    *total_amount,
+                    correct_seeds
+                ).unwrap();
+                return (vec![init_distributor_instruction], vec![]);
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::ClaimDistribution { amount, proof, .. },
+                ..
+            } => {
+                let claim_distribution_instruction = claim_distribution(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.token_program_id,
+                    &token_vesting_testenv.system_program_id,
+                    &token_vesting_testenv.sysvarclock_program_id,
+                    &correct_vesting_account_key,
+                    &correct_vesting_token_key,
+                    &destination_token_owner_key.pubkey(),
+                    destination_token_key,
+                    *amount,
+                    proof.clone(),
+                    correct_seeds
+                ).unwrap();
+                return (
+                    vec![claim_distribution_instruction],
+                    vec![clone_keypair(destination_token_owner_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::ChangeSplitDestination { .. },
+                ..
+            } => {
+                let change_split_instruction = change_split_destination(
+                    &token_vesting_testenv.vesting_program_id,
+                    &correct_vesting_account_key,
+                    &destination_token_owner_key.pubkey(),
+                    destination_token_key,
+                    new_destination_token_key,
+                    correct_seeds
+                ).unwrap();
+                return (
+                    vec![change_split_instruction],
+                    vec![clone_keypair(destination_token_owner_key)]
+                );
             }
         };
 
@@ -403,7 +663,12 @@ fn run_fuzz_instruction(
             },
 
             FuzzInstruction {
-                instruction: VestingInstruction::Create { .. },
+                instruction: VestingInstruction::Create { .. }
+                    | VestingInstruction::CreateWithTimeBasis { .. }
+                    | VestingInstruction::CreateMilestones { .. }
+                    | VestingInstruction::CreatePausable { .. }
+                    | VestingInstruction::CreateWithSplits { .. }
+                    | VestingInstruction::CreateNative { .. },
                 ..
             } => {
                 let create_instructions = create(
@@ -459,6 +724,242 @@ fn run_fuzz_instruction(
                     vec![change_instruction],
                     vec![clone_keypair(destination_token_owner_key)]
                 );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::UnlockWithAssociatedDestination{ .. },
+                ..
+            } => {
+                let unlock_instruction = unlock_with_associated_destination(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.token_program_id,
+                    &token_vesting_testenv.sysvarclock_program_id,
+                    &token_vesting_testenv.system_program_id,
+                    &spl_associated_token_account::id(),
+                    vesting_account_key,
+                    vesting_token_account_key,
+                    &destination_token_owner_key.pubkey(),
+                    &mint_key.pubkey(),
+                    &payer_key.pubkey(),
+                    fuzz_instruction.seeds,
+                ).unwrap();
+                return (
+                    vec![unlock_instruction],
+                    vec![clone_keypair(payer_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::GetClaimable,
+                ..
+            } => {
+                let get_claimable_instruction = get_claimable(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.sysvarclock_program_id,
+                    vesting_account_key
+                ).unwrap();
+                return (vec![get_claimable_instruction], vec![]);
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::ReleaseMilestone { schedule_index },
+                ..
+            } => {
+                let release_milestone_instruction = release_milestone(
+                    &token_vesting_testenv.vesting_program_id,
+                    vesting_account_key,
+                    &payer_key.pubkey(),
+                    *schedule_index
+                ).unwrap();
+                return (
+                    vec![release_milestone_instruction],
+                    vec![clone_keypair(payer_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::Pause,
+                ..
+            } => {
+                let pause_instruction = pause(
+                    &token_vesting_testenv.vesting_program_id,
+                    vesting_account_key,
+                    &payer_key.pubkey()
+                ).unwrap();
+                return (
+                    vec![pause_instruction],
+                    vec![clone_keypair(payer_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::Resume { shift_release_times },
+                ..
+            } => {
+                let resume_instruction = resume(
+                    &token_vesting_testenv.vesting_program_id,
+                    vesting_account_key,
+                    &payer_key.pubkey(),
+                    *shift_release_times
+                ).unwrap();
+                return (
+                    vec![resume_instruction],
+                    vec![clone_keypair(payer_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::InitPool {
+                    seeds,
+                    mint_address,
+                    pool_authority,
+                    time_basis
+                },
+                ..
+            } => {
+                let init_pool_instruction = init_pool(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.system_program_id,
+                    &token_vesting_testenv.rent_program_id,
+                    &payer_key.pubkey(),
+                    vesting_account_key,
+                    mint_address,
+                    pool_authority,
+                    *time_basis,
+                    *seeds
+                ).unwrap();
+                return (
+                    vec![init_pool_instruction],
+                    vec![clone_keypair(payer_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::AddAllocation { schedules },
+                ..
+            } => {
+                let add_allocation_instruction = add_allocation(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.system_program_id,
+                    &payer_key.pubkey(),
+                    vesting_account_key,
+                    &payer_key.pubkey(),
+                    vesting_token_account_key,
+                    destination_token_key,
+                    schedules.clone()
+                ).unwrap();
+                return (
+                    vec![add_allocation_instruction],
+                    vec![clone_keypair(payer_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::RemoveAllocation,
+                ..
+            } => {
+                let remove_allocation_instruction = remove_allocation(
+                    &token_vesting_testenv.vesting_program_id,
+                    vesting_account_key,
+                    &payer_key.pubkey(),
+                    destination_token_key,
+                    new_destination_token_key
+                ).unwrap();
+                return (
+                    vec![remove_allocation_instruction],
+                    vec![clone_keypair(payer_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::ClaimAllocation { seeds },
+                ..
+            } => {
+                let claim_allocation_instruction = claim_allocation(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.token_program_id,
+                    &token_vesting_testenv.sysvarclock_program_id,
+                    vesting_account_key,
+                    vesting_token_account_key,
+                    destination_token_key,
+                    *seeds
+                ).unwrap();
+                return (vec![claim_allocation_instruction], vec![]);
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::InitDistributor {
+                    seeds,
+                    mint_address,
+                    merkle_root,
+                    time_basis,
+                    start_time,
+                    cliff_time,
+                    end_time,
+                    total_amount
+                },
+                ..
+            } => {
+                let init_distributor_instruction = init_distributor(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.system_program_id,
+                    &token_vesting_testenv.rent_program_id,
+                    &payer_key.pubkey(),
+                    vesting_account_key,
+                    mint_address,
+                    *merkle_root,
+                    *time_basis,
+                    *start_time,
+                    *cliff_time,
+                    *end_time,
+                    *total_amount,
+                    *seeds
+                ).unwrap();
+                return (
+                    vec![init_distributor_instruction],
+                    vec![clone_keypair(payer_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::ClaimDistribution { seeds, amount, proof },
+                ..
+            } => {
+                let claim_distribution_instruction = claim_distribution(
+                    &token_vesting_testenv.vesting_program_id,
+                    &token_vesting_testenv.token_program_id,
+                    &token_vesting_testenv.system_program_id,
+                    &token_vesting_testenv.sysvarclock_program_id,
+                    vesting_account_key,
+                    vesting_token_account_key,
+                    &payer_key.pubkey(),
+                    destination_token_key,
+                    *amount,
+                    proof.clone(),
+                    *seeds
+                ).unwrap();
+                return (
+                    vec![claim_distribution_instruction],
+                    vec![clone_keypair(payer_key)]
+                );
+            },
+
+            FuzzInstruction {
+                instruction: VestingInstruction::ChangeSplitDestination { seeds },
+                ..
+            } => {
+                let change_split_instruction = change_split_destination(
+                    &token_vesting_testenv.vesting_program_id,
+                    vesting_account_key,
+                    &destination_token_owner_key.pubkey(),
+                    destination_token_key,
+                    new_destination_token_key,
+                    *seeds
+                ).unwrap();
+                return (
+                    vec![change_split_instruction],
+                    vec![clone_keypair(destination_token_owner_key)]
+                );
             }
         };
     }
@@ -7,7 +7,43 @@ use thiserror::Error;
 pub enum VestingError {
     // Invalid instruction
     #[error("Invalid Instruction")]
-    InvalidInstruction
+    InvalidInstruction,
+    // An account that should sign the transaction did not
+    #[error("Missing Required Signer")]
+    MissingRequiredSigner,
+    // An account that is modified was not passed as writable
+    #[error("Account Not Writable")]
+    AccountNotWritable,
+    // A program or sysvar account does not have the expected address
+    #[error("Incorrect Account Key")]
+    IncorrectAccountKey,
+    // An account is not owned by the expected program
+    #[error("Incorrect Account Owner")]
+    IncorrectAccountOwner,
+    // The seeds do not end with the canonical bump seed of the vesting account
+    #[error("Non Canonical Bump Seed")]
+    NonCanonicalBumpSeed,
+    // The signer is not the approver of a milestone contract
+    #[error("Incorrect Approver")]
+    IncorrectApprover,
+    // The signer is not the pause authority of a pausable contract
+    #[error("Incorrect Pause Authority")]
+    IncorrectPauseAuthority,
+    // The contract is paused and cannot be unlocked
+    #[error("Contract Paused")]
+    ContractPaused,
+    // The split destinations are too many or their shares exceed the whole release
+    #[error("Invalid Destination Splits")]
+    InvalidDestinationSplits,
+    // The signer is not the authority of a pool
+    #[error("Incorrect Pool Authority")]
+    IncorrectPoolAuthority,
+    // The allocations of a pool would exceed the balance of its token account
+    #[error("Pool Over Allocated")]
+    PoolOverAllocated,
+    // The proof does not lead from the claimed allocation to the root of the distributor
+    #[error("Invalid Merkle Proof")]
+    InvalidMerkleProof,
 }
 
 impl From<VestingError> for ProgramError {
@@ -0,0 +1,221 @@
+//! Structured events logged by the program so that indexers can follow vesting contracts without
+//! decoding the token transfers.
+//!
+//! Events show up in the transaction logs as `Program log: VestingEvent: <base64>`, the payload
+//! being the layout version followed by the borsh serialized `VestingEvent`.
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{msg, pubkey::Pubkey};
+
+use crate::instruction::Schedule;
+
+/// Prefix of the log lines written by `msg!`
+pub const PROGRAM_LOG_PREFIX: &str = "Program log: ";
+
+/// Marks the logs holding an event
+pub const EVENT_LOG_PREFIX: &str = "VestingEvent: ";
+
+/// Version of the event layout, bumped whenever it changes in an incompatible way
+pub const EVENT_VERSION: u8 = 1;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum VestingEvent {
+    /// A vesting contract was created and funded
+    Created {
+        vesting_account: Pubkey,
+        mint_address: Pubkey,
+        destination_token_address: Pubkey,
+        total_amount: u64,
+        schedules: Vec<Schedule>,
+        unix_timestamp: i64,
+    },
+    /// The schedules that reached their release time were unlocked
+    Unlocked {
+        vesting_account: Pubkey,
+        destination_token_address: Pubkey,
+        amount: u64,
+        unix_timestamp: i64,
+    },
+    /// The destination of a vesting contract was changed by its owner
+    DestinationChanged {
+        vesting_account: Pubkey,
+        old_destination_token_address: Pubkey,
+        new_destination_token_address: Pubkey,
+        unix_timestamp: i64,
+    },
+    /// A milestone was approved, its schedule can be unlocked
+    MilestoneReleased {
+        vesting_account: Pubkey,
+        schedule_index: u32,
+        unix_timestamp: i64,
+    },
+    /// A vesting contract was paused by its pause authority
+    Paused {
+        vesting_account: Pubkey,
+        unix_timestamp: i64,
+    },
+    /// A paused vesting contract was resumed, the paused duration being in its time basis
+    Resumed {
+        vesting_account: Pubkey,
+        paused_duration: u64,
+        shifted_release_times: bool,
+        unix_timestamp: i64,
+    },
+    /// A treasury pool was created, its allocations being funded by its associated token account
+    PoolCreated {
+        pool_account: Pubkey,
+        mint_address: Pubkey,
+        pool_authority: Pubkey,
+        unix_timestamp: i64,
+    },
+    /// The pool authority allocated an amount of the pool to a beneficiary
+    AllocationAdded {
+        pool_account: Pubkey,
+        allocation_account: Pubkey,
+        destination_token_address: Pubkey,
+        total_amount: u64,
+        schedules: Vec<Schedule>,
+        unix_timestamp: i64,
+    },
+    /// The pool authority removed an allocation, the schedules that had not reached their release
+    /// time returning to the pool
+    AllocationRemoved {
+        pool_account: Pubkey,
+        allocation_account: Pubkey,
+        cancelled_amount: u64,
+        unix_timestamp: i64,
+    },
+    /// The schedules of an allocation that reached their release time were claimed
+    AllocationClaimed {
+        pool_account: Pubkey,
+        allocation_account: Pubkey,
+        destination_token_address: Pubkey,
+        amount: u64,
+        unix_timestamp: i64,
+    },
+    /// A Merkle distributor was created, its associated token account being funded separately
+    DistributorCreated {
+        distributor_account: Pubkey,
+        mint_address: Pubkey,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        unix_timestamp: i64,
+    },
+    /// The vested part of an allocation of a distributor was claimed
+    DistributionClaimed {
+        distributor_account: Pubkey,
+        claimant: Pubkey,
+        destination_token_address: Pubkey,
+        amount: u64,
+        unix_timestamp: i64,
+    },
+    /// A split destination of a vesting contract was changed by its owner
+    SplitDestinationChanged {
+        vesting_account: Pubkey,
+        old_split_token_address: Pubkey,
+        new_split_token_address: Pubkey,
+        unix_timestamp: i64,
+    },
+}
+
+impl VestingEvent {
+    pub fn pack(&self) -> Vec<u8> {
+        let mut buf = vec![EVENT_VERSION];
+        // Serializing into a vector cannot fail
+        self.serialize(&mut buf).unwrap();
+        buf
+    }
+
+    /// Decodes an event payload, returns `None` for an unknown layout version
+    pub fn unpack(input: &[u8]) -> Option<Self> {
+        let (&version, rest) = input.split_first()?;
+        if version != EVENT_VERSION {
+            return None;
+        }
+        Self::try_from_slice(rest).ok()
+    }
+
+    /// Logs the event, to be called by the processor once the instruction has succeeded
+    pub fn emit(&self) {
+        msg!("{}{}", EVENT_LOG_PREFIX, base64::encode(self.pack()));
+    }
+
+    /// Decodes the event of a transaction log line, if it holds one
+    pub fn from_log(log: &str) -> Option<Self> {
+        let encoded = log
+            .strip_prefix(PROGRAM_LOG_PREFIX)?
+            .strip_prefix(EVENT_LOG_PREFIX)?;
+        Self::unpack(&base64::decode(encoded).ok()?)
+    }
+
+    /// Decodes the events logged by the given vesting program in the logs of a transaction. The
+    /// invocations are followed so that logs written by other programs are ignored.
+    pub fn from_logs<S: AsRef<str>>(program_id: &Pubkey, logs: &[S]) -> Vec<Self> {
+        let program_id = program_id.to_string();
+        let mut invoked_programs: Vec<&str> = vec![];
+        let mut events = vec![];
+        for log in logs.iter().map(AsRef::as_ref) {
+            let mut words = log.split(' ');
+            match (words.next(), words.next(), words.next()) {
+                (Some("Program"), Some(id), Some("invoke")) => invoked_programs.push(id),
+                (Some("Program"), Some(_), Some("success"))
+                | (Some("Program"), Some(_), Some("failed:")) => {
+                    invoked_programs.pop();
+                }
+                _ => {
+                    if invoked_programs.last() == Some(&program_id.as_str()) {
+                        events.extend(Self::from_log(log));
+                    }
+                }
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VestingEvent, EVENT_LOG_PREFIX, EVENT_VERSION, PROGRAM_LOG_PREFIX};
+    use crate::instruction::Schedule;
+    use solana_program::pubkey::Pubkey;
+
+    #[test]
+    fn test_event_packing() {
+        let created = VestingEvent::Created {
+            vesting_account: Pubkey::new_unique(),
+            mint_address: Pubkey::new_unique(),
+            destination_token_address: Pubkey::new_unique(),
+            total_amount: 42,
+            schedules: vec![Schedule {
+                release_time: 1609459200,
+                amount: 42,
+            }],
+            unix_timestamp: 1577836800,
+        };
+        let packed = created.pack();
+        assert_eq!(packed[0], EVENT_VERSION);
+        assert_eq!(VestingEvent::unpack(&packed), Some(created.clone()));
+
+        let log = format!(
+            "{}{}{}",
+            PROGRAM_LOG_PREFIX,
+            EVENT_LOG_PREFIX,
+            base64::encode(&packed)
+        );
+        let program_id = Pubkey::new_unique();
+        let other_program_id = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            "Program log: Instruction: Create Schedule".to_string(),
+            format!("Program {} invoke [2]", other_program_id),
+            log.clone(),
+            format!("Program {} success", other_program_id),
+            log,
+            format!("Program {} success", program_id),
+        ];
+        assert_eq!(VestingEvent::from_logs(&program_id, &logs), vec![created]);
+
+        let mut unknown_version = packed;
+        unknown_version[0] += 1;
+        assert_eq!(VestingEvent::unpack(&unknown_version), None);
+    }
+}
@@ -1,14 +1,20 @@
-use crate::error::VestingError;
+use crate::{
+    error::VestingError,
+    state::{
+        find_allocation_address, find_distribution_claim_address, DestinationSplit, TimeBasis,
+    },
+};
 
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     instruction::{AccountMeta, Instruction},
     msg,
     program_error::ProgramError,
     pubkey::Pubkey
 };
+use spl_associated_token_account::get_associated_token_address;
 
 use std::convert::TryInto;
-use std::mem::size_of;
 
 #[cfg(feature = "fuzz")]
 use arbitrary::Arbitrary;
@@ -17,7 +23,9 @@ use arbitrary::Arbitrary;
 impl Arbitrary for VestingInstruction {
     fn arbitrary(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<Self> {
         let seeds: [u8; 32] = u.arbitrary()?;
-        let choice = u.choose(&[0, 1, 2, 3])?;
+        let choice = u.choose(&[
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+        ])?;
         match choice {
             0 => {
                 let number_of_schedules = u.arbitrary()?;
@@ -40,14 +48,159 @@ impl Arbitrary for VestingInstruction {
                 });
             }
             2 => return Ok(Self::Unlock { seeds }),
-            _ => return Ok(Self::ChangeDestination { seeds }),
+            3 => return Ok(Self::ChangeDestination { seeds }),
+            4 => return Ok(Self::UnlockWithAssociatedDestination { seeds }),
+            5 => return Ok(Self::GetClaimable),
+            6 => {
+                let schedules: [Schedule; 10] = u.arbitrary()?;
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let mint_address: Pubkey = Pubkey::new(&key_bytes);
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let destination_token_address: Pubkey = Pubkey::new(&key_bytes);
+                let time_basis = *u.choose(&[
+                    TimeBasis::UnixTimestamp,
+                    TimeBasis::Slot,
+                    TimeBasis::Epoch,
+                ])?;
+                return Ok(Self::CreateWithTimeBasis {
+                    seeds,
+                    mint_address,
+                    destination_token_address,
+                    time_basis,
+                    schedules: schedules.to_vec(),
+                });
+            }
+            7 => {
+                let schedules: [Schedule; 10] = u.arbitrary()?;
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let mint_address: Pubkey = Pubkey::new(&key_bytes);
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let destination_token_address: Pubkey = Pubkey::new(&key_bytes);
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let approver: Pubkey = Pubkey::new(&key_bytes);
+                return Ok(Self::CreateMilestones {
+                    seeds,
+                    mint_address,
+                    destination_token_address,
+                    time_basis: TimeBasis::UnixTimestamp,
+                    approver,
+                    schedules: schedules.to_vec(),
+                });
+            }
+            8 => {
+                let schedule_index = u.arbitrary()?;
+                return Ok(Self::ReleaseMilestone { schedule_index });
+            }
+            9 => {
+                let schedules: [Schedule; 10] = u.arbitrary()?;
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let mint_address: Pubkey = Pubkey::new(&key_bytes);
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let destination_token_address: Pubkey = Pubkey::new(&key_bytes);
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let pause_authority: Pubkey = Pubkey::new(&key_bytes);
+                return Ok(Self::CreatePausable {
+                    seeds,
+                    mint_address,
+                    destination_token_address,
+                    time_basis: TimeBasis::UnixTimestamp,
+                    approver: None,
+                    pause_authority,
+                    schedules: schedules.to_vec(),
+                });
+            }
+            10 => return Ok(Self::Pause),
+            11 => {
+                let shift_release_times = u.arbitrary()?;
+                return Ok(Self::Resume {
+                    shift_release_times,
+                });
+            }
+            12 => {
+                let schedules: [Schedule; 10] = u.arbitrary()?;
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let mint_address: Pubkey = Pubkey::new(&key_bytes);
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let destination_token_address: Pubkey = Pubkey::new(&key_bytes);
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let split_destination = DestinationSplit {
+                    token_address: Pubkey::new(&key_bytes),
+                    basis_points: u.arbitrary()?,
+                };
+                return Ok(Self::CreateWithSplits {
+                    seeds,
+                    mint_address,
+                    destination_token_address,
+                    time_basis: TimeBasis::UnixTimestamp,
+                    approver: None,
+                    pause_authority: None,
+                    split_destinations: vec![split_destination],
+                    schedules: schedules.to_vec(),
+                });
+            }
+            13 => {
+                let schedules: [Schedule; 10] = u.arbitrary()?;
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let destination_address: Pubkey = Pubkey::new(&key_bytes);
+                return Ok(Self::CreateNative {
+                    seeds,
+                    destination_address,
+                    time_basis: TimeBasis::UnixTimestamp,
+                    approver: None,
+                    pause_authority: None,
+                    schedules: schedules.to_vec(),
+                });
+            }
+            14 => {
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let mint_address: Pubkey = Pubkey::new(&key_bytes);
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let pool_authority: Pubkey = Pubkey::new(&key_bytes);
+                return Ok(Self::InitPool {
+                    seeds,
+                    mint_address,
+                    pool_authority,
+                    time_basis: TimeBasis::UnixTimestamp,
+                });
+            }
+            15 => {
+                let schedules: [Schedule; 10] = u.arbitrary()?;
+                return Ok(Self::AddAllocation {
+                    schedules: schedules.to_vec(),
+                });
+            }
+            16 => return Ok(Self::RemoveAllocation),
+            17 => return Ok(Self::ClaimAllocation { seeds }),
+            18 => {
+                let key_bytes: [u8; 32] = u.arbitrary()?;
+                let mint_address: Pubkey = Pubkey::new(&key_bytes);
+                return Ok(Self::InitDistributor {
+                    seeds,
+                    mint_address,
+                    merkle_root: u.arbitrary()?,
+                    time_basis: TimeBasis::UnixTimestamp,
+                    start_time: u.arbitrary()?,
+                    cliff_time: u.arbitrary()?,
+                    end_time: u.arbitrary()?,
+                    total_amount: u.arbitrary()?,
+                });
+            }
+            19 => {
+                let proof: [[u8; 32]; 4] = u.arbitrary()?;
+                return Ok(Self::ClaimDistribution {
+                    seeds,
+                    amount: u.arbitrary()?,
+                    proof: proof.to_vec(),
+                });
+            }
+            _ => return Ok(Self::ChangeSplitDestination { seeds }),
         }
     }
 }
 
 #[cfg_attr(feature = "fuzz", derive(Arbitrary))]
 #[repr(C)]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
 pub struct Schedule {
     // Schedule release time in unix timestamp
     pub release_time: u64,
@@ -56,11 +209,22 @@ pub struct Schedule {
 
 pub const SCHEDULE_SIZE: usize = 16;
 
+/// First byte of the versioned instruction layout, followed by the layout version and the borsh
+/// serialized instruction. Instructions of the legacy layout start with their tag instead.
+pub const VERSIONED_INSTRUCTION_TAG: u8 = 0xff;
+
+/// Version of the borsh layout, bumped whenever it changes in an incompatible way
+pub const INSTRUCTION_VERSION: u8 = 1;
+
 #[repr(C)]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
 pub enum VestingInstruction {
     /// Initializes an empty program account for the token_vesting program
     ///
+    /// Accounts initialized by earlier versions of the program only have room for a legacy
+    /// header: `Create` writes its contract in the legacy layout, and the instructions creating
+    /// contracts with options fail on them.
+    ///
     /// Accounts expected by this instruction:
     ///
     ///   * Single owner
@@ -69,7 +233,8 @@ pub enum VestingInstruction {
     ///   1. `[signer]` The fee payer account
     ///   1. `[]` The vesting account
     Init {
-        // The seed used to derive the vesting accounts address
+        // The seed used to derive the vesting accounts address, its last byte being the canonical
+        // bump seed found with `find_program_address` from the first 31 bytes
         seeds: [u8; 32],
         // The number of release schedules for this contract to hold
         number_of_schedules: u32,
@@ -91,7 +256,8 @@ pub enum VestingInstruction {
         schedules: Vec<Schedule>,
     },
     /// Unlocks a simple vesting contract (SVC) - can only be invoked by the program itself
-    /// Accounts expected by this instruction:
+    /// Accounts expected by this instruction, the accounts of a native contract being the system
+    /// program account, the clock sysvar account, the vesting account and the destination wallet:
     ///
     ///   * Single owner
     ///   0. `[]` The spl-token program account
@@ -99,11 +265,14 @@ pub enum VestingInstruction {
     ///   1. `[writable]` The vesting account
     ///   2. `[writable]` The vesting spl-token account
     ///   3. `[writable]` The destination spl-token account
+    ///   4. `[writable]` The split destination spl-token accounts of the contract, in order, if any
     Unlock { seeds: [u8; 32] },
 
     /// Change the destination account of a given simple vesting contract (SVC)
     /// - can only be invoked by the present destination address of the contract.
     ///
+    /// The destination accounts of a native contract are wallets, the current one signing.
+    ///
     /// Accounts expected by this instruction:
     ///
     ///   * Single owner
@@ -112,63 +281,341 @@ pub enum VestingInstruction {
     ///   2. `[signer]` The destination spl-token account owner
     ///   3. `[]` The new destination spl-token account
     ChangeDestination { seeds: [u8; 32] },
+
+    /// Unlocks a simple vesting contract (SVC) into the associated token account of the
+    /// destination wallet, creating this account first if it does not exist.
+    /// The contract destination must be that associated token account.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner
+    ///   0. `[]` The spl-token program account
+    ///   1. `[]` The clock sysvar account
+    ///   2. `[writable]` The vesting account
+    ///   3. `[writable]` The vesting spl-token account
+    ///   4. `[writable]` The destination associated spl-token account
+    ///   5. `[]` The destination wallet account
+    ///   6. `[]` The mint account
+    ///   7. `[writable, signer]` The fee payer account, funding the destination account
+    ///   8. `[]` The system program account
+    ///   9. `[]` The associated token account program account
+    ///   10. `[writable]` The split destination spl-token accounts of the contract, in order, if any
+    UnlockWithAssociatedDestination { seeds: [u8; 32] },
+
+    /// Computes the amounts of a vesting contract at the current time without modifying it,
+    /// and returns them as a packed `ClaimableAmounts` through the return data
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The vesting account
+    ///   1. `[]` The clock sysvar account
+    GetClaimable,
+
+    /// Creates a new vesting schedule contract whose release times are slots or epochs instead
+    /// of unix timestamps, as given by the time basis
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner
+    ///   0. `[]` The spl-token program account
+    ///   1. `[writable]` The vesting account
+    ///   2. `[writable]` The vesting spl-token account
+    ///   3. `[signer]` The source spl-token account owner
+    ///   4. `[writable]` The source spl-token account
+    CreateWithTimeBasis {
+        seeds: [u8; 32],
+        mint_address: Pubkey,
+        destination_token_address: Pubkey,
+        time_basis: TimeBasis,
+        schedules: Vec<Schedule>,
+    },
+
+    /// Creates a new vesting contract whose schedules are milestones: a schedule can be unlocked
+    /// once the approver signs a `ReleaseMilestone` instruction for it, or at its release time as
+    /// a fallback. A release time of `u64::MAX` disables the fallback.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner
+    ///   0. `[]` The spl-token program account
+    ///   1. `[writable]` The vesting account
+    ///   2. `[writable]` The vesting spl-token account
+    ///   3. `[signer]` The source spl-token account owner
+    ///   4. `[writable]` The source spl-token account
+    CreateMilestones {
+        seeds: [u8; 32],
+        mint_address: Pubkey,
+        destination_token_address: Pubkey,
+        time_basis: TimeBasis,
+        approver: Pubkey,
+        schedules: Vec<Schedule>,
+    },
+
+    /// Marks a schedule of a milestone contract as releasable regardless of its release time
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The vesting account
+    ///   1. `[signer]` The approver of the contract
+    ReleaseMilestone { schedule_index: u32 },
+
+    /// Creates a new vesting contract that its pause authority can pause with a `Pause`
+    /// instruction, the schedules being milestones as well if an approver is given
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner
+    ///   0. `[]` The spl-token program account
+    ///   1. `[writable]` The vesting account
+    ///   2. `[writable]` The vesting spl-token account
+    ///   3. `[signer]` The source spl-token account owner
+    ///   4. `[writable]` The source spl-token account
+    CreatePausable {
+        seeds: [u8; 32],
+        mint_address: Pubkey,
+        destination_token_address: Pubkey,
+        time_basis: TimeBasis,
+        approver: Option<Pubkey>,
+        pause_authority: Pubkey,
+        schedules: Vec<Schedule>,
+    },
+
+    /// Pauses a pausable contract, refusing every unlock until it is resumed
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The vesting account
+    ///   1. `[signer]` The pause authority of the contract
+    Pause,
+
+    /// Resumes a paused contract, optionally delaying the schedules that had not reached their
+    /// release time when it was paused by the time it stayed paused
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The vesting account
+    ///   1. `[signer]` The pause authority of the contract
+    Resume { shift_release_times: bool },
+
+    /// Creates a new vesting contract whose releases are split between its destination and up to
+    /// `MAX_SPLIT_DESTINATIONS` other destinations, each receiving its share in basis points
+    /// rounded down, the destination receiving the rest. The contract is pausable and its
+    /// schedules are milestones if a pause authority and an approver are given.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner
+    ///   0. `[]` The spl-token program account
+    ///   1. `[writable]` The vesting account
+    ///   2. `[writable]` The vesting spl-token account
+    ///   3. `[signer]` The source spl-token account owner
+    ///   4. `[writable]` The source spl-token account
+    ///   5. `[]` The split destination spl-token accounts, in order, of the mint of the contract
+    CreateWithSplits {
+        seeds: [u8; 32],
+        mint_address: Pubkey,
+        destination_token_address: Pubkey,
+        time_basis: TimeBasis,
+        approver: Option<Pubkey>,
+        pause_authority: Option<Pubkey>,
+        split_destinations: Vec<DestinationSplit>,
+        schedules: Vec<Schedule>,
+    },
+
+    /// Creates a new vesting contract of native SOL, the vesting account holding the lamports
+    /// that `Unlock` transfers to the destination wallet. The contract is pausable and its
+    /// schedules are milestones if a pause authority and an approver are given.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner
+    ///   0. `[]` The system program account
+    ///   1. `[writable]` The vesting account
+    ///   2. `[writable, signer]` The source wallet
+    CreateNative {
+        seeds: [u8; 32],
+        destination_address: Pubkey,
+        time_basis: TimeBasis,
+        approver: Option<Pubkey>,
+        pause_authority: Option<Pubkey>,
+        schedules: Vec<Schedule>,
+    },
+
+    /// Initializes a treasury pool whose authority allocates the tokens of the associated token
+    /// account of the pool account to beneficiaries, each allocation having its own schedules
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The system program account
+    ///   1. `[]` The sysvar Rent account
+    ///   2. `[writable, signer]` The fee payer account
+    ///   3. `[writable]` The pool account
+    InitPool {
+        // The seed used to derive the pool account address, its last byte being the canonical
+        // bump seed found with `find_program_address` from the first 31 bytes
+        seeds: [u8; 32],
+        mint_address: Pubkey,
+        pool_authority: Pubkey,
+        time_basis: TimeBasis,
+    },
+
+    /// Allocates an amount of a pool to a beneficiary, released by the given schedules. The
+    /// amount of the allocations not claimed yet cannot exceed the balance of the pool token
+    /// account.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The system program account
+    ///   1. `[writable, signer]` The fee payer account, funding the allocation account
+    ///   2. `[writable]` The pool account
+    ///   3. `[signer]` The pool authority
+    ///   4. `[]` The pool spl-token account, associated token account of the pool account
+    ///   5. `[writable]` The allocation account, derived from the pool account and the
+    ///      destination spl-token account
+    ///   6. `[]` The destination spl-token account of the beneficiary
+    AddAllocation { schedules: Vec<Schedule> },
+
+    /// Removes an allocation of a pool: the schedules that have not reached their release time
+    /// return to the pool, the others can still be claimed. The allocation account is closed
+    /// once nothing remains to claim.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The pool account
+    ///   1. `[signer]` The pool authority
+    ///   2. `[writable]` The allocation account
+    ///   3. `[writable]` The account receiving the rent of the allocation account when it is closed
+    RemoveAllocation,
+
+    /// Claims the schedules of an allocation that have reached their release time into the
+    /// destination spl-token account of the beneficiary
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The spl-token program account
+    ///   1. `[]` The clock sysvar account
+    ///   2. `[writable]` The pool account
+    ///   3. `[writable]` The pool spl-token account
+    ///   4. `[writable]` The allocation account
+    ///   5. `[writable]` The destination spl-token account
+    ClaimAllocation { seeds: [u8; 32] },
+
+    /// Initializes a distributor releasing the allocations committed to by the root of a Merkle
+    /// tree, funded by the associated token account of the distributor account. Every allocation
+    /// vests linearly from the start time to the end time, nothing being released before the
+    /// cliff time.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The system program account
+    ///   1. `[]` The sysvar Rent account
+    ///   2. `[writable, signer]` The fee payer account
+    ///   3. `[writable]` The distributor account
+    InitDistributor {
+        // The seed used to derive the distributor account address, its last byte being the
+        // canonical bump seed found with `find_program_address` from the first 31 bytes
+        seeds: [u8; 32],
+        mint_address: Pubkey,
+        // Root of the tree whose leaves are the claimants and their allocations
+        merkle_root: [u8; 32],
+        time_basis: TimeBasis,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        // Sum of the allocations of the tree
+        total_amount: u64,
+    },
+
+    /// Claims the vested part of the allocation of the signing claimant into a destination
+    /// spl-token account. The first claim verifies the proof of the allocation and creates the
+    /// claim account of the claimant, the proof being ignored afterwards.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The spl-token program account
+    ///   1. `[]` The system program account
+    ///   2. `[]` The clock sysvar account
+    ///   3. `[writable]` The distributor account
+    ///   4. `[writable]` The distributor spl-token account, associated token account of the
+    ///      distributor account
+    ///   5. `[writable]` The claim account, derived from the distributor account and the claimant
+    ///   6. `[writable, signer]` The claimant, funding the claim account
+    ///   7. `[writable]` The destination spl-token account
+    ClaimDistribution {
+        seeds: [u8; 32],
+        // Allocation of the claimant in the tree
+        amount: u64,
+        // Siblings of the nodes from the leaf of the allocation up to the root
+        proof: Vec<[u8; 32]>,
+    },
+
+    /// Changes a split destination of a vesting contract, signed by the owner of its current
+    /// spl-token account. The new split destination has to be a spl-token account of the mint of
+    /// the contract.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The vesting account
+    ///   1. `[]` The current split destination spl-token account
+    ///   2. `[signer]` The current split destination spl-token account owner
+    ///   3. `[]` The new split destination spl-token account
+    ChangeSplitDestination { seeds: [u8; 32] },
 }
 
 impl VestingInstruction {
     pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
         use VestingError::InvalidInstruction;
         let (&tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+        if tag != VERSIONED_INSTRUCTION_TAG {
+            return Self::unpack_legacy(tag, rest);
+        }
+        let (&version, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+        if version != INSTRUCTION_VERSION {
+            msg!("Unsupported instruction version");
+            return Err(InvalidInstruction.into());
+        }
+        // Fails on short data as well as on trailing bytes
+        Self::try_from_slice(rest).map_err(|_| InvalidInstruction.into())
+    }
+
+    // Layout of the instructions sent by clients predating the versioned layout
+    fn unpack_legacy(tag: u8, rest: &[u8]) -> Result<Self, ProgramError> {
+        use VestingError::InvalidInstruction;
+        if tag == 5 {
+            if !rest.is_empty() {
+                return Err(InvalidInstruction.into());
+            }
+            return Ok(Self::GetClaimable);
+        }
+        let seeds: [u8; 32] = rest
+            .get(..32)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(InvalidInstruction)?;
+        let rest = &rest[32..];
         Ok(match tag {
             0 => {
-                let seeds: [u8; 32] = rest
-                    .get(..32)
-                    .and_then(|slice| slice.try_into().ok())
-                    .unwrap();
-                let number_of_schedules = rest
-                    .get(32..36)
-                    .and_then(|slice| slice.try_into().ok())
-                    .map(u32::from_le_bytes)
-                    .ok_or(InvalidInstruction)?;
+                if rest.len() != 4 {
+                    return Err(InvalidInstruction.into());
+                }
                 Self::Init {
                     seeds,
-                    number_of_schedules,
+                    number_of_schedules: u32::from_le_bytes(rest.try_into().unwrap()),
                 }
             }
             1 => {
-                let seeds: [u8; 32] = rest
-                    .get(..32)
-                    .and_then(|slice| slice.try_into().ok())
-                    .unwrap();
-                let mint_address = rest
-                    .get(32..64)
-                    .and_then(|slice| slice.try_into().ok())
-                    .map(Pubkey::new)
-                    .ok_or(InvalidInstruction)?;
-                let destination_token_address = rest
-                    .get(64..96)
-                    .and_then(|slice| slice.try_into().ok())
-                    .map(Pubkey::new)
-                    .ok_or(InvalidInstruction)?;
-                let number_of_schedules = rest[96..].len() / SCHEDULE_SIZE;
-                let mut schedules: Vec<Schedule> = Vec::with_capacity(number_of_schedules);
-                let mut offset = 96;
-                for _ in 0..number_of_schedules {
-                    let release_time = rest
-                        .get(offset..offset + 8)
-                        .and_then(|slice| slice.try_into().ok())
-                        .map(u64::from_le_bytes)
-                        .ok_or(InvalidInstruction)?;
-                    let amount = rest
-                        .get(offset + 8..offset + 16)
-                        .and_then(|slice| slice.try_into().ok())
-                        .map(u64::from_le_bytes)
-                        .ok_or(InvalidInstruction)?;
-                    offset += SCHEDULE_SIZE;
-                    schedules.push(Schedule {
-                        release_time,
-                        amount,
-                    })
+                if rest.len() < 64 || (rest.len() - 64) % SCHEDULE_SIZE != 0 {
+                    return Err(InvalidInstruction.into());
                 }
+                let mint_address = Pubkey::new_from_array(rest[..32].try_into().unwrap());
+                let destination_token_address =
+                    Pubkey::new_from_array(rest[32..64].try_into().unwrap());
+                let schedules = rest[64..]
+                    .chunks_exact(SCHEDULE_SIZE)
+                    .map(|chunk| Schedule {
+                        release_time: u64::from_le_bytes(chunk[..8].try_into().unwrap()),
+                        amount: u64::from_le_bytes(chunk[8..].try_into().unwrap()),
+                    })
+                    .collect();
                 Self::Create {
                     seeds,
                     mint_address,
@@ -176,14 +623,14 @@ impl VestingInstruction {
                     schedules,
                 }
             }
-            2 | 3 => {
-                let seeds: [u8; 32] = rest
-                    .get(..32)
-                    .and_then(|slice| slice.try_into().ok())
-                    .unwrap();
+            2..=4 => {
+                if !rest.is_empty() {
+                    return Err(InvalidInstruction.into());
+                }
                 match tag {
                     2 => Self::Unlock { seeds },
-                    _ => Self::ChangeDestination { seeds },
+                    3 => Self::ChangeDestination { seeds },
+                    _ => Self::UnlockWithAssociatedDestination { seeds },
                 }
             }
             _ => {
@@ -194,40 +641,9 @@ impl VestingInstruction {
     }
 
     pub fn pack(&self) -> Vec<u8> {
-        let mut buf = Vec::with_capacity(size_of::<Self>());
-        match self {
-            &Self::Init {
-                seeds,
-                number_of_schedules,
-            } => {
-                buf.push(0);
-                buf.extend_from_slice(&seeds);
-                buf.extend_from_slice(&number_of_schedules.to_le_bytes())
-            }
-            Self::Create {
-                seeds,
-                mint_address,
-                destination_token_address,
-                schedules,
-            } => {
-                buf.push(1);
-                buf.extend_from_slice(seeds);
-                buf.extend_from_slice(&mint_address.to_bytes());
-                buf.extend_from_slice(&destination_token_address.to_bytes());
-                for s in schedules.iter() {
-                    buf.extend_from_slice(&s.release_time.to_le_bytes());
-                    buf.extend_from_slice(&s.amount.to_le_bytes());
-                }
-            }
-            &Self::Unlock { seeds } => {
-                buf.push(2);
-                buf.extend_from_slice(&seeds);
-            }
-            &Self::ChangeDestination { seeds } => {
-                buf.push(3);
-                buf.extend_from_slice(&seeds);
-            }
-        };
+        let mut buf = vec![VERSIONED_INSTRUCTION_TAG, INSTRUCTION_VERSION];
+        // Serializing into a vector cannot fail
+        self.serialize(&mut buf).unwrap();
         buf
     }
 }
@@ -294,20 +710,279 @@ pub fn create(
     })
 }
 
-// Creates an `Unlock` instruction
-pub fn unlock(
+// Creates a `CreateWithTimeBasis` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_with_time_basis(
     vesting_program_id: &Pubkey,
     token_program_id: &Pubkey,
-    clock_sysvar_id: &Pubkey,
     vesting_account_key: &Pubkey,
     vesting_token_account_key: &Pubkey,
+    source_token_account_owner_key: &Pubkey,
+    source_token_account_key: &Pubkey,
     destination_token_account_key: &Pubkey,
+    mint_address: &Pubkey,
+    time_basis: TimeBasis,
+    schedules: Vec<Schedule>,
     seeds: [u8; 32],
 ) -> Result<Instruction, ProgramError> {
-    let data = VestingInstruction::Unlock { seeds }.pack();
-    let accounts = vec![
-        AccountMeta::new_readonly(*token_program_id, false),
-        AccountMeta::new_readonly(*clock_sysvar_id, false),
+    let data = VestingInstruction::CreateWithTimeBasis {
+        seeds,
+        mint_address: *mint_address,
+        destination_token_address: *destination_token_account_key,
+        time_basis,
+        schedules,
+    }
+    .pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(*vesting_account_key, false),
+        AccountMeta::new(*vesting_token_account_key, false),
+        AccountMeta::new_readonly(*source_token_account_owner_key, true),
+        AccountMeta::new(*source_token_account_key, false),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates a `CreateMilestones` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_milestones(
+    vesting_program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    vesting_account_key: &Pubkey,
+    vesting_token_account_key: &Pubkey,
+    source_token_account_owner_key: &Pubkey,
+    source_token_account_key: &Pubkey,
+    destination_token_account_key: &Pubkey,
+    mint_address: &Pubkey,
+    time_basis: TimeBasis,
+    approver_key: &Pubkey,
+    schedules: Vec<Schedule>,
+    seeds: [u8; 32],
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::CreateMilestones {
+        seeds,
+        mint_address: *mint_address,
+        destination_token_address: *destination_token_account_key,
+        time_basis,
+        approver: *approver_key,
+        schedules,
+    }
+    .pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(*vesting_account_key, false),
+        AccountMeta::new(*vesting_token_account_key, false),
+        AccountMeta::new_readonly(*source_token_account_owner_key, true),
+        AccountMeta::new(*source_token_account_key, false),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates a `ReleaseMilestone` instruction
+pub fn release_milestone(
+    vesting_program_id: &Pubkey,
+    vesting_account_key: &Pubkey,
+    approver_key: &Pubkey,
+    schedule_index: u32,
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::ReleaseMilestone { schedule_index }.pack();
+    let accounts = vec![
+        AccountMeta::new(*vesting_account_key, false),
+        AccountMeta::new_readonly(*approver_key, true),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates a `CreatePausable` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_pausable(
+    vesting_program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    vesting_account_key: &Pubkey,
+    vesting_token_account_key: &Pubkey,
+    source_token_account_owner_key: &Pubkey,
+    source_token_account_key: &Pubkey,
+    destination_token_account_key: &Pubkey,
+    mint_address: &Pubkey,
+    time_basis: TimeBasis,
+    approver_key: Option<&Pubkey>,
+    pause_authority_key: &Pubkey,
+    schedules: Vec<Schedule>,
+    seeds: [u8; 32],
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::CreatePausable {
+        seeds,
+        mint_address: *mint_address,
+        destination_token_address: *destination_token_account_key,
+        time_basis,
+        approver: approver_key.copied(),
+        pause_authority: *pause_authority_key,
+        schedules,
+    }
+    .pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(*vesting_account_key, false),
+        AccountMeta::new(*vesting_token_account_key, false),
+        AccountMeta::new_readonly(*source_token_account_owner_key, true),
+        AccountMeta::new(*source_token_account_key, false),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates a `CreateWithSplits` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_with_splits(
+    vesting_program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    vesting_account_key: &Pubkey,
+    vesting_token_account_key: &Pubkey,
+    source_token_account_owner_key: &Pubkey,
+    source_token_account_key: &Pubkey,
+    destination_token_account_key: &Pubkey,
+    mint_address: &Pubkey,
+    time_basis: TimeBasis,
+    approver_key: Option<&Pubkey>,
+    pause_authority_key: Option<&Pubkey>,
+    split_destinations: Vec<DestinationSplit>,
+    schedules: Vec<Schedule>,
+    seeds: [u8; 32],
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::CreateWithSplits {
+        seeds,
+        mint_address: *mint_address,
+        destination_token_address: *destination_token_account_key,
+        time_basis,
+        approver: approver_key.copied(),
+        pause_authority: pause_authority_key.copied(),
+        split_destinations: split_destinations.clone(),
+        schedules,
+    }
+    .pack();
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new(*vesting_account_key, false),
+        AccountMeta::new(*vesting_token_account_key, false),
+        AccountMeta::new_readonly(*source_token_account_owner_key, true),
+        AccountMeta::new(*source_token_account_key, false),
+    ];
+    accounts.extend(
+        split_destinations
+            .iter()
+            .map(|split| AccountMeta::new_readonly(split.token_address, false)),
+    );
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates a `CreateNative` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_native(
+    vesting_program_id: &Pubkey,
+    system_program_id: &Pubkey,
+    vesting_account_key: &Pubkey,
+    source_key: &Pubkey,
+    destination_key: &Pubkey,
+    time_basis: TimeBasis,
+    approver_key: Option<&Pubkey>,
+    pause_authority_key: Option<&Pubkey>,
+    schedules: Vec<Schedule>,
+    seeds: [u8; 32],
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::CreateNative {
+        seeds,
+        destination_address: *destination_key,
+        time_basis,
+        approver: approver_key.copied(),
+        pause_authority: pause_authority_key.copied(),
+        schedules,
+    }
+    .pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(*system_program_id, false),
+        AccountMeta::new(*vesting_account_key, false),
+        AccountMeta::new(*source_key, true),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates a `Pause` instruction
+pub fn pause(
+    vesting_program_id: &Pubkey,
+    vesting_account_key: &Pubkey,
+    pause_authority_key: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::Pause.pack();
+    let accounts = vec![
+        AccountMeta::new(*vesting_account_key, false),
+        AccountMeta::new_readonly(*pause_authority_key, true),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates a `Resume` instruction
+pub fn resume(
+    vesting_program_id: &Pubkey,
+    vesting_account_key: &Pubkey,
+    pause_authority_key: &Pubkey,
+    shift_release_times: bool,
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::Resume {
+        shift_release_times,
+    }
+    .pack();
+    let accounts = vec![
+        AccountMeta::new(*vesting_account_key, false),
+        AccountMeta::new_readonly(*pause_authority_key, true),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates an `Unlock` instruction
+pub fn unlock(
+    vesting_program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    clock_sysvar_id: &Pubkey,
+    vesting_account_key: &Pubkey,
+    vesting_token_account_key: &Pubkey,
+    destination_token_account_key: &Pubkey,
+    seeds: [u8; 32],
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::Unlock { seeds }.pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(*clock_sysvar_id, false),
         AccountMeta::new(*vesting_account_key, false),
         AccountMeta::new(*vesting_token_account_key, false),
         AccountMeta::new(*destination_token_account_key, false),
@@ -319,6 +994,29 @@ pub fn unlock(
     })
 }
 
+// Creates an `Unlock` instruction for a native contract
+pub fn unlock_native(
+    vesting_program_id: &Pubkey,
+    system_program_id: &Pubkey,
+    clock_sysvar_id: &Pubkey,
+    vesting_account_key: &Pubkey,
+    destination_key: &Pubkey,
+    seeds: [u8; 32],
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::Unlock { seeds }.pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(*system_program_id, false),
+        AccountMeta::new_readonly(*clock_sysvar_id, false),
+        AccountMeta::new(*vesting_account_key, false),
+        AccountMeta::new(*destination_key, false),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
 pub fn change_destination(
     vesting_program_id: &Pubkey,
     vesting_account_key: &Pubkey,
@@ -341,10 +1039,379 @@ pub fn change_destination(
     })
 }
 
+// Creates a `ChangeSplitDestination` instruction
+pub fn change_split_destination(
+    vesting_program_id: &Pubkey,
+    vesting_account_key: &Pubkey,
+    current_split_token_account_owner: &Pubkey,
+    current_split_token_account: &Pubkey,
+    target_split_token_account: &Pubkey,
+    seeds: [u8; 32],
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::ChangeSplitDestination { seeds }.pack();
+    let accounts = vec![
+        AccountMeta::new(*vesting_account_key, false),
+        AccountMeta::new_readonly(*current_split_token_account, false),
+        AccountMeta::new_readonly(*current_split_token_account_owner, true),
+        AccountMeta::new_readonly(*target_split_token_account, false),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates an `UnlockWithAssociatedDestination` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn unlock_with_associated_destination(
+    vesting_program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    clock_sysvar_id: &Pubkey,
+    system_program_id: &Pubkey,
+    associated_token_program_id: &Pubkey,
+    vesting_account_key: &Pubkey,
+    vesting_token_account_key: &Pubkey,
+    destination_account_key: &Pubkey,
+    mint_address: &Pubkey,
+    payer_key: &Pubkey,
+    seeds: [u8; 32],
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::UnlockWithAssociatedDestination { seeds }.pack();
+    let destination_token_account_key =
+        get_associated_token_address(destination_account_key, mint_address);
+    let accounts = vec![
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(*clock_sysvar_id, false),
+        AccountMeta::new(*vesting_account_key, false),
+        AccountMeta::new(*vesting_token_account_key, false),
+        AccountMeta::new(destination_token_account_key, false),
+        AccountMeta::new_readonly(*destination_account_key, false),
+        AccountMeta::new_readonly(*mint_address, false),
+        AccountMeta::new(*payer_key, true),
+        AccountMeta::new_readonly(*system_program_id, false),
+        AccountMeta::new_readonly(*associated_token_program_id, false),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Appends the split destination spl-token accounts of a contract to an unlock instruction
+pub fn with_split_destinations(
+    mut unlock_instruction: Instruction,
+    split_destinations: &[DestinationSplit],
+) -> Instruction {
+    unlock_instruction.accounts.extend(
+        split_destinations
+            .iter()
+            .map(|split| AccountMeta::new(split.token_address, false)),
+    );
+    unlock_instruction
+}
+
+// Creates an `InitPool` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn init_pool(
+    vesting_program_id: &Pubkey,
+    system_program_id: &Pubkey,
+    rent_program_id: &Pubkey,
+    payer_key: &Pubkey,
+    pool_account_key: &Pubkey,
+    mint_address: &Pubkey,
+    pool_authority_key: &Pubkey,
+    time_basis: TimeBasis,
+    seeds: [u8; 32],
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::InitPool {
+        seeds,
+        mint_address: *mint_address,
+        pool_authority: *pool_authority_key,
+        time_basis,
+    }
+    .pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(*system_program_id, false),
+        AccountMeta::new_readonly(*rent_program_id, false),
+        AccountMeta::new(*payer_key, true),
+        AccountMeta::new(*pool_account_key, false),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates an `AddAllocation` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn add_allocation(
+    vesting_program_id: &Pubkey,
+    system_program_id: &Pubkey,
+    payer_key: &Pubkey,
+    pool_account_key: &Pubkey,
+    pool_authority_key: &Pubkey,
+    pool_token_account_key: &Pubkey,
+    destination_token_account_key: &Pubkey,
+    schedules: Vec<Schedule>,
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::AddAllocation { schedules }.pack();
+    let (allocation_account_key, _) = find_allocation_address(
+        vesting_program_id,
+        pool_account_key,
+        destination_token_account_key,
+    );
+    let accounts = vec![
+        AccountMeta::new_readonly(*system_program_id, false),
+        AccountMeta::new(*payer_key, true),
+        AccountMeta::new(*pool_account_key, false),
+        AccountMeta::new_readonly(*pool_authority_key, true),
+        AccountMeta::new_readonly(*pool_token_account_key, false),
+        AccountMeta::new(allocation_account_key, false),
+        AccountMeta::new_readonly(*destination_token_account_key, false),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates a `RemoveAllocation` instruction
+pub fn remove_allocation(
+    vesting_program_id: &Pubkey,
+    pool_account_key: &Pubkey,
+    pool_authority_key: &Pubkey,
+    destination_token_account_key: &Pubkey,
+    rent_receiver_key: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::RemoveAllocation.pack();
+    let (allocation_account_key, _) = find_allocation_address(
+        vesting_program_id,
+        pool_account_key,
+        destination_token_account_key,
+    );
+    let accounts = vec![
+        AccountMeta::new(*pool_account_key, false),
+        AccountMeta::new_readonly(*pool_authority_key, true),
+        AccountMeta::new(allocation_account_key, false),
+        AccountMeta::new(*rent_receiver_key, false),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates a `ClaimAllocation` instruction
+pub fn claim_allocation(
+    vesting_program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    clock_sysvar_id: &Pubkey,
+    pool_account_key: &Pubkey,
+    pool_token_account_key: &Pubkey,
+    destination_token_account_key: &Pubkey,
+    seeds: [u8; 32],
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::ClaimAllocation { seeds }.pack();
+    let (allocation_account_key, _) = find_allocation_address(
+        vesting_program_id,
+        pool_account_key,
+        destination_token_account_key,
+    );
+    let accounts = vec![
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(*clock_sysvar_id, false),
+        AccountMeta::new(*pool_account_key, false),
+        AccountMeta::new(*pool_token_account_key, false),
+        AccountMeta::new(allocation_account_key, false),
+        AccountMeta::new(*destination_token_account_key, false),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates an `InitDistributor` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn init_distributor(
+    vesting_program_id: &Pubkey,
+    system_program_id: &Pubkey,
+    rent_program_id: &Pubkey,
+    payer_key: &Pubkey,
+    distributor_account_key: &Pubkey,
+    mint_address: &Pubkey,
+    merkle_root: [u8; 32],
+    time_basis: TimeBasis,
+    start_time: u64,
+    cliff_time: u64,
+    end_time: u64,
+    total_amount: u64,
+    seeds: [u8; 32],
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::InitDistributor {
+        seeds,
+        mint_address: *mint_address,
+        merkle_root,
+        time_basis,
+        start_time,
+        cliff_time,
+        end_time,
+        total_amount,
+    }
+    .pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(*system_program_id, false),
+        AccountMeta::new_readonly(*rent_program_id, false),
+        AccountMeta::new(*payer_key, true),
+        AccountMeta::new(*distributor_account_key, false),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates a `ClaimDistribution` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn claim_distribution(
+    vesting_program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    system_program_id: &Pubkey,
+    clock_sysvar_id: &Pubkey,
+    distributor_account_key: &Pubkey,
+    distributor_token_account_key: &Pubkey,
+    claimant_key: &Pubkey,
+    destination_token_account_key: &Pubkey,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+    seeds: [u8; 32],
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::ClaimDistribution {
+        seeds,
+        amount,
+        proof,
+    }
+    .pack();
+    let (claim_account_key, _) =
+        find_distribution_claim_address(vesting_program_id, distributor_account_key, claimant_key);
+    let accounts = vec![
+        AccountMeta::new_readonly(*token_program_id, false),
+        AccountMeta::new_readonly(*system_program_id, false),
+        AccountMeta::new_readonly(*clock_sysvar_id, false),
+        AccountMeta::new(*distributor_account_key, false),
+        AccountMeta::new(*distributor_token_account_key, false),
+        AccountMeta::new(claim_account_key, false),
+        AccountMeta::new(*claimant_key, true),
+        AccountMeta::new(*destination_token_account_key, false),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
+// Creates a `GetClaimable` instruction
+pub fn get_claimable(
+    vesting_program_id: &Pubkey,
+    clock_sysvar_id: &Pubkey,
+    vesting_account_key: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = VestingInstruction::GetClaimable.pack();
+    let accounts = vec![
+        AccountMeta::new_readonly(*vesting_account_key, false),
+        AccountMeta::new_readonly(*clock_sysvar_id, false),
+    ];
+    Ok(Instruction {
+        program_id: *vesting_program_id,
+        accounts,
+        data,
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn test_legacy_instruction_unpacking() {
+        let seeds = [50u8; 32];
+        let mint_address = Pubkey::new_unique();
+        let destination_token_address = Pubkey::new_unique();
+
+        let mut init = vec![0];
+        init.extend_from_slice(&seeds);
+        init.extend_from_slice(&42u32.to_le_bytes());
+        assert_eq!(
+            VestingInstruction::unpack(&init).unwrap(),
+            VestingInstruction::Init {
+                seeds,
+                number_of_schedules: 42
+            }
+        );
+
+        let mut create = vec![1];
+        create.extend_from_slice(&seeds);
+        create.extend_from_slice(&mint_address.to_bytes());
+        create.extend_from_slice(&destination_token_address.to_bytes());
+        create.extend_from_slice(&250u64.to_le_bytes());
+        create.extend_from_slice(&42u64.to_le_bytes());
+        assert_eq!(
+            VestingInstruction::unpack(&create).unwrap(),
+            VestingInstruction::Create {
+                seeds,
+                mint_address,
+                destination_token_address,
+                schedules: vec![Schedule {
+                    release_time: 250,
+                    amount: 42
+                }],
+            }
+        );
+
+        let mut unlock = vec![2];
+        unlock.extend_from_slice(&seeds);
+        assert_eq!(
+            VestingInstruction::unpack(&unlock).unwrap(),
+            VestingInstruction::Unlock { seeds }
+        );
+
+        let mut change_destination = vec![3];
+        change_destination.extend_from_slice(&seeds);
+        assert_eq!(
+            VestingInstruction::unpack(&change_destination).unwrap(),
+            VestingInstruction::ChangeDestination { seeds }
+        );
+
+        // Short and trailing data is rejected instead of panicking or being ignored
+        let invalid_instruction = Err(VestingError::InvalidInstruction.into());
+        assert_eq!(VestingInstruction::unpack(&init[..20]), invalid_instruction);
+        assert_eq!(VestingInstruction::unpack(&create[..create.len() - 1]), invalid_instruction);
+        assert_eq!(VestingInstruction::unpack(&[unlock.clone(), vec![0]].concat()), invalid_instruction);
+        assert_eq!(VestingInstruction::unpack(&[5, 0]), invalid_instruction);
+        assert_eq!(VestingInstruction::unpack(&[]), invalid_instruction);
+    }
+
+    #[test]
+    fn test_versioned_instruction_unpacking() {
+        let unlock = VestingInstruction::Unlock { seeds: [50u8; 32] }.pack();
+        assert_eq!(unlock[..2], [VERSIONED_INSTRUCTION_TAG, INSTRUCTION_VERSION]);
+
+        let invalid_instruction = Err(VestingError::InvalidInstruction.into());
+        assert_eq!(VestingInstruction::unpack(&unlock[..unlock.len() - 1]), invalid_instruction);
+        assert_eq!(VestingInstruction::unpack(&[unlock.clone(), vec![0]].concat()), invalid_instruction);
+        let mut unknown_version = unlock;
+        unknown_version[1] += 1;
+        assert_eq!(VestingInstruction::unpack(&unknown_version), invalid_instruction);
+    }
+
     #[test]
     fn test_instruction_packing() {
         let mint_address = Pubkey::new_unique();
@@ -356,7 +1423,7 @@ mod test {
                 amount: 42,
                 release_time: 250,
             }],
-            mint_address: mint_address.clone(),
+            mint_address,
             destination_token_address,
         };
         let packed_create = original_create.pack();
@@ -383,5 +1450,197 @@ mod test {
             original_change,
             VestingInstruction::unpack(&original_change.pack()).unwrap()
         );
+
+        let original_unlock_with_associated_destination =
+            VestingInstruction::UnlockWithAssociatedDestination { seeds: [50u8; 32] };
+        assert_eq!(
+            original_unlock_with_associated_destination,
+            VestingInstruction::unpack(&original_unlock_with_associated_destination.pack())
+                .unwrap()
+        );
+
+        let original_get_claimable = VestingInstruction::GetClaimable;
+        assert_eq!(
+            original_get_claimable,
+            VestingInstruction::unpack(&original_get_claimable.pack()).unwrap()
+        );
+
+        let original_create_with_time_basis = VestingInstruction::CreateWithTimeBasis {
+            seeds: [50u8; 32],
+            mint_address,
+            destination_token_address,
+            time_basis: TimeBasis::Slot,
+            schedules: vec![Schedule {
+                amount: 42,
+                release_time: 250,
+            }],
+        };
+        let packed_create_with_time_basis = original_create_with_time_basis.pack();
+        // Variants are appended so that the encoding of the previous ones does not change
+        assert_eq!(packed_create_with_time_basis[2], 6);
+        assert_eq!(
+            original_create_with_time_basis,
+            VestingInstruction::unpack(&packed_create_with_time_basis).unwrap()
+        );
+
+        let original_create_milestones = VestingInstruction::CreateMilestones {
+            seeds: [50u8; 32],
+            mint_address,
+            destination_token_address,
+            time_basis: TimeBasis::UnixTimestamp,
+            approver: Pubkey::new_unique(),
+            schedules: vec![Schedule {
+                amount: 42,
+                release_time: u64::MAX,
+            }],
+        };
+        assert_eq!(
+            original_create_milestones,
+            VestingInstruction::unpack(&original_create_milestones.pack()).unwrap()
+        );
+
+        let original_release_milestone = VestingInstruction::ReleaseMilestone { schedule_index: 3 };
+        let packed_release_milestone = original_release_milestone.pack();
+        assert_eq!(packed_release_milestone[2..], [8, 3, 0, 0, 0]);
+        assert_eq!(
+            original_release_milestone,
+            VestingInstruction::unpack(&packed_release_milestone).unwrap()
+        );
+
+        let original_create_pausable = VestingInstruction::CreatePausable {
+            seeds: [50u8; 32],
+            mint_address,
+            destination_token_address,
+            time_basis: TimeBasis::Epoch,
+            approver: None,
+            pause_authority: Pubkey::new_unique(),
+            schedules: vec![Schedule {
+                amount: 42,
+                release_time: 250,
+            }],
+        };
+        assert_eq!(
+            original_create_pausable,
+            VestingInstruction::unpack(&original_create_pausable.pack()).unwrap()
+        );
+
+        assert_eq!(VestingInstruction::Pause.pack()[2..], [10]);
+        let original_resume = VestingInstruction::Resume {
+            shift_release_times: true,
+        };
+        let packed_resume = original_resume.pack();
+        assert_eq!(packed_resume[2..], [11, 1]);
+        assert_eq!(
+            original_resume,
+            VestingInstruction::unpack(&packed_resume).unwrap()
+        );
+
+        let original_create_with_splits = VestingInstruction::CreateWithSplits {
+            seeds: [50u8; 32],
+            mint_address,
+            destination_token_address,
+            time_basis: TimeBasis::UnixTimestamp,
+            approver: None,
+            pause_authority: Some(Pubkey::new_unique()),
+            split_destinations: vec![DestinationSplit {
+                token_address: Pubkey::new_unique(),
+                basis_points: 3000,
+            }],
+            schedules: vec![Schedule {
+                amount: 42,
+                release_time: 250,
+            }],
+        };
+        let packed_create_with_splits = original_create_with_splits.pack();
+        assert_eq!(packed_create_with_splits[2], 12);
+        assert_eq!(
+            original_create_with_splits,
+            VestingInstruction::unpack(&packed_create_with_splits).unwrap()
+        );
+
+        let original_create_native = VestingInstruction::CreateNative {
+            seeds: [50u8; 32],
+            destination_address: Pubkey::new_unique(),
+            time_basis: TimeBasis::UnixTimestamp,
+            approver: None,
+            pause_authority: None,
+            schedules: vec![Schedule {
+                amount: 42,
+                release_time: 250,
+            }],
+        };
+        let packed_create_native = original_create_native.pack();
+        assert_eq!(packed_create_native[2], 13);
+        assert_eq!(
+            original_create_native,
+            VestingInstruction::unpack(&packed_create_native).unwrap()
+        );
+
+        let original_init_pool = VestingInstruction::InitPool {
+            seeds: [50u8; 32],
+            mint_address,
+            pool_authority: Pubkey::new_unique(),
+            time_basis: TimeBasis::UnixTimestamp,
+        };
+        let packed_init_pool = original_init_pool.pack();
+        assert_eq!(packed_init_pool[2], 14);
+        assert_eq!(
+            original_init_pool,
+            VestingInstruction::unpack(&packed_init_pool).unwrap()
+        );
+
+        let original_add_allocation = VestingInstruction::AddAllocation {
+            schedules: vec![Schedule {
+                amount: 42,
+                release_time: 250,
+            }],
+        };
+        assert_eq!(
+            original_add_allocation,
+            VestingInstruction::unpack(&original_add_allocation.pack()).unwrap()
+        );
+        assert_eq!(VestingInstruction::RemoveAllocation.pack()[2..], [16]);
+        let original_claim_allocation = VestingInstruction::ClaimAllocation { seeds: [50u8; 32] };
+        assert_eq!(
+            original_claim_allocation,
+            VestingInstruction::unpack(&original_claim_allocation.pack()).unwrap()
+        );
+
+        let original_init_distributor = VestingInstruction::InitDistributor {
+            seeds: [50u8; 32],
+            mint_address,
+            merkle_root: [7u8; 32],
+            time_basis: TimeBasis::Slot,
+            start_time: 100,
+            cliff_time: 150,
+            end_time: 300,
+            total_amount: 1_000,
+        };
+        let packed_init_distributor = original_init_distributor.pack();
+        assert_eq!(packed_init_distributor[2], 18);
+        assert_eq!(
+            original_init_distributor,
+            VestingInstruction::unpack(&packed_init_distributor).unwrap()
+        );
+
+        let original_claim_distribution = VestingInstruction::ClaimDistribution {
+            seeds: [50u8; 32],
+            amount: 400,
+            proof: vec![[1u8; 32], [2u8; 32]],
+        };
+        let packed_claim_distribution = original_claim_distribution.pack();
+        assert_eq!(packed_claim_distribution[2], 19);
+        assert_eq!(
+            original_claim_distribution,
+            VestingInstruction::unpack(&packed_claim_distribution).unwrap()
+        );
+
+        let original_change_split = VestingInstruction::ChangeSplitDestination { seeds: [50u8; 32] };
+        let packed_change_split = original_change_split.pack();
+        assert_eq!(packed_change_split[2], 20);
+        assert_eq!(
+            original_change_split,
+            VestingInstruction::unpack(&packed_change_split).unwrap()
+        );
     }
 }
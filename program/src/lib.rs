@@ -1,8 +1,13 @@
+// The SBF toolchain building the program predates `is_multiple_of`, keep the remainder checks
+#![allow(clippy::manual_is_multiple_of)]
+
 #[cfg(not(feature = "no-entrypoint"))]
 pub mod entrypoint;
 
 pub mod error;
+pub mod events;
 pub mod instruction;
+pub mod merkle;
 pub mod state;
 
 pub mod processor;
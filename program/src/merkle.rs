@@ -0,0 +1,112 @@
+//! Merkle trees of the distributors. Every leaf commits to a claimant and the amount allocated to
+//! it, and every node hashes its two children in sorted order so that proofs need no positions.
+use solana_program::{keccak::hashv, pubkey::Pubkey};
+
+// Keep a leaf from being passed off as a node, and the other way around
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf of the allocation of `amount` tokens to a claimant
+pub fn leaf_hash(claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, claimant.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Whether the proof, the siblings of the nodes from the leaf up, leads from the leaf to the root
+pub fn verify_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| node_hash(&node, sibling))
+        == *root
+}
+
+/// Builds the levels of a tree from its leaves up to its root. A node without a sibling moves up
+/// to the next level unchanged.
+pub fn tree_levels(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves];
+    while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+        let next_level = level
+            .chunks(2)
+            .map(|nodes| match nodes {
+                [left, right] => node_hash(left, right),
+                _ => nodes[0],
+            })
+            .collect();
+        levels.push(next_level);
+    }
+    levels
+}
+
+/// Root of a tree built by `tree_levels`, if it has any leaf
+pub fn tree_root(levels: &[Vec<[u8; 32]>]) -> Option<[u8; 32]> {
+    levels.last()?.first().copied()
+}
+
+/// Proof of the leaf at the given index of a tree built by `tree_levels`
+pub fn tree_proof(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = vec![];
+    for level in levels.iter().take(levels.len().saturating_sub(1)) {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        index /= 2;
+    }
+    proof
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{leaf_hash, tree_levels, tree_proof, tree_root, verify_proof};
+    use solana_program::pubkey::Pubkey;
+
+    #[test]
+    fn test_merkle_proofs() {
+        let claims: Vec<(Pubkey, u64)> = (1..=5).map(|i| (Pubkey::new_unique(), i * 100)).collect();
+        let levels = tree_levels(
+            claims
+                .iter()
+                .map(|(claimant, amount)| leaf_hash(claimant, *amount))
+                .collect(),
+        );
+        // 5 leaves, then 3, 2 and 1 nodes
+        assert_eq!(
+            levels.iter().map(Vec::len).collect::<Vec<_>>(),
+            [5, 3, 2, 1]
+        );
+        let root = tree_root(&levels).unwrap();
+
+        for (index, (claimant, amount)) in claims.iter().enumerate() {
+            let proof = tree_proof(&levels, index);
+            assert!(verify_proof(&proof, &root, leaf_hash(claimant, *amount)));
+            assert!(!verify_proof(
+                &proof,
+                &root,
+                leaf_hash(claimant, amount + 1)
+            ));
+            assert!(!verify_proof(
+                &proof,
+                &root,
+                leaf_hash(&Pubkey::new_unique(), *amount)
+            ));
+        }
+        // The last leaf has no sibling on the first two levels
+        assert_eq!(tree_proof(&levels, 4).len(), 1);
+
+        // An inner node is not a leaf
+        assert!(!verify_proof(&[levels[1][1]], &root, levels[1][0]));
+
+        let single_leaf = leaf_hash(&claims[0].0, claims[0].1);
+        let levels = tree_levels(vec![single_leaf]);
+        assert_eq!(tree_root(&levels), Some(single_leaf));
+        assert!(verify_proof(
+            &tree_proof(&levels, 0),
+            &single_leaf,
+            single_leaf
+        ));
+        assert_eq!(tree_root(&tree_levels(vec![])), None);
+    }
+}
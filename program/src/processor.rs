@@ -3,25 +3,322 @@ use solana_program::{
     decode_error::DecodeError,
     entrypoint::ProgramResult,
     msg,
-    program::{invoke, invoke_signed},
+    program::{invoke, invoke_signed, set_return_data},
     program_error::PrintProgramError,
     program_error::ProgramError,
     program_pack::Pack,
     pubkey::Pubkey,
     rent::Rent,
-    system_instruction::create_account,
-    sysvar::{clock::Clock, Sysvar},
+    system_instruction::{self, create_account},
+    system_program,
+    sysvar::{self, clock::Clock, Sysvar},
 };
 
 use num_traits::FromPrimitive;
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
 use spl_token::{instruction::transfer, state::Account};
 
 use crate::{
     error::VestingError,
+    events::VestingEvent,
     instruction::{Schedule, VestingInstruction, SCHEDULE_SIZE},
-    state::{pack_schedules_into_slice, unpack_schedules, VestingSchedule, VestingScheduleHeader},
+    merkle::{leaf_hash, verify_proof},
+    state::{
+        are_valid_splits, claimable_amounts, find_allocation_address,
+        find_distribution_claim_address, pack_schedules_into_slice, unpack_schedules, Allocation,
+        AllocationHeader, ClaimableAmounts, DestinationSplit, DistributionClaim, DistributorHeader,
+        PoolHeader, TimeBasis, VestingSchedule, VestingScheduleHeader,
+    },
 };
 
+// Properties expected from an account of an instruction, checked by `validate_accounts`
+#[derive(Default)]
+struct AccountCheck<'a> {
+    key: Option<&'a Pubkey>,
+    owner: Option<&'a Pubkey>,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+impl<'a> AccountCheck<'a> {
+    // Any account, e.g. a wallet only used for its address
+    fn any() -> Self {
+        Self::default()
+    }
+
+    // A program or sysvar account
+    fn key(key: &'a Pubkey) -> Self {
+        Self {
+            key: Some(key),
+            ..Self::default()
+        }
+    }
+
+    fn owned_by(owner: &'a Pubkey) -> Self {
+        Self {
+            owner: Some(owner),
+            ..Self::default()
+        }
+    }
+
+    fn signer(self) -> Self {
+        Self {
+            is_signer: true,
+            ..self
+        }
+    }
+
+    fn writable(self) -> Self {
+        Self {
+            is_writable: true,
+            ..self
+        }
+    }
+}
+
+// Checks the accounts of an instruction against the expected ones, in order
+fn validate_accounts(accounts: &[AccountInfo], checks: &[AccountCheck]) -> ProgramResult {
+    if accounts.len() < checks.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    for (i, (account, check)) in accounts.iter().zip(checks.iter()).enumerate() {
+        if let Some(key) = check.key {
+            if account.key != key {
+                msg!("Account {} should be {}", i, key);
+                return Err(VestingError::IncorrectAccountKey.into());
+            }
+        }
+        if let Some(owner) = check.owner {
+            if account.owner != owner {
+                msg!("Account {} should be owned by {}", i, owner);
+                return Err(VestingError::IncorrectAccountOwner.into());
+            }
+        }
+        if check.is_signer && !account.is_signer {
+            msg!("Account {} should be a signer", i);
+            return Err(VestingError::MissingRequiredSigner.into());
+        }
+        if check.is_writable && !account.is_writable {
+            msg!("Account {} should be writable", i);
+            return Err(VestingError::AccountNotWritable.into());
+        }
+    }
+    Ok(())
+}
+
+// Derives a vesting, pool or distributor account from the seeds of an instruction, whose last
+// byte has to be the canonical bump seed stored in the account when it was initialized
+fn seeded_account_address(
+    program_id: &Pubkey,
+    seeds: &[u8; 32],
+    bump_seed: u8,
+) -> Result<Pubkey, ProgramError> {
+    if seeds[31] != bump_seed {
+        msg!("The seeds should end with the bump seed of the account");
+        return Err(VestingError::NonCanonicalBumpSeed.into());
+    }
+    Pubkey::create_program_address(&[&seeds[..31], &[bump_seed]], program_id)
+        .map_err(|_| ProgramError::InvalidSeeds)
+}
+
+// Derives the vesting account of a contract from the seeds of an instruction. Legacy contracts do
+// not store their bump seed and were created with any bump seed the runtime accepted, their seeds
+// are used as they are.
+fn vesting_account_address(
+    program_id: &Pubkey,
+    seeds: &[u8; 32],
+    header: &VestingScheduleHeader,
+) -> Result<Pubkey, ProgramError> {
+    if header.is_legacy {
+        return Pubkey::create_program_address(&[seeds], program_id)
+            .map_err(|_| ProgramError::InvalidSeeds);
+    }
+    seeded_account_address(program_id, seeds, header.bump_seed)
+}
+
+// Reads the header of an initialized vesting contract
+fn unpack_initialized_header(
+    vesting_account: &AccountInfo,
+) -> Result<VestingScheduleHeader, ProgramError> {
+    let data = vesting_account.data.borrow();
+    let header = VestingScheduleHeader::unpack_from_slice(&data)?;
+    if (data.len() - header.packed_len()) % VestingSchedule::LEN != 0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if !header.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(header)
+}
+
+// Reads the header of an initialized pool account
+fn unpack_initialized_pool(pool_account: &AccountInfo) -> Result<PoolHeader, ProgramError> {
+    let pool = PoolHeader::unpack_from_slice(&pool_account.data.borrow())?;
+    if !pool.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(pool)
+}
+
+// Reads an allocation of the given pool, checking that the allocation account is the one derived
+// from the pool account and the destination of the allocation
+fn unpack_pool_allocation(
+    program_id: &Pubkey,
+    allocation_account: &AccountInfo,
+    pool_account_key: &Pubkey,
+) -> Result<Allocation, ProgramError> {
+    let allocation = Allocation::unpack(&allocation_account.data.borrow())?;
+    if !allocation.header.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    if allocation.header.pool_address != *pool_account_key {
+        msg!("The allocation does not belong to the pool");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let allocation_account_key = Pubkey::create_program_address(
+        &[
+            pool_account_key.as_ref(),
+            allocation.header.destination_address.as_ref(),
+            &[allocation.header.bump_seed],
+        ],
+        program_id,
+    )
+    .map_err(|_| ProgramError::InvalidSeeds)?;
+    if allocation_account_key != *allocation_account.key {
+        msg!("Invalid allocation account key");
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(allocation)
+}
+
+// Creates the program account derived from the seeds of an `Init`, `InitPool` or
+// `InitDistributor` instruction, returning its bump seed. Only the canonical bump seed is
+// accepted so that a seed matches a single account.
+fn create_seeded_account<'a>(
+    program_id: &Pubkey,
+    seeds: &[u8; 32],
+    state_size: usize,
+    system_program_account: &AccountInfo<'a>,
+    rent_sysvar_account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+) -> Result<u8, ProgramError> {
+    let rent = Rent::from_account_info(rent_sysvar_account)?;
+
+    // Find the non reversible public key for the account via the seed
+    let (account_key, bump_seed) = Pubkey::find_program_address(&[&seeds[..31]], program_id);
+    if seeds[31] != bump_seed {
+        msg!("The seeds should end with the canonical bump seed {}", bump_seed);
+        return Err(VestingError::NonCanonicalBumpSeed.into());
+    }
+    if account_key != *new_account.key {
+        msg!("Provided account is not derived from the seeds");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let init_account = create_account(
+        payer.key,
+        &account_key,
+        rent.minimum_balance(state_size),
+        state_size as u64,
+        program_id,
+    );
+
+    invoke_signed(
+        &init_account,
+        &[
+            system_program_account.clone(),
+            payer.clone(),
+            new_account.clone(),
+        ],
+        &[&[seeds]],
+    )?;
+    Ok(bump_seed)
+}
+
+// Reads the header written by `Init` in the vesting account derived from the seeds, which must
+// not hold a contract yet. The header is a legacy one if the account was initialized by an
+// earlier version of the program.
+fn unpack_uninitialized_header(
+    program_id: &Pubkey,
+    vesting_account: &AccountInfo,
+    seeds: &[u8; 32],
+) -> Result<VestingScheduleHeader, ProgramError> {
+    let header = VestingScheduleHeader::unpack_from_slice(&vesting_account.try_borrow_data()?)?;
+    let vesting_account_key = vesting_account_address(program_id, seeds, &header)?;
+    if vesting_account_key != *vesting_account.key {
+        msg!("Provided vesting account is invalid");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Verifying that no SVC was already created with this seed
+    if header.is_initialized {
+        msg!("Cannot overwrite an existing vesting contract.");
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(header)
+}
+
+// Writes the header and the schedules of a new contract, returning the total amount they vest
+fn pack_contract_into_slice(
+    state_header: &VestingScheduleHeader,
+    schedules: &[Schedule],
+    data: &mut [u8],
+) -> Result<u64, ProgramError> {
+    // Accounts initialized by earlier versions of the program only have room for a legacy header
+    if state_header.is_legacy && !state_header.fits_legacy_layout() {
+        msg!("Accounts initialized by earlier program versions hold contracts without options");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let header_len = state_header.packed_len();
+    if data.len() != header_len + schedules.len() * VestingSchedule::LEN {
+        return Err(ProgramError::InvalidAccountData)
+    }
+    state_header.pack_into_slice(data);
+
+    let mut offset = header_len;
+    let mut total_amount: u64 = 0;
+
+    for s in schedules.iter() {
+        let state_schedule = VestingSchedule {
+            release_time: s.release_time,
+            amount: s.amount,
+        };
+        state_schedule.pack_into_slice(&mut data[offset..]);
+        let delta = total_amount.checked_add(s.amount);
+        match delta {
+            Some(n) => total_amount = n,
+            None => return Err(ProgramError::InvalidInstructionData), // Total amount overflows u64
+        }
+        offset += SCHEDULE_SIZE;
+    }
+    Ok(total_amount)
+}
+
+// Zeroes the amounts of the schedules that have reached maturity, returning the schedules and the
+// amount they release
+fn release_matured_schedules(
+    packed_schedules: &[u8],
+    current_time: u64,
+) -> Result<(Vec<VestingSchedule>, u64), ProgramError> {
+    let mut total_amount_to_transfer = 0;
+    let mut schedules = unpack_schedules(packed_schedules)?;
+
+    for s in schedules.iter_mut() {
+        if current_time >= s.release_time {
+            total_amount_to_transfer += s.amount;
+            s.amount = 0;
+        }
+    }
+    if total_amount_to_transfer == 0 {
+        msg!("Vesting contract has not yet reached release time");
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok((schedules, total_amount_to_transfer))
+}
+
 pub struct Processor {}
 
 impl Processor {
@@ -31,6 +328,16 @@ impl Processor {
         seeds: [u8; 32],
         schedules: u32
     ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::key(&system_program::id()),
+                AccountCheck::key(&sysvar::rent::id()),
+                AccountCheck::any().signer().writable(),
+                // The vesting account does not exist yet
+                AccountCheck::owned_by(&system_program::id()).writable(),
+            ],
+        )?;
         let accounts_iter = &mut accounts.iter();
 
         let system_program_account = next_account_info(accounts_iter)?;
@@ -38,45 +345,58 @@ impl Processor {
         let payer = next_account_info(accounts_iter)?;
         let vesting_account = next_account_info(accounts_iter)?;
 
-        let rent = Rent::from_account_info(rent_sysvar_account)?;
-
-        // Find the non reversible public key for the vesting contract via the seed
-        let vesting_account_key = Pubkey::create_program_address(&[&seeds], &program_id).unwrap();
-        if vesting_account_key != *vesting_account.key {
-            msg!("Provided vesting account is invalid");
-            return Err(ProgramError::InvalidArgument);
-        }
-
         let state_size = (schedules as usize) * VestingSchedule::LEN + VestingScheduleHeader::LEN;
-
-        let init_vesting_account = create_account(
-            &payer.key,
-            &vesting_account_key,
-            rent.minimum_balance(state_size),
-            state_size as u64,
-            &program_id,
-        );
-
-        invoke_signed(
-            &init_vesting_account,
-            &[
-                system_program_account.clone(),
-                payer.clone(),
-                vesting_account.clone(),
-            ],
-            &[&[&seeds]],
+        let bump_seed = create_seeded_account(
+            program_id,
+            &seeds,
+            state_size,
+            system_program_account,
+            rent_sysvar_account,
+            payer,
+            vesting_account,
         )?;
+
+        // The bump seed is kept until the contract is created
+        let state_header = VestingScheduleHeader {
+            destination_address: Pubkey::default(),
+            mint_address: Pubkey::default(),
+            is_initialized: false,
+            bump_seed,
+            time_basis: TimeBasis::default(),
+            approver: None,
+            pause_authority: None,
+            paused_at: None,
+            split_destinations: vec![],
+            is_native: false,
+            is_legacy: false,
+        };
+        state_header.pack_into_slice(&mut vesting_account.data.borrow_mut());
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn process_create(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
         seeds: [u8; 32],
         mint_address: &Pubkey,
         destination_token_address: &Pubkey,
+        time_basis: TimeBasis,
+        approver: Option<Pubkey>,
+        pause_authority: Option<Pubkey>,
+        split_destinations: Vec<DestinationSplit>,
         schedules: Vec<Schedule>,
     ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::key(&spl_token::id()),
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::owned_by(&spl_token::id()).writable(),
+                AccountCheck::any().signer(),
+                AccountCheck::owned_by(&spl_token::id()).writable(),
+            ],
+        )?;
         let accounts_iter = &mut accounts.iter();
 
         let spl_token_account = next_account_info(accounts_iter)?;
@@ -85,30 +405,8 @@ impl Processor {
         let source_token_account_owner = next_account_info(accounts_iter)?;
         let source_token_account = next_account_info(accounts_iter)?;
 
-        let vesting_account_key = Pubkey::create_program_address(&[&seeds], program_id)?;
-        if vesting_account_key != *vesting_account.key {
-            msg!("Provided vesting account is invalid");
-            return Err(ProgramError::InvalidArgument);
-        }
-
-        if !source_token_account_owner.is_signer {
-            msg!("Source token account owner should be a signer.");
-            return Err(ProgramError::InvalidArgument);
-        }
-
-        if *vesting_account.owner != *program_id {
-            msg!("Program should own vesting account");
-            return Err(ProgramError::InvalidArgument);
-        }
-
-        // Verifying that no SVC was already created with this seed
-        let is_initialized =
-            vesting_account.try_borrow_data()?[VestingScheduleHeader::LEN - 1] == 1;
-
-        if is_initialized {
-            msg!("Cannot overwrite an existing vesting contract.");
-            return Err(ProgramError::InvalidArgument);
-        }
+        let initial_header = unpack_uninitialized_header(program_id, vesting_account, &seeds)?;
+        let vesting_account_key = *vesting_account.key;
 
         let vesting_token_account_data = Account::unpack(&vesting_token_account.data.borrow())?;
 
@@ -127,35 +425,51 @@ impl Processor {
             return Err(ProgramError::InvalidAccountData);
         }
 
+        if !are_valid_splits(&split_destinations) {
+            msg!("The split destinations are invalid");
+            return Err(VestingError::InvalidDestinationSplits.into());
+        }
+
+        let split_destination_accounts = accounts_iter.as_slice();
+        let spl_token_id = spl_token::id();
+        let split_checks: Vec<AccountCheck> = split_destinations
+            .iter()
+            .map(|_| AccountCheck::owned_by(&spl_token_id))
+            .collect();
+        validate_accounts(split_destination_accounts, &split_checks)?;
+        for (split, split_account) in split_destinations
+            .iter()
+            .zip(split_destination_accounts.iter())
+        {
+            if split.token_address != *split_account.key {
+                msg!("Contract split destination accounts do not match provided accounts");
+                return Err(ProgramError::InvalidArgument);
+            }
+            if Account::unpack(&split_account.data.borrow())?.mint != *mint_address {
+                msg!("The split destination token accounts should be of the mint of the contract");
+                return Err(ProgramError::InvalidArgument);
+            }
+        }
+
         let state_header = VestingScheduleHeader {
             destination_address: *destination_token_address,
             mint_address: *mint_address,
             is_initialized: true,
+            bump_seed: initial_header.bump_seed,
+            time_basis,
+            approver,
+            pause_authority,
+            paused_at: None,
+            split_destinations,
+            is_native: false,
+            is_legacy: initial_header.is_legacy,
         };
+        let total_amount = pack_contract_into_slice(
+            &state_header,
+            &schedules,
+            &mut vesting_account.data.borrow_mut(),
+        )?;
 
-        let mut data = vesting_account.data.borrow_mut();
-        if data.len() != VestingScheduleHeader::LEN + schedules.len() * VestingSchedule::LEN {
-            return Err(ProgramError::InvalidAccountData)
-        }
-        state_header.pack_into_slice(&mut data);
-
-        let mut offset = VestingScheduleHeader::LEN;
-        let mut total_amount: u64 = 0;
-
-        for s in schedules.iter() {
-            let state_schedule = VestingSchedule {
-                release_time: s.release_time,
-                amount: s.amount,
-            };
-            state_schedule.pack_into_slice(&mut data[offset..]);
-            let delta = total_amount.checked_add(s.amount);
-            match delta {
-                Some(n) => total_amount = n,
-                None => return Err(ProgramError::InvalidInstructionData), // Total amount overflows u64
-            }
-            offset += SCHEDULE_SIZE;
-        }
-        
         if Account::unpack(&source_token_account.data.borrow())?.amount < total_amount {
             msg!("The source token account has insufficient funds.");
             return Err(ProgramError::InsufficientFunds)
@@ -179,6 +493,92 @@ impl Processor {
                 source_token_account_owner.clone(),
             ],
         )?;
+
+        VestingEvent::Created {
+            vesting_account: vesting_account_key,
+            mint_address: *mint_address,
+            destination_token_address: *destination_token_address,
+            total_amount,
+            schedules,
+            unix_timestamp: Clock::get()?.unix_timestamp,
+        }
+        .emit();
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_create_native(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        seeds: [u8; 32],
+        destination_address: &Pubkey,
+        time_basis: TimeBasis,
+        approver: Option<Pubkey>,
+        pause_authority: Option<Pubkey>,
+        schedules: Vec<Schedule>,
+    ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::key(&system_program::id()),
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::any().signer().writable(),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let system_program_account = next_account_info(accounts_iter)?;
+        let vesting_account = next_account_info(accounts_iter)?;
+        let source_account = next_account_info(accounts_iter)?;
+
+        let initial_header = unpack_uninitialized_header(program_id, vesting_account, &seeds)?;
+
+        let state_header = VestingScheduleHeader {
+            destination_address: *destination_address,
+            mint_address: Pubkey::default(),
+            is_initialized: true,
+            bump_seed: initial_header.bump_seed,
+            time_basis,
+            approver,
+            pause_authority,
+            paused_at: None,
+            split_destinations: vec![],
+            is_native: true,
+            is_legacy: initial_header.is_legacy,
+        };
+        let total_amount = pack_contract_into_slice(
+            &state_header,
+            &schedules,
+            &mut vesting_account.data.borrow_mut(),
+        )?;
+
+        if source_account.lamports() < total_amount {
+            msg!("The source wallet has insufficient funds.");
+            return Err(ProgramError::InsufficientFunds)
+        };
+
+        // The vesting account keeps its rent exempt balance on top of the vested lamports
+        let transfer_lamports_to_vesting_account =
+            system_instruction::transfer(source_account.key, vesting_account.key, total_amount);
+
+        invoke(
+            &transfer_lamports_to_vesting_account,
+            &[
+                source_account.clone(),
+                vesting_account.clone(),
+                system_program_account.clone(),
+            ],
+        )?;
+
+        VestingEvent::Created {
+            vesting_account: *vesting_account.key,
+            mint_address: Pubkey::default(),
+            destination_token_address: *destination_address,
+            total_amount,
+            schedules,
+            unix_timestamp: Clock::get()?.unix_timestamp,
+        }
+        .emit();
         Ok(())
     }
 
@@ -187,6 +587,20 @@ impl Processor {
         _accounts: &[AccountInfo],
         seeds: [u8; 32],
     ) -> ProgramResult {
+        // Native contracts are unlocked with the system program instead of the spl-token program
+        if _accounts.first().map(|account| account.key) == Some(&system_program::id()) {
+            return Self::process_unlock_native(program_id, _accounts, seeds);
+        }
+        validate_accounts(
+            _accounts,
+            &[
+                AccountCheck::key(&spl_token::id()),
+                AccountCheck::key(&sysvar::clock::id()),
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::owned_by(&spl_token::id()).writable(),
+                AccountCheck::owned_by(&spl_token::id()).writable(),
+            ],
+        )?;
         let accounts_iter = &mut _accounts.iter();
 
         let spl_token_account = next_account_info(accounts_iter)?;
@@ -194,27 +608,50 @@ impl Processor {
         let vesting_account = next_account_info(accounts_iter)?;
         let vesting_token_account = next_account_info(accounts_iter)?;
         let destination_token_account = next_account_info(accounts_iter)?;
+        let split_destination_accounts = accounts_iter.as_slice();
 
-        let vesting_account_key = Pubkey::create_program_address(&[&seeds], program_id)?;
+        let packed_state = &vesting_account.data;
+        let header_state = unpack_initialized_header(vesting_account)?;
+
+        let vesting_account_key = vesting_account_address(program_id, &seeds, &header_state)?;
         if vesting_account_key != *vesting_account.key {
             msg!("Invalid vesting account key");
             return Err(ProgramError::InvalidArgument);
         }
 
-        if spl_token_account.key != &spl_token::id() {
-            msg!("The provided spl token program account is invalid");
-            return Err(ProgramError::InvalidArgument)
+        if header_state.is_native {
+            msg!("The vesting contract holds native SOL");
+            return Err(ProgramError::InvalidArgument);
         }
 
-        let packed_state = &vesting_account.data;
-        let header_state =
-            VestingScheduleHeader::unpack(&packed_state.borrow()[..VestingScheduleHeader::LEN])?;
-
         if header_state.destination_address != *destination_token_account.key {
             msg!("Contract destination account does not matched provided account");
             return Err(ProgramError::InvalidArgument);
         }
 
+        let spl_token_id = spl_token::id();
+        let split_checks: Vec<AccountCheck> = header_state
+            .split_destinations
+            .iter()
+            .map(|_| AccountCheck::owned_by(&spl_token_id).writable())
+            .collect();
+        validate_accounts(split_destination_accounts, &split_checks)?;
+        for (split, split_account) in header_state
+            .split_destinations
+            .iter()
+            .zip(split_destination_accounts.iter())
+        {
+            if split.token_address != *split_account.key {
+                msg!("Contract split destination accounts do not match provided accounts");
+                return Err(ProgramError::InvalidArgument);
+            }
+        }
+
+        if header_state.paused_at.is_some() {
+            msg!("The vesting contract is paused");
+            return Err(VestingError::ContractPaused.into());
+        }
+
         let vesting_token_account_data = Account::unpack(&vesting_token_account.data.borrow())?;
 
         if vesting_token_account_data.owner != vesting_account_key {
@@ -224,129 +661,1158 @@ impl Processor {
 
         // Unlock the schedules that have reached maturity
         let clock = Clock::from_account_info(&clock_sysvar_account)?;
-        let mut total_amount_to_transfer = 0;
-        let mut schedules = unpack_schedules(&packed_state.borrow()[VestingScheduleHeader::LEN..])?;
+        let current_time = header_state.time_basis.current_time(&clock);
+        let (schedules, total_amount_to_transfer) = release_matured_schedules(
+            &packed_state.borrow()[header_state.packed_len()..],
+            current_time,
+        )?;
 
-        for s in schedules.iter_mut() {
-            if clock.unix_timestamp as u64 >= s.release_time {
-                total_amount_to_transfer += s.amount;
-                s.amount = 0;
-            }
-        }
-        if total_amount_to_transfer == 0 {
-            msg!("Vesting contract has not yet reached release time");
-            return Err(ProgramError::InvalidArgument);
+        // The split destinations receive their share rounded down and the destination the rest
+        let mut transfers = Vec::with_capacity(header_state.split_destinations.len() + 1);
+        let mut destination_amount = total_amount_to_transfer;
+        for (split, split_account) in header_state
+            .split_destinations
+            .iter()
+            .zip(split_destination_accounts.iter())
+        {
+            let share = split.share_of(total_amount_to_transfer);
+            destination_amount -= share;
+            transfers.push((split_account, share));
         }
+        transfers.insert(0, (destination_token_account, destination_amount));
 
-        let transfer_tokens_from_vesting_account = transfer(
-            &spl_token_account.key,
-            &vesting_token_account.key,
-            destination_token_account.key,
-            &vesting_account_key,
-            &[],
-            total_amount_to_transfer,
-        )?;
+        for (transfer_destination_account, amount) in transfers.iter() {
+            if *amount == 0 {
+                continue;
+            }
+            let transfer_tokens_from_vesting_account = transfer(
+                spl_token_account.key,
+                vesting_token_account.key,
+                transfer_destination_account.key,
+                &vesting_account_key,
+                &[],
+                *amount,
+            )?;
 
-        invoke_signed(
-            &transfer_tokens_from_vesting_account,
-            &[
-                spl_token_account.clone(),
-                vesting_token_account.clone(),
-                destination_token_account.clone(),
-                vesting_account.clone(),
-            ],
-            &[&[&seeds]],
-        )?;
+            invoke_signed(
+                &transfer_tokens_from_vesting_account,
+                &[
+                    spl_token_account.clone(),
+                    vesting_token_account.clone(),
+                    (*transfer_destination_account).clone(),
+                    vesting_account.clone(),
+                ],
+                &[&[&seeds]],
+            )?;
+        }
 
         // Reset released amounts to 0. This makes the simple unlock safe with complex scheduling contracts
         pack_schedules_into_slice(
             schedules,
-            &mut packed_state.borrow_mut()[VestingScheduleHeader::LEN..],
+            &mut packed_state.borrow_mut()[header_state.packed_len()..],
         );
 
+        for (transfer_destination_account, amount) in transfers.iter() {
+            if *amount == 0 {
+                continue;
+            }
+            VestingEvent::Unlocked {
+                vesting_account: vesting_account_key,
+                destination_token_address: *transfer_destination_account.key,
+                amount: *amount,
+                unix_timestamp: clock.unix_timestamp,
+            }
+            .emit();
+        }
         Ok(())
     }
 
-    pub fn process_change_destination(
+    fn process_unlock_native(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
         seeds: [u8; 32],
     ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::key(&system_program::id()),
+                AccountCheck::key(&sysvar::clock::id()),
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::any().writable(),
+            ],
+        )?;
         let accounts_iter = &mut accounts.iter();
 
+        let _system_program_account = next_account_info(accounts_iter)?;
+        let clock_sysvar_account = next_account_info(accounts_iter)?;
         let vesting_account = next_account_info(accounts_iter)?;
-        let destination_token_account = next_account_info(accounts_iter)?;
-        let destination_token_account_owner = next_account_info(accounts_iter)?;
-        let new_destination_token_account = next_account_info(accounts_iter)?;
+        let destination_account = next_account_info(accounts_iter)?;
 
-        if vesting_account.data.borrow().len() < VestingScheduleHeader::LEN {
-            return Err(ProgramError::InvalidAccountData)
-        }
-        let vesting_account_key = Pubkey::create_program_address(&[&seeds], program_id)?;
-        let state = VestingScheduleHeader::unpack(
-            &vesting_account.data.borrow()[..VestingScheduleHeader::LEN],
-        )?;
+        let header_state = unpack_initialized_header(vesting_account)?;
 
+        let vesting_account_key = vesting_account_address(program_id, &seeds, &header_state)?;
         if vesting_account_key != *vesting_account.key {
             msg!("Invalid vesting account key");
             return Err(ProgramError::InvalidArgument);
         }
 
-        if state.destination_address != *destination_token_account.key {
-            msg!("Contract destination account does not matched provided account");
+        if !header_state.is_native {
+            msg!("The vesting contract does not hold native SOL");
             return Err(ProgramError::InvalidArgument);
         }
 
-        if !destination_token_account_owner.is_signer {
-            msg!("Destination token account owner should be a signer.");
+        if header_state.destination_address != *destination_account.key {
+            msg!("Contract destination account does not matched provided account");
             return Err(ProgramError::InvalidArgument);
         }
 
-        let destination_token_account = Account::unpack(&destination_token_account.data.borrow())?;
-
-        if destination_token_account.owner != *destination_token_account_owner.key {
-            msg!("The current destination token account isn't owned by the provided owner");
-            return Err(ProgramError::InvalidArgument);
+        if header_state.paused_at.is_some() {
+            msg!("The vesting contract is paused");
+            return Err(VestingError::ContractPaused.into());
         }
 
-        let mut new_state = state;
-        new_state.destination_address = *new_destination_token_account.key;
-        new_state
-            .pack_into_slice(&mut vesting_account.data.borrow_mut()[..VestingScheduleHeader::LEN]);
+        let clock = Clock::from_account_info(clock_sysvar_account)?;
+        let current_time = header_state.time_basis.current_time(&clock);
+        let (schedules, total_amount_to_transfer) = release_matured_schedules(
+            &vesting_account.data.borrow()[header_state.packed_len()..],
+            current_time,
+        )?;
+
+        // The vesting account is owned by the program, which debits its lamports directly
+        let vesting_lamports = vesting_account
+            .lamports()
+            .checked_sub(total_amount_to_transfer)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        let destination_lamports = destination_account
+            .lamports()
+            .checked_add(total_amount_to_transfer)
+            .ok_or(ProgramError::InvalidArgument)?;
+        **vesting_account.try_borrow_mut_lamports()? = vesting_lamports;
+        **destination_account.try_borrow_mut_lamports()? = destination_lamports;
+
+        pack_schedules_into_slice(
+            schedules,
+            &mut vesting_account.data.borrow_mut()[header_state.packed_len()..],
+        );
 
+        VestingEvent::Unlocked {
+            vesting_account: vesting_account_key,
+            destination_token_address: *destination_account.key,
+            amount: total_amount_to_transfer,
+            unix_timestamp: clock.unix_timestamp,
+        }
+        .emit();
         Ok(())
     }
 
-    pub fn process_instruction(
+    pub fn process_unlock_with_associated_destination(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        instruction_data: &[u8],
+        seeds: [u8; 32],
     ) -> ProgramResult {
-        msg!("Beginning processing");
-        let instruction = VestingInstruction::unpack(instruction_data)?;
-        msg!("Instruction unpacked");
-        match instruction {
-            VestingInstruction::Init {
-                seeds,
-                number_of_schedules,
-            } => {
-                msg!("Instruction: Init");
-                Self::process_init(program_id, accounts, seeds, number_of_schedules)
-            }
-            VestingInstruction::Unlock { seeds } => {
-                msg!("Instruction: Unlock");
-                Self::process_unlock(program_id, accounts, seeds)
-            }
-            VestingInstruction::ChangeDestination { seeds } => {
-                msg!("Instruction: Change Destination");
-                Self::process_change_destination(program_id, accounts, seeds)
-            }
-            VestingInstruction::Create {
-                seeds,
-                mint_address,
-                destination_token_address,
-                schedules,
-            } => {
+        // The first accounts are checked by the unlock once the destination token account exists
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::key(&spl_token::id()),
+                AccountCheck::key(&sysvar::clock::id()),
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::owned_by(&spl_token::id()).writable(),
+                AccountCheck::any().writable(),
+                AccountCheck::any(),
+                AccountCheck::owned_by(&spl_token::id()),
+                AccountCheck::any().signer().writable(),
+                AccountCheck::key(&system_program::id()),
+                AccountCheck::key(&spl_associated_token_account::id()),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let spl_token_account = next_account_info(accounts_iter)?;
+        let _clock_sysvar_account = next_account_info(accounts_iter)?;
+        let vesting_account = next_account_info(accounts_iter)?;
+        let _vesting_token_account = next_account_info(accounts_iter)?;
+        let destination_token_account = next_account_info(accounts_iter)?;
+        let destination_account = next_account_info(accounts_iter)?;
+        let mint_account = next_account_info(accounts_iter)?;
+        let payer = next_account_info(accounts_iter)?;
+        let system_program_account = next_account_info(accounts_iter)?;
+        let associated_token_program_account = next_account_info(accounts_iter)?;
+
+        if unpack_initialized_header(vesting_account)?.is_native {
+            msg!("Native contracts are unlocked to their destination wallet");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if get_associated_token_address(destination_account.key, mint_account.key)
+            != *destination_token_account.key
+        {
+            msg!("The destination token account is not the associated token account of the destination");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Does nothing if the destination token account already exists
+        let create_destination_token_account = create_associated_token_account_idempotent(
+            payer.key,
+            destination_account.key,
+            mint_account.key,
+            spl_token_account.key,
+        );
+
+        invoke(
+            &create_destination_token_account,
+            &[
+                payer.clone(),
+                destination_token_account.clone(),
+                destination_account.clone(),
+                mint_account.clone(),
+                system_program_account.clone(),
+                spl_token_account.clone(),
+                associated_token_program_account.clone(),
+            ],
+        )?;
+
+        // The contract destination is checked against the destination token account when unlocking,
+        // the split destination accounts following the accounts of this instruction
+        let unlock_accounts = [&accounts[..5], &accounts[10..]].concat();
+        Self::process_unlock(program_id, &unlock_accounts, seeds)
+    }
+
+    pub fn process_get_claimable(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::owned_by(program_id),
+                AccountCheck::key(&sysvar::clock::id()),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let vesting_account = next_account_info(accounts_iter)?;
+        let clock_sysvar_account = next_account_info(accounts_iter)?;
+
+        let header_state = unpack_initialized_header(vesting_account)?;
+        let packed_state = vesting_account.data.borrow();
+        let schedules = unpack_schedules(&packed_state[header_state.packed_len()..])?;
+
+        let clock = Clock::from_account_info(clock_sysvar_account)?;
+        let mut amounts =
+            claimable_amounts(&schedules, header_state.time_basis.current_time(&clock));
+        // Nothing can be unlocked while the contract is paused
+        if header_state.paused_at.is_some() {
+            amounts.locked += amounts.claimable;
+            amounts.claimable = 0;
+        }
+
+        let mut return_data = [0u8; ClaimableAmounts::LEN];
+        amounts.pack_into_slice(&mut return_data);
+        set_return_data(&return_data);
+        Ok(())
+    }
+
+    pub fn process_change_destination(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        seeds: [u8; 32],
+    ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::owned_by(program_id).writable(),
+                // A token account, or the destination wallet of a native contract
+                AccountCheck::any(),
+                AccountCheck::any().signer(),
+                // The new destination may be an associated token account created when unlocking
+                AccountCheck::any(),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let vesting_account = next_account_info(accounts_iter)?;
+        let destination_token_account = next_account_info(accounts_iter)?;
+        let destination_token_account_owner = next_account_info(accounts_iter)?;
+        let new_destination_token_account = next_account_info(accounts_iter)?;
+
+        let state = unpack_initialized_header(vesting_account)?;
+        let vesting_account_key = vesting_account_address(program_id, &seeds, &state)?;
+
+        if vesting_account_key != *vesting_account.key {
+            msg!("Invalid vesting account key");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if state.destination_address != *destination_token_account.key {
+            msg!("Contract destination account does not matched provided account");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if state.is_native {
+            if destination_token_account.key != destination_token_account_owner.key {
+                msg!("The current destination wallet should sign");
+                return Err(ProgramError::InvalidArgument);
+            }
+        } else {
+            if destination_token_account.owner != &spl_token::id() {
+                msg!("Account 1 should be owned by {}", spl_token::id());
+                return Err(VestingError::IncorrectAccountOwner.into());
+            }
+
+            let destination_token_account =
+                Account::unpack(&destination_token_account.data.borrow())?;
+
+            if destination_token_account.owner != *destination_token_account_owner.key {
+                msg!("The current destination token account isn't owned by the provided owner");
+                return Err(ProgramError::InvalidArgument);
+            }
+        }
+
+        let old_destination_token_address = state.destination_address;
+        let mut new_state = state;
+        new_state.destination_address = *new_destination_token_account.key;
+        let header_len = new_state.packed_len();
+        new_state.pack_into_slice(&mut vesting_account.data.borrow_mut()[..header_len]);
+
+        VestingEvent::DestinationChanged {
+            vesting_account: vesting_account_key,
+            old_destination_token_address,
+            new_destination_token_address: *new_destination_token_account.key,
+            unix_timestamp: Clock::get()?.unix_timestamp,
+        }
+        .emit();
+        Ok(())
+    }
+
+    pub fn process_change_split_destination(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        seeds: [u8; 32],
+    ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::owned_by(&spl_token::id()),
+                AccountCheck::any().signer(),
+                // Unlocks transfer to the split destinations, which have to exist already
+                AccountCheck::owned_by(&spl_token::id()),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let vesting_account = next_account_info(accounts_iter)?;
+        let split_token_account = next_account_info(accounts_iter)?;
+        let split_token_account_owner = next_account_info(accounts_iter)?;
+        let new_split_token_account = next_account_info(accounts_iter)?;
+
+        let state = unpack_initialized_header(vesting_account)?;
+        let vesting_account_key = vesting_account_address(program_id, &seeds, &state)?;
+
+        if vesting_account_key != *vesting_account.key {
+            msg!("Invalid vesting account key");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let split_index = match state
+            .split_destinations
+            .iter()
+            .position(|split| split.token_address == *split_token_account.key)
+        {
+            Some(split_index) => split_index,
+            None => {
+                msg!("The provided account is not a split destination of the contract");
+                return Err(ProgramError::InvalidArgument);
+            }
+        };
+
+        if Account::unpack(&split_token_account.data.borrow())?.owner
+            != *split_token_account_owner.key
+        {
+            msg!("The current split destination token account isn't owned by the provided owner");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if Account::unpack(&new_split_token_account.data.borrow())?.mint != state.mint_address {
+            msg!("The new split destination token account should be of the mint of the contract");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut new_state = state;
+        new_state.split_destinations[split_index].token_address = *new_split_token_account.key;
+        let header_len = new_state.packed_len();
+        new_state.pack_into_slice(&mut vesting_account.data.borrow_mut()[..header_len]);
+
+        VestingEvent::SplitDestinationChanged {
+            vesting_account: vesting_account_key,
+            old_split_token_address: *split_token_account.key,
+            new_split_token_address: *new_split_token_account.key,
+            unix_timestamp: Clock::get()?.unix_timestamp,
+        }
+        .emit();
+        Ok(())
+    }
+
+    pub fn process_release_milestone(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        schedule_index: u32,
+    ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::any().signer(),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let vesting_account = next_account_info(accounts_iter)?;
+        let approver_account = next_account_info(accounts_iter)?;
+
+        let header_state = unpack_initialized_header(vesting_account)?;
+        if header_state.approver != Some(*approver_account.key) {
+            msg!("The signer is not the approver of the contract");
+            return Err(VestingError::IncorrectApprover.into());
+        }
+
+        let mut packed_state = vesting_account.data.borrow_mut();
+        let offset = header_state.packed_len() + (schedule_index as usize) * VestingSchedule::LEN;
+        let packed_schedule = match packed_state.get_mut(offset..offset + VestingSchedule::LEN) {
+            Some(packed_schedule) => packed_schedule,
+            None => {
+                msg!("The contract has no schedule {}", schedule_index);
+                return Err(ProgramError::InvalidArgument);
+            }
+        };
+        let mut schedule = VestingSchedule::unpack_from_slice(packed_schedule)?;
+        if schedule.amount == 0 {
+            msg!("The milestone was already unlocked");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Any time basis has reached zero
+        schedule.release_time = 0;
+        schedule.pack_into_slice(packed_schedule);
+
+        VestingEvent::MilestoneReleased {
+            vesting_account: *vesting_account.key,
+            schedule_index,
+            unix_timestamp: Clock::get()?.unix_timestamp,
+        }
+        .emit();
+        Ok(())
+    }
+
+    pub fn process_pause(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::any().signer(),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let vesting_account = next_account_info(accounts_iter)?;
+        let pause_authority_account = next_account_info(accounts_iter)?;
+
+        let mut header_state = unpack_initialized_header(vesting_account)?;
+        if header_state.pause_authority != Some(*pause_authority_account.key) {
+            msg!("The signer is not the pause authority of the contract");
+            return Err(VestingError::IncorrectPauseAuthority.into());
+        }
+        if header_state.paused_at.is_some() {
+            msg!("The vesting contract is already paused");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let clock = Clock::get()?;
+        header_state.paused_at = Some(header_state.time_basis.current_time(&clock));
+        header_state
+            .pack_into_slice(&mut vesting_account.data.borrow_mut()[..header_state.packed_len()]);
+
+        VestingEvent::Paused {
+            vesting_account: *vesting_account.key,
+            unix_timestamp: clock.unix_timestamp,
+        }
+        .emit();
+        Ok(())
+    }
+
+    pub fn process_resume(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        shift_release_times: bool,
+    ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::any().signer(),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let vesting_account = next_account_info(accounts_iter)?;
+        let pause_authority_account = next_account_info(accounts_iter)?;
+
+        let mut header_state = unpack_initialized_header(vesting_account)?;
+        if header_state.pause_authority != Some(*pause_authority_account.key) {
+            msg!("The signer is not the pause authority of the contract");
+            return Err(VestingError::IncorrectPauseAuthority.into());
+        }
+        let paused_at = match header_state.paused_at {
+            Some(paused_at) => paused_at,
+            None => {
+                msg!("The vesting contract is not paused");
+                return Err(ProgramError::InvalidArgument);
+            }
+        };
+
+        let clock = Clock::get()?;
+        let paused_duration = header_state
+            .time_basis
+            .current_time(&clock)
+            .saturating_sub(paused_at);
+        header_state.paused_at = None;
+
+        let mut packed_state = vesting_account.data.borrow_mut();
+        header_state.pack_into_slice(&mut packed_state[..header_state.packed_len()]);
+        if shift_release_times {
+            // Only the schedules that had not matured when the contract was paused are delayed,
+            // released milestones having a zero release time
+            let mut schedules = unpack_schedules(&packed_state[header_state.packed_len()..])?;
+            for s in schedules.iter_mut() {
+                if s.amount != 0 && s.release_time > paused_at {
+                    s.release_time = s.release_time.saturating_add(paused_duration);
+                }
+            }
+            pack_schedules_into_slice(schedules, &mut packed_state[header_state.packed_len()..]);
+        }
+
+        VestingEvent::Resumed {
+            vesting_account: *vesting_account.key,
+            paused_duration,
+            shifted_release_times: shift_release_times,
+            unix_timestamp: clock.unix_timestamp,
+        }
+        .emit();
+        Ok(())
+    }
+
+    pub fn process_init_pool(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        seeds: [u8; 32],
+        mint_address: &Pubkey,
+        pool_authority: &Pubkey,
+        time_basis: TimeBasis,
+    ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::key(&system_program::id()),
+                AccountCheck::key(&sysvar::rent::id()),
+                AccountCheck::any().signer().writable(),
+                // The pool account does not exist yet
+                AccountCheck::owned_by(&system_program::id()).writable(),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let system_program_account = next_account_info(accounts_iter)?;
+        let rent_sysvar_account = next_account_info(accounts_iter)?;
+        let payer = next_account_info(accounts_iter)?;
+        let pool_account = next_account_info(accounts_iter)?;
+
+        let bump_seed = create_seeded_account(
+            program_id,
+            &seeds,
+            PoolHeader::LEN,
+            system_program_account,
+            rent_sysvar_account,
+            payer,
+            pool_account,
+        )?;
+
+        let pool = PoolHeader {
+            pool_authority: *pool_authority,
+            mint_address: *mint_address,
+            is_initialized: true,
+            bump_seed,
+            time_basis,
+            allocated_amount: 0,
+        };
+        pool.pack_into_slice(&mut pool_account.data.borrow_mut());
+
+        VestingEvent::PoolCreated {
+            pool_account: *pool_account.key,
+            mint_address: *mint_address,
+            pool_authority: *pool_authority,
+            unix_timestamp: Clock::get()?.unix_timestamp,
+        }
+        .emit();
+        Ok(())
+    }
+
+    pub fn process_add_allocation(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        schedules: Vec<Schedule>,
+    ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::key(&system_program::id()),
+                AccountCheck::any().signer().writable(),
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::any().signer(),
+                AccountCheck::owned_by(&spl_token::id()),
+                // The allocation account does not exist yet
+                AccountCheck::owned_by(&system_program::id()).writable(),
+                AccountCheck::owned_by(&spl_token::id()),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let system_program_account = next_account_info(accounts_iter)?;
+        let payer = next_account_info(accounts_iter)?;
+        let pool_account = next_account_info(accounts_iter)?;
+        let pool_authority_account = next_account_info(accounts_iter)?;
+        let pool_token_account = next_account_info(accounts_iter)?;
+        let allocation_account = next_account_info(accounts_iter)?;
+        let destination_token_account = next_account_info(accounts_iter)?;
+
+        let mut pool = unpack_initialized_pool(pool_account)?;
+        if pool.pool_authority != *pool_authority_account.key {
+            msg!("The signer is not the authority of the pool");
+            return Err(VestingError::IncorrectPoolAuthority.into());
+        }
+
+        // A single token account funds the pool so that its balance is not counted twice
+        if get_associated_token_address(pool_account.key, &pool.mint_address)
+            != *pool_token_account.key
+        {
+            msg!("The pool token account is not the associated token account of the pool");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if Account::unpack(&destination_token_account.data.borrow())?.mint != pool.mint_address {
+            msg!("The destination token account is not an account of the mint of the pool");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let (allocation_account_key, bump_seed) =
+            find_allocation_address(program_id, pool_account.key, destination_token_account.key);
+        if allocation_account_key != *allocation_account.key {
+            msg!("Invalid allocation account key");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut total_amount: u64 = 0;
+        for s in schedules.iter() {
+            total_amount = match total_amount.checked_add(s.amount) {
+                Some(n) => n,
+                None => return Err(ProgramError::InvalidInstructionData), // Total amount overflows u64
+            };
+        }
+
+        // The allocations not claimed yet never exceed the funding of the pool
+        let pool_balance = Account::unpack(&pool_token_account.data.borrow())?.amount;
+        let allocated_amount = pool
+            .allocated_amount
+            .checked_add(total_amount)
+            .filter(|allocated_amount| *allocated_amount <= pool_balance)
+            .ok_or_else(|| {
+                msg!("The pool token account holds {} tokens", pool_balance);
+                VestingError::PoolOverAllocated
+            })?;
+
+        let state_size = AllocationHeader::LEN + schedules.len() * VestingSchedule::LEN;
+        let init_allocation_account = create_account(
+            payer.key,
+            &allocation_account_key,
+            Rent::get()?.minimum_balance(state_size),
+            state_size as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &init_allocation_account,
+            &[
+                system_program_account.clone(),
+                payer.clone(),
+                allocation_account.clone(),
+            ],
+            &[&[
+                pool_account.key.as_ref(),
+                destination_token_account.key.as_ref(),
+                &[bump_seed],
+            ]],
+        )?;
+
+        let header = AllocationHeader {
+            pool_address: *pool_account.key,
+            destination_address: *destination_token_account.key,
+            is_initialized: true,
+            bump_seed,
+        };
+        let mut data = allocation_account.data.borrow_mut();
+        header.pack_into_slice(&mut data[..AllocationHeader::LEN]);
+        pack_schedules_into_slice(
+            schedules
+                .iter()
+                .map(|s| VestingSchedule {
+                    release_time: s.release_time,
+                    amount: s.amount,
+                })
+                .collect(),
+            &mut data[AllocationHeader::LEN..],
+        );
+
+        pool.allocated_amount = allocated_amount;
+        pool.pack_into_slice(&mut pool_account.data.borrow_mut());
+
+        VestingEvent::AllocationAdded {
+            pool_account: *pool_account.key,
+            allocation_account: allocation_account_key,
+            destination_token_address: *destination_token_account.key,
+            total_amount,
+            schedules,
+            unix_timestamp: Clock::get()?.unix_timestamp,
+        }
+        .emit();
+        Ok(())
+    }
+
+    pub fn process_remove_allocation(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::any().signer(),
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::any().writable(),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let pool_account = next_account_info(accounts_iter)?;
+        let pool_authority_account = next_account_info(accounts_iter)?;
+        let allocation_account = next_account_info(accounts_iter)?;
+        let rent_receiver_account = next_account_info(accounts_iter)?;
+
+        let mut pool = unpack_initialized_pool(pool_account)?;
+        if pool.pool_authority != *pool_authority_account.key {
+            msg!("The signer is not the authority of the pool");
+            return Err(VestingError::IncorrectPoolAuthority.into());
+        }
+        let mut allocation =
+            unpack_pool_allocation(program_id, allocation_account, pool_account.key)?;
+
+        // The schedules that reached their release time stay claimable
+        let clock = Clock::get()?;
+        let current_time = pool.time_basis.current_time(&clock);
+        let mut cancelled_amount = 0;
+        for s in allocation.schedules.iter_mut() {
+            if current_time < s.release_time {
+                cancelled_amount += s.amount;
+                s.amount = 0;
+            }
+        }
+        pool.allocated_amount = pool
+            .allocated_amount
+            .checked_sub(cancelled_amount)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        pool.pack_into_slice(&mut pool_account.data.borrow_mut());
+
+        if allocation.total_locked() == Some(0) {
+            // Closing the allocation account lets the beneficiary be allocated again
+            let rent_receiver_lamports = rent_receiver_account
+                .lamports()
+                .checked_add(allocation_account.lamports())
+                .ok_or(ProgramError::InvalidArgument)?;
+            **rent_receiver_account.try_borrow_mut_lamports()? = rent_receiver_lamports;
+            **allocation_account.try_borrow_mut_lamports()? = 0;
+            allocation_account.data.borrow_mut().fill(0);
+        } else {
+            pack_schedules_into_slice(
+                allocation.schedules,
+                &mut allocation_account.data.borrow_mut()[AllocationHeader::LEN..],
+            );
+        }
+
+        VestingEvent::AllocationRemoved {
+            pool_account: *pool_account.key,
+            allocation_account: *allocation_account.key,
+            cancelled_amount,
+            unix_timestamp: clock.unix_timestamp,
+        }
+        .emit();
+        Ok(())
+    }
+
+    pub fn process_claim_allocation(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        seeds: [u8; 32],
+    ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::key(&spl_token::id()),
+                AccountCheck::key(&sysvar::clock::id()),
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::owned_by(&spl_token::id()).writable(),
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::owned_by(&spl_token::id()).writable(),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let spl_token_account = next_account_info(accounts_iter)?;
+        let clock_sysvar_account = next_account_info(accounts_iter)?;
+        let pool_account = next_account_info(accounts_iter)?;
+        let pool_token_account = next_account_info(accounts_iter)?;
+        let allocation_account = next_account_info(accounts_iter)?;
+        let destination_token_account = next_account_info(accounts_iter)?;
+
+        let mut pool = unpack_initialized_pool(pool_account)?;
+        let pool_account_key = seeded_account_address(program_id, &seeds, pool.bump_seed)?;
+        if pool_account_key != *pool_account.key {
+            msg!("Invalid pool account key");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if get_associated_token_address(&pool_account_key, &pool.mint_address)
+            != *pool_token_account.key
+        {
+            msg!("The pool token account is not the associated token account of the pool");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let allocation = unpack_pool_allocation(program_id, allocation_account, &pool_account_key)?;
+        if allocation.header.destination_address != *destination_token_account.key {
+            msg!("Allocation destination account does not matched provided account");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let clock = Clock::from_account_info(clock_sysvar_account)?;
+        let current_time = pool.time_basis.current_time(&clock);
+        let (schedules, amount) = release_matured_schedules(
+            &allocation_account.data.borrow()[AllocationHeader::LEN..],
+            current_time,
+        )?;
+
+        let transfer_tokens_from_pool_account = transfer(
+            spl_token_account.key,
+            pool_token_account.key,
+            destination_token_account.key,
+            &pool_account_key,
+            &[],
+            amount,
+        )?;
+
+        invoke_signed(
+            &transfer_tokens_from_pool_account,
+            &[
+                spl_token_account.clone(),
+                pool_token_account.clone(),
+                destination_token_account.clone(),
+                pool_account.clone(),
+            ],
+            &[&[&seeds]],
+        )?;
+
+        pool.allocated_amount = pool
+            .allocated_amount
+            .checked_sub(amount)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        pool.pack_into_slice(&mut pool_account.data.borrow_mut());
+        pack_schedules_into_slice(
+            schedules,
+            &mut allocation_account.data.borrow_mut()[AllocationHeader::LEN..],
+        );
+
+        VestingEvent::AllocationClaimed {
+            pool_account: pool_account_key,
+            allocation_account: *allocation_account.key,
+            destination_token_address: *destination_token_account.key,
+            amount,
+            unix_timestamp: clock.unix_timestamp,
+        }
+        .emit();
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_init_distributor(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        seeds: [u8; 32],
+        mint_address: &Pubkey,
+        merkle_root: [u8; 32],
+        time_basis: TimeBasis,
+        start_time: u64,
+        cliff_time: u64,
+        end_time: u64,
+        total_amount: u64,
+    ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::key(&system_program::id()),
+                AccountCheck::key(&sysvar::rent::id()),
+                AccountCheck::any().signer().writable(),
+                // The distributor account does not exist yet
+                AccountCheck::owned_by(&system_program::id()).writable(),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let system_program_account = next_account_info(accounts_iter)?;
+        let rent_sysvar_account = next_account_info(accounts_iter)?;
+        let payer = next_account_info(accounts_iter)?;
+        let distributor_account = next_account_info(accounts_iter)?;
+
+        let mut distributor = DistributorHeader {
+            merkle_root,
+            mint_address: *mint_address,
+            is_initialized: true,
+            bump_seed: 0,
+            time_basis,
+            start_time,
+            cliff_time,
+            end_time,
+            total_amount,
+            claimed_amount: 0,
+        };
+        if !distributor.is_valid_period() {
+            msg!("The cliff time should lie between the start time and the end time");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        distributor.bump_seed = create_seeded_account(
+            program_id,
+            &seeds,
+            DistributorHeader::LEN,
+            system_program_account,
+            rent_sysvar_account,
+            payer,
+            distributor_account,
+        )?;
+        distributor.pack_into_slice(&mut distributor_account.data.borrow_mut());
+
+        VestingEvent::DistributorCreated {
+            distributor_account: *distributor_account.key,
+            mint_address: *mint_address,
+            merkle_root,
+            total_amount,
+            unix_timestamp: Clock::get()?.unix_timestamp,
+        }
+        .emit();
+        Ok(())
+    }
+
+    pub fn process_claim_distribution(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        seeds: [u8; 32],
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> ProgramResult {
+        validate_accounts(
+            accounts,
+            &[
+                AccountCheck::key(&spl_token::id()),
+                AccountCheck::key(&system_program::id()),
+                AccountCheck::key(&sysvar::clock::id()),
+                AccountCheck::owned_by(program_id).writable(),
+                AccountCheck::owned_by(&spl_token::id()).writable(),
+                // The claim account does not exist before the first claim
+                AccountCheck::any().writable(),
+                AccountCheck::any().signer().writable(),
+                AccountCheck::owned_by(&spl_token::id()).writable(),
+            ],
+        )?;
+        let accounts_iter = &mut accounts.iter();
+
+        let spl_token_account = next_account_info(accounts_iter)?;
+        let system_program_account = next_account_info(accounts_iter)?;
+        let clock_sysvar_account = next_account_info(accounts_iter)?;
+        let distributor_account = next_account_info(accounts_iter)?;
+        let distributor_token_account = next_account_info(accounts_iter)?;
+        let claim_account = next_account_info(accounts_iter)?;
+        let claimant = next_account_info(accounts_iter)?;
+        let destination_token_account = next_account_info(accounts_iter)?;
+
+        let mut distributor = DistributorHeader::unpack(&distributor_account.data.borrow())?;
+        let distributor_account_key =
+            seeded_account_address(program_id, &seeds, distributor.bump_seed)?;
+        if distributor_account_key != *distributor_account.key {
+            msg!("Invalid distributor account key");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if get_associated_token_address(&distributor_account_key, &distributor.mint_address)
+            != *distributor_token_account.key
+        {
+            msg!("The distributor token account is not its associated token account");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let (claim_account_key, bump_seed) =
+            find_distribution_claim_address(program_id, &distributor_account_key, claimant.key);
+        if claim_account_key != *claim_account.key {
+            msg!("Invalid claim account key");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let is_first_claim = *claim_account.owner == system_program::id();
+        let mut claim = if is_first_claim {
+            if !verify_proof(
+                &proof,
+                &distributor.merkle_root,
+                leaf_hash(claimant.key, amount),
+            ) {
+                return Err(VestingError::InvalidMerkleProof.into());
+            }
+
+            let init_claim_account = create_account(
+                claimant.key,
+                &claim_account_key,
+                Rent::get()?.minimum_balance(DistributionClaim::LEN),
+                DistributionClaim::LEN as u64,
+                program_id,
+            );
+            invoke_signed(
+                &init_claim_account,
+                &[
+                    system_program_account.clone(),
+                    claimant.clone(),
+                    claim_account.clone(),
+                ],
+                &[&[
+                    distributor_account_key.as_ref(),
+                    claimant.key.as_ref(),
+                    &[bump_seed],
+                ]],
+            )?;
+
+            DistributionClaim {
+                distributor_address: distributor_account_key,
+                claimant: *claimant.key,
+                is_initialized: true,
+                bump_seed,
+                amount,
+                claimed_amount: 0,
+            }
+        } else if *claim_account.owner == *program_id {
+            DistributionClaim::unpack(&claim_account.data.borrow())?
+        } else {
+            return Err(VestingError::IncorrectAccountOwner.into());
+        };
+
+        let clock = Clock::from_account_info(clock_sysvar_account)?;
+        let current_time = distributor.time_basis.current_time(&clock);
+        let released_amount = distributor
+            .vested_amount(claim.amount, current_time)
+            .saturating_sub(claim.claimed_amount);
+        if released_amount == 0 && !is_first_claim {
+            msg!("Nothing more has vested yet");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // A tree whose allocations exceed its total cannot drain the funds of other distributors
+        let claimed_amount = distributor
+            .claimed_amount
+            .checked_add(released_amount)
+            .filter(|claimed_amount| *claimed_amount <= distributor.total_amount)
+            .ok_or_else(|| {
+                msg!("The claims would exceed the total amount of the distributor");
+                ProgramError::InsufficientFunds
+            })?;
+
+        let transfer_tokens_from_distributor_account = transfer(
+            spl_token_account.key,
+            distributor_token_account.key,
+            destination_token_account.key,
+            &distributor_account_key,
+            &[],
+            released_amount,
+        )?;
+
+        invoke_signed(
+            &transfer_tokens_from_distributor_account,
+            &[
+                spl_token_account.clone(),
+                distributor_token_account.clone(),
+                destination_token_account.clone(),
+                distributor_account.clone(),
+            ],
+            &[&[&seeds]],
+        )?;
+
+        distributor.claimed_amount = claimed_amount;
+        distributor.pack_into_slice(&mut distributor_account.data.borrow_mut());
+        claim.claimed_amount += released_amount;
+        claim.pack_into_slice(&mut claim_account.data.borrow_mut());
+
+        VestingEvent::DistributionClaimed {
+            distributor_account: distributor_account_key,
+            claimant: *claimant.key,
+            destination_token_address: *destination_token_account.key,
+            amount: released_amount,
+            unix_timestamp: clock.unix_timestamp,
+        }
+        .emit();
+        Ok(())
+    }
+
+    pub fn process_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        msg!("Beginning processing");
+        let instruction = VestingInstruction::unpack(instruction_data)?;
+        msg!("Instruction unpacked");
+        match instruction {
+            VestingInstruction::Init {
+                seeds,
+                number_of_schedules,
+            } => {
+                msg!("Instruction: Init");
+                Self::process_init(program_id, accounts, seeds, number_of_schedules)
+            }
+            VestingInstruction::Unlock { seeds } => {
+                msg!("Instruction: Unlock");
+                Self::process_unlock(program_id, accounts, seeds)
+            }
+            VestingInstruction::ChangeDestination { seeds } => {
+                msg!("Instruction: Change Destination");
+                Self::process_change_destination(program_id, accounts, seeds)
+            }
+            VestingInstruction::ChangeSplitDestination { seeds } => {
+                msg!("Instruction: Change Split Destination");
+                Self::process_change_split_destination(program_id, accounts, seeds)
+            }
+            VestingInstruction::UnlockWithAssociatedDestination { seeds } => {
+                msg!("Instruction: Unlock With Associated Destination");
+                Self::process_unlock_with_associated_destination(program_id, accounts, seeds)
+            }
+            VestingInstruction::GetClaimable => {
+                msg!("Instruction: Get Claimable");
+                Self::process_get_claimable(program_id, accounts)
+            }
+            VestingInstruction::Create {
+                seeds,
+                mint_address,
+                destination_token_address,
+                schedules,
+            } => {
                 msg!("Instruction: Create Schedule");
                 Self::process_create(
                     program_id,
@@ -354,9 +1820,197 @@ impl Processor {
                     seeds,
                     &mint_address,
                     &destination_token_address,
+                    TimeBasis::UnixTimestamp,
+                    None,
+                    None,
+                    vec![],
+                    schedules,
+                )
+            }
+            VestingInstruction::CreateWithTimeBasis {
+                seeds,
+                mint_address,
+                destination_token_address,
+                time_basis,
+                schedules,
+            } => {
+                msg!("Instruction: Create Schedule With Time Basis");
+                Self::process_create(
+                    program_id,
+                    accounts,
+                    seeds,
+                    &mint_address,
+                    &destination_token_address,
+                    time_basis,
+                    None,
+                    None,
+                    vec![],
+                    schedules,
+                )
+            }
+            VestingInstruction::CreateMilestones {
+                seeds,
+                mint_address,
+                destination_token_address,
+                time_basis,
+                approver,
+                schedules,
+            } => {
+                msg!("Instruction: Create Milestones");
+                Self::process_create(
+                    program_id,
+                    accounts,
+                    seeds,
+                    &mint_address,
+                    &destination_token_address,
+                    time_basis,
+                    Some(approver),
+                    None,
+                    vec![],
+                    schedules,
+                )
+            }
+            VestingInstruction::ReleaseMilestone { schedule_index } => {
+                msg!("Instruction: Release Milestone");
+                Self::process_release_milestone(program_id, accounts, schedule_index)
+            }
+            VestingInstruction::CreatePausable {
+                seeds,
+                mint_address,
+                destination_token_address,
+                time_basis,
+                approver,
+                pause_authority,
+                schedules,
+            } => {
+                msg!("Instruction: Create Pausable");
+                Self::process_create(
+                    program_id,
+                    accounts,
+                    seeds,
+                    &mint_address,
+                    &destination_token_address,
+                    time_basis,
+                    approver,
+                    Some(pause_authority),
+                    vec![],
+                    schedules,
+                )
+            }
+            VestingInstruction::CreateWithSplits {
+                seeds,
+                mint_address,
+                destination_token_address,
+                time_basis,
+                approver,
+                pause_authority,
+                split_destinations,
+                schedules,
+            } => {
+                msg!("Instruction: Create With Splits");
+                Self::process_create(
+                    program_id,
+                    accounts,
+                    seeds,
+                    &mint_address,
+                    &destination_token_address,
+                    time_basis,
+                    approver,
+                    pause_authority,
+                    split_destinations,
+                    schedules,
+                )
+            }
+            VestingInstruction::CreateNative {
+                seeds,
+                destination_address,
+                time_basis,
+                approver,
+                pause_authority,
+                schedules,
+            } => {
+                msg!("Instruction: Create Native");
+                Self::process_create_native(
+                    program_id,
+                    accounts,
+                    seeds,
+                    &destination_address,
+                    time_basis,
+                    approver,
+                    pause_authority,
                     schedules,
                 )
             }
+            VestingInstruction::InitPool {
+                seeds,
+                mint_address,
+                pool_authority,
+                time_basis,
+            } => {
+                msg!("Instruction: Init Pool");
+                Self::process_init_pool(
+                    program_id,
+                    accounts,
+                    seeds,
+                    &mint_address,
+                    &pool_authority,
+                    time_basis,
+                )
+            }
+            VestingInstruction::AddAllocation { schedules } => {
+                msg!("Instruction: Add Allocation");
+                Self::process_add_allocation(program_id, accounts, schedules)
+            }
+            VestingInstruction::RemoveAllocation => {
+                msg!("Instruction: Remove Allocation");
+                Self::process_remove_allocation(program_id, accounts)
+            }
+            VestingInstruction::ClaimAllocation { seeds } => {
+                msg!("Instruction: Claim Allocation");
+                Self::process_claim_allocation(program_id, accounts, seeds)
+            }
+            VestingInstruction::InitDistributor {
+                seeds,
+                mint_address,
+                merkle_root,
+                time_basis,
+                start_time,
+                cliff_time,
+                end_time,
+                total_amount,
+            } => {
+                msg!("Instruction: Init Distributor");
+                Self::process_init_distributor(
+                    program_id,
+                    accounts,
+                    seeds,
+                    &mint_address,
+                    merkle_root,
+                    time_basis,
+                    start_time,
+                    cliff_time,
+                    end_time,
+                    total_amount,
+                )
+            }
+            VestingInstruction::ClaimDistribution {
+                seeds,
+                amount,
+                proof,
+            } => {
+                msg!("Instruction: Claim Distribution");
+                Self::process_claim_distribution(program_id, accounts, seeds, amount, proof)
+            }
+            VestingInstruction::Pause => {
+                msg!("Instruction: Pause");
+                Self::process_pause(program_id, accounts)
+            }
+            VestingInstruction::Resume {
+                shift_release_times,
+            } => {
+                msg!("Instruction: Resume");
+                Self::process_resume(program_id, accounts, shift_release_times)
+            }
         }
     }
 }
@@ -368,6 +2022,18 @@ impl PrintProgramError for VestingError {
     {
         match self {
             VestingError::InvalidInstruction => msg!("Error: Invalid instruction!"),
+            VestingError::MissingRequiredSigner => msg!("Error: Missing required signer!"),
+            VestingError::AccountNotWritable => msg!("Error: Account not writable!"),
+            VestingError::IncorrectAccountKey => msg!("Error: Incorrect account key!"),
+            VestingError::IncorrectAccountOwner => msg!("Error: Incorrect account owner!"),
+            VestingError::NonCanonicalBumpSeed => msg!("Error: Non canonical bump seed!"),
+            VestingError::IncorrectApprover => msg!("Error: Incorrect approver!"),
+            VestingError::IncorrectPauseAuthority => msg!("Error: Incorrect pause authority!"),
+            VestingError::ContractPaused => msg!("Error: Contract paused!"),
+            VestingError::InvalidDestinationSplits => msg!("Error: Invalid destination splits!"),
+            VestingError::IncorrectPoolAuthority => msg!("Error: Incorrect pool authority!"),
+            VestingError::PoolOverAllocated => msg!("Error: Pool over allocated!"),
+            VestingError::InvalidMerkleProof => msg!("Error: Invalid Merkle proof!"),
         }
     }
 }
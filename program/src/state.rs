@@ -1,27 +1,209 @@
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
+    clock::Clock,
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
     pubkey::Pubkey,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
+
+/// Clock value that the release times of a contract are compared with
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TimeBasis {
+    #[default]
+    UnixTimestamp,
+    Slot,
+    Epoch,
+}
+
+impl TimeBasis {
+    /// Current time of the cluster in this basis
+    pub fn current_time(&self, clock: &Clock) -> u64 {
+        match self {
+            // Kept as a plain cast to release schedules exactly as before time bases existed
+            TimeBasis::UnixTimestamp => clock.unix_timestamp as u64,
+            TimeBasis::Slot => clock.slot,
+            TimeBasis::Epoch => clock.epoch,
+        }
+    }
+
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(TimeBasis::UnixTimestamp),
+            1 => Some(TimeBasis::Slot),
+            2 => Some(TimeBasis::Epoch),
+            _ => None,
+        }
+    }
+}
+
+/// Maximum number of split destinations of a contract, besides its destination
+pub const MAX_SPLIT_DESTINATIONS: usize = 4;
+
+/// Basis points of a whole release
+pub const TOTAL_BASIS_POINTS: u16 = 10_000;
+
+/// Destination receiving a share of every release of a contract, in basis points. The share is
+/// rounded down and the destination of the contract receives the rest of the release.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DestinationSplit {
+    pub token_address: Pubkey,
+    pub basis_points: u16,
+}
+
+impl DestinationSplit {
+    /// Share of a release sent to this destination
+    pub fn share_of(&self, amount: u64) -> u64 {
+        (amount as u128 * self.basis_points as u128 / TOTAL_BASIS_POINTS as u128) as u64
+    }
+}
+
+/// Whether split destinations can be stored in a contract: at most `MAX_SPLIT_DESTINATIONS`, each
+/// with a share, the shares not exceeding the whole release
+pub fn are_valid_splits(split_destinations: &[DestinationSplit]) -> bool {
+    split_destinations.len() <= MAX_SPLIT_DESTINATIONS
+        && split_destinations.iter().all(|split| split.basis_points > 0)
+        && split_destinations
+            .iter()
+            .map(|split| split.basis_points as u32)
+            .sum::<u32>()
+            <= TOTAL_BASIS_POINTS as u32
+}
+
+/// Type of a program account, stored after its two leading 32 byte fields. Accounts are told
+/// apart by their type only, as accounts of different types may have the same size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AccountType {
+    // Vesting account created by `Init`, waiting for its contract
+    Uninitialized,
+    // Vesting account whose header was written before the layout of headers was versioned
+    LegacyVesting,
+    Vesting,
+    Pool,
+    Allocation,
+    Distributor,
+    DistributionClaim,
+}
+
+impl AccountType {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(AccountType::Uninitialized),
+            1 => Some(AccountType::LegacyVesting),
+            2 => Some(AccountType::Vesting),
+            3 => Some(AccountType::Pool),
+            4 => Some(AccountType::Allocation),
+            5 => Some(AccountType::Distributor),
+            6 => Some(AccountType::DistributionClaim),
+            _ => None,
+        }
+    }
+
+    // Type byte of an account of this type, which may not be initialized yet
+    fn pack(self, is_initialized: bool) -> u8 {
+        if is_initialized {
+            self as u8
+        } else {
+            AccountType::Uninitialized as u8
+        }
+    }
+
+    // Whether an account of this type is initialized, failing for accounts of other types
+    fn unpack_is_initialized(self, value: u8) -> Result<bool, ProgramError> {
+        match Self::from_u8(value) {
+            Some(AccountType::Uninitialized) => Ok(false),
+            Some(account_type) if account_type == self => Ok(true),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct VestingSchedule {
     pub release_time: u64,
     pub amount: u64,
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct VestingScheduleHeader {
     pub destination_address: Pubkey,
     pub mint_address: Pubkey,
     pub is_initialized: bool,
+    // Canonical bump seed of the vesting account address, stored when the account is initialized
+    pub bump_seed: u8,
+    pub time_basis: TimeBasis,
+    // Signer of the `ReleaseMilestone` instructions of a milestone contract
+    pub approver: Option<Pubkey>,
+    // Signer of the `Pause` and `Resume` instructions of a pausable contract
+    pub pause_authority: Option<Pubkey>,
+    // Time at which the contract was paused, in its time basis, if it is paused
+    pub paused_at: Option<u64>,
+    // Destinations receiving a share of every release, the destination address receiving the rest
+    pub split_destinations: Vec<DestinationSplit>,
+    // Whether the vesting account holds lamports released to a destination wallet instead of
+    // tokens, the mint address being the default pubkey
+    pub is_native: bool,
+    // Whether the header was written before the layout of headers was versioned. Such a header
+    // ends with its account type and does not store the bump seed of the vesting account.
+    pub is_legacy: bool,
+}
+
+impl VestingScheduleHeader {
+    /// Version of the layout of the headers written by the program. Decoding dispatches on the
+    /// version stored in the header, so new fields come with a new version rather than with
+    /// reserved bytes paid for by every contract.
+    pub const LAYOUT_VERSION: u8 = 1;
+
+    /// Size of the legacy headers, the schedules of their contracts following them. Accounts
+    /// initialized by earlier versions of the program only have room for this header.
+    pub const LEGACY_LEN: usize = 65;
+
+    /// Size of the header in its account, where the schedules of the contract start
+    pub fn packed_len(&self) -> usize {
+        if self.is_legacy {
+            Self::LEGACY_LEN
+        } else {
+            Self::LEN
+        }
+    }
+
+    /// Whether the contract has no option beyond the fields of the legacy layout
+    pub fn fits_legacy_layout(&self) -> bool {
+        self.time_basis == TimeBasis::UnixTimestamp
+            && self.approver.is_none()
+            && self.pause_authority.is_none()
+            && self.split_destinations.is_empty()
+            && !self.is_native
+    }
+
+    fn legacy(destination_address: Pubkey, mint_address: Pubkey, is_initialized: bool) -> Self {
+        Self {
+            destination_address,
+            mint_address,
+            is_initialized,
+            bump_seed: 0,
+            time_basis: TimeBasis::UnixTimestamp,
+            approver: None,
+            pause_authority: None,
+            paused_at: None,
+            split_destinations: vec![],
+            is_native: false,
+            is_legacy: true,
+        }
+    }
 }
 
 impl Sealed for VestingScheduleHeader {}
 
 impl Pack for VestingScheduleHeader {
-    const LEN: usize = 65;
+    const LEN: usize = 281;
 
     fn pack_into_slice(&self, target: &mut [u8]) {
         let destination_address_bytes = self.destination_address.to_bytes();
@@ -34,20 +216,108 @@ impl Pack for VestingScheduleHeader {
             target[i] = mint_address_bytes[i - 32];
         }
 
-        target[64] = self.is_initialized as u8;
+        // Legacy contracts only keep the fields of the legacy layout
+        if self.is_legacy {
+            target[64] = AccountType::LegacyVesting.pack(self.is_initialized);
+            return;
+        }
+        target[64] = AccountType::Vesting.pack(self.is_initialized);
+        target[65] = Self::LAYOUT_VERSION;
+        target[66] = self.bump_seed;
+        target[67] = self.time_basis as u8;
+        target[68] = self.approver.is_some() as u8;
+        target[69..101].copy_from_slice(self.approver.unwrap_or_default().as_ref());
+        target[101] = self.pause_authority.is_some() as u8;
+        target[102..134].copy_from_slice(self.pause_authority.unwrap_or_default().as_ref());
+        target[134] = self.paused_at.is_some() as u8;
+        target[135..143].copy_from_slice(&self.paused_at.unwrap_or_default().to_le_bytes());
+        target[143] = self.split_destinations.len() as u8;
+        for (i, chunk) in target[144..280].chunks_exact_mut(34).enumerate() {
+            let split = self.split_destinations.get(i);
+            chunk[..32].copy_from_slice(
+                split
+                    .map(|split| split.token_address)
+                    .unwrap_or_default()
+                    .as_ref(),
+            );
+            chunk[32..].copy_from_slice(
+                &split
+                    .map(|split| split.basis_points)
+                    .unwrap_or_default()
+                    .to_le_bytes(),
+            );
+        }
+        target[280] = self.is_native as u8;
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        if src.len() < 65 {
+        if src.len() < Self::LEGACY_LEN {
             return Err(ProgramError::InvalidAccountData)
         }
         let destination_address = Pubkey::new(&src[..32]);
         let mint_address = Pubkey::new(&src[32..64]);
-        let is_initialized = src[64] == 1;
+        let is_initialized = match AccountType::from_u8(src[64]) {
+            // Earlier versions of the program left the account zeroed after the account type
+            Some(AccountType::Uninitialized) if src.get(65) != Some(&Self::LAYOUT_VERSION) => {
+                return Ok(Self::legacy(destination_address, mint_address, false))
+            }
+            Some(AccountType::Uninitialized) => false,
+            Some(AccountType::Vesting) => true,
+            // The bump seed of a legacy contract is derived from the seeds of its instructions
+            Some(AccountType::LegacyVesting) => {
+                return Ok(Self::legacy(destination_address, mint_address, true))
+            }
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        if src.len() < Self::LEN || src[65] != Self::LAYOUT_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let bump_seed = src[66];
+        let time_basis = TimeBasis::from_u8(src[67]).ok_or(ProgramError::InvalidAccountData)?;
+        let approver = match src[68] {
+            0 => None,
+            1 => Some(Pubkey::new_from_array(src[69..101].try_into().unwrap())),
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let pause_authority = match src[101] {
+            0 => None,
+            1 => Some(Pubkey::new_from_array(src[102..134].try_into().unwrap())),
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let paused_at = match src[134] {
+            0 => None,
+            1 => Some(u64::from_le_bytes(src[135..143].try_into().unwrap())),
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let number_of_splits = src[143] as usize;
+        if number_of_splits > MAX_SPLIT_DESTINATIONS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let split_destinations = src[144..280]
+            .chunks_exact(34)
+            .take(number_of_splits)
+            .map(|chunk| DestinationSplit {
+                token_address: Pubkey::new_from_array(chunk[..32].try_into().unwrap()),
+                basis_points: u16::from_le_bytes(chunk[32..].try_into().unwrap()),
+            })
+            .collect();
+        let is_native = match src[280] {
+            0 => false,
+            1 => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
         Ok(Self {
             destination_address,
             mint_address,
             is_initialized,
+            bump_seed,
+            time_basis,
+            approver,
+            pause_authority,
+            paused_at,
+            split_destinations,
+            is_native,
+            is_legacy: false,
         })
     }
 }
@@ -101,6 +371,123 @@ pub fn unpack_schedules(input: &[u8]) -> Result<Vec<VestingSchedule>, ProgramErr
     Ok(output)
 }
 
+/// A whole vesting account, the header followed by its schedules. Unlocked schedules stay in the
+/// account with a zero amount.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VestingContract {
+    pub header: VestingScheduleHeader,
+    pub schedules: Vec<VestingSchedule>,
+}
+
+impl VestingContract {
+    /// Decodes the data of a vesting account, which must hold a header and a whole number of schedules
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let header = VestingScheduleHeader::unpack_from_slice(input)?;
+        if (input.len() - header.packed_len()) % VestingSchedule::LEN != 0 {
+            return Err(ProgramError::InvalidAccountData)
+        }
+        let schedules = unpack_schedules(&input[header.packed_len()..])?;
+        Ok(Self { header, schedules })
+    }
+
+    /// Amount still held by the contract, none if the amounts of the schedules overflow
+    pub fn total_locked(&self) -> Option<u64> {
+        self.schedules
+            .iter()
+            .try_fold(0u64, |total, s| total.checked_add(s.amount))
+    }
+
+    /// Current time of the cluster in the time basis of the contract
+    pub fn current_time(&self, clock: &Clock) -> u64 {
+        self.header.time_basis.current_time(clock)
+    }
+
+    /// Amount held by the contract that an unlock at the given time would release, the time being
+    /// in the time basis of the contract. Nothing is released while the contract is paused.
+    pub fn vested_at(&self, current_time: u64) -> u64 {
+        if self.header.paused_at.is_some() {
+            return 0;
+        }
+        claimable_amounts(&self.schedules, current_time).claimable
+    }
+
+    /// Earliest schedule that still holds tokens, if any
+    pub fn next_release(&self) -> Option<&VestingSchedule> {
+        self.schedules
+            .iter()
+            .filter(|s| s.amount != 0)
+            .min_by_key(|s| s.release_time)
+    }
+
+    /// Whether every schedule has reached its release time at the given time, in the time basis
+    /// of the contract. Like `vested_at`, a paused contract is not vested until it is resumed.
+    pub fn is_fully_vested(&self, current_time: u64) -> bool {
+        self.header.paused_at.is_none()
+            && self.schedules.iter().all(|s| current_time >= s.release_time)
+    }
+}
+
+/// Amounts of a vesting contract at a given time, as returned by the `GetClaimable` instruction.
+/// Unlocking a schedule resets its amount to zero, so only the number of claimed schedules is known.
+#[derive(Debug, PartialEq)]
+pub struct ClaimableAmounts {
+    // Amount of the schedules that have reached their release time and were not unlocked yet
+    pub claimable: u64,
+    // Amount of the schedules that have not reached their release time
+    pub locked: u64,
+    // Number of schedules that have reached their release time and were already unlocked
+    pub claimed_schedules: u32,
+}
+
+impl Sealed for ClaimableAmounts {}
+
+impl Pack for ClaimableAmounts {
+    const LEN: usize = 20;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        dst[..8].copy_from_slice(&self.claimable.to_le_bytes());
+        dst[8..16].copy_from_slice(&self.locked.to_le_bytes());
+        dst[16..20].copy_from_slice(&self.claimed_schedules.to_le_bytes());
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < 20 {
+            return Err(ProgramError::InvalidAccountData)
+        }
+        let claimable = u64::from_le_bytes(src[0..8].try_into().unwrap());
+        let locked = u64::from_le_bytes(src[8..16].try_into().unwrap());
+        let claimed_schedules = u32::from_le_bytes(src[16..20].try_into().unwrap());
+        Ok(Self {
+            claimable,
+            locked,
+            claimed_schedules,
+        })
+    }
+}
+
+/// Computes the amounts of the schedules at the given time, in the time basis of their contract,
+/// with the same release rule as the unlock instruction. This can be used off-chain as well.
+pub fn claimable_amounts(schedules: &[VestingSchedule], current_time: u64) -> ClaimableAmounts {
+    let mut amounts = ClaimableAmounts {
+        claimable: 0,
+        locked: 0,
+        claimed_schedules: 0,
+    };
+    // The total amount of a contract is checked not to overflow when it is created
+    for s in schedules.iter() {
+        if current_time >= s.release_time {
+            if s.amount == 0 {
+                amounts.claimed_schedules += 1;
+            }
+            amounts.claimable += s.amount;
+        } else {
+            amounts.locked += s.amount;
+        }
+    }
+    amounts
+}
+
 pub fn pack_schedules_into_slice(schedules: Vec<VestingSchedule>, target: &mut [u8]) {
     let mut offset = 0;
     for s in schedules.iter() {
@@ -109,10 +496,294 @@ pub fn pack_schedules_into_slice(schedules: Vec<VestingSchedule>, target: &mut [
     }
 }
 
+/// Header of a treasury pool account. The pool funds the allocations of its beneficiaries from
+/// the associated token account of the pool account, whose balance never falls below the amount
+/// allocated and not claimed yet.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PoolHeader {
+    // Signer adding and removing the allocations of the pool
+    pub pool_authority: Pubkey,
+    pub mint_address: Pubkey,
+    pub is_initialized: bool,
+    pub bump_seed: u8,
+    // Clock value that the release times of the allocations are compared with
+    pub time_basis: TimeBasis,
+    // Amount of the allocations that was not claimed yet
+    pub allocated_amount: u64,
+}
+
+impl Sealed for PoolHeader {}
+
+impl Pack for PoolHeader {
+    const LEN: usize = 75;
+
+    fn pack_into_slice(&self, target: &mut [u8]) {
+        target[..32].copy_from_slice(&self.pool_authority.to_bytes());
+        target[32..64].copy_from_slice(&self.mint_address.to_bytes());
+        target[64] = AccountType::Pool.pack(self.is_initialized);
+        target[65] = self.bump_seed;
+        target[66] = self.time_basis as u8;
+        target[67..75].copy_from_slice(&self.allocated_amount.to_le_bytes());
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < 75 {
+            return Err(ProgramError::InvalidAccountData)
+        }
+        let is_initialized = AccountType::Pool.unpack_is_initialized(src[64])?;
+        let time_basis = TimeBasis::from_u8(src[66]).ok_or(ProgramError::InvalidAccountData)?;
+        Ok(Self {
+            pool_authority: Pubkey::new_from_array(src[..32].try_into().unwrap()),
+            mint_address: Pubkey::new_from_array(src[32..64].try_into().unwrap()),
+            is_initialized,
+            bump_seed: src[65],
+            time_basis,
+            allocated_amount: u64::from_le_bytes(src[67..75].try_into().unwrap()),
+        })
+    }
+}
+
+impl IsInitialized for PoolHeader {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+/// Header of the allocation account of a beneficiary of a pool, followed by the schedules of the
+/// allocation. Claimed schedules stay in the account with a zero amount.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AllocationHeader {
+    pub pool_address: Pubkey,
+    // Token account of the beneficiary, receiving the claims
+    pub destination_address: Pubkey,
+    pub is_initialized: bool,
+    pub bump_seed: u8,
+}
+
+impl Sealed for AllocationHeader {}
+
+impl Pack for AllocationHeader {
+    const LEN: usize = 66;
+
+    fn pack_into_slice(&self, target: &mut [u8]) {
+        target[..32].copy_from_slice(&self.pool_address.to_bytes());
+        target[32..64].copy_from_slice(&self.destination_address.to_bytes());
+        target[64] = AccountType::Allocation.pack(self.is_initialized);
+        target[65] = self.bump_seed;
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < 66 {
+            return Err(ProgramError::InvalidAccountData)
+        }
+        Ok(Self {
+            pool_address: Pubkey::new_from_array(src[..32].try_into().unwrap()),
+            destination_address: Pubkey::new_from_array(src[32..64].try_into().unwrap()),
+            is_initialized: AccountType::Allocation.unpack_is_initialized(src[64])?,
+            bump_seed: src[65],
+        })
+    }
+}
+
+impl IsInitialized for AllocationHeader {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+/// Finds the allocation account of a beneficiary of a pool, derived from the pool account and
+/// the destination token account of the beneficiary, with its bump seed
+pub fn find_allocation_address(
+    program_id: &Pubkey,
+    pool_address: &Pubkey,
+    destination_address: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[pool_address.as_ref(), destination_address.as_ref()], program_id)
+}
+
+/// A whole allocation account, the header followed by its schedules
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Allocation {
+    pub header: AllocationHeader,
+    pub schedules: Vec<VestingSchedule>,
+}
+
+impl Allocation {
+    /// Decodes the data of an allocation account, which must hold a header and a whole number of
+    /// schedules
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let header = AllocationHeader::unpack_from_slice(input)?;
+        if (input.len() - AllocationHeader::LEN) % VestingSchedule::LEN != 0 {
+            return Err(ProgramError::InvalidAccountData)
+        }
+        let schedules = unpack_schedules(&input[AllocationHeader::LEN..])?;
+        Ok(Self { header, schedules })
+    }
+
+    /// Amount of the allocation that was not claimed yet, none if the amounts of the schedules
+    /// overflow
+    pub fn total_locked(&self) -> Option<u64> {
+        self.schedules
+            .iter()
+            .try_fold(0u64, |total, s| total.checked_add(s.amount))
+    }
+}
+
+/// Header of a distributor account, releasing the allocations committed to by the root of a Merkle
+/// tree from the associated token account of the distributor account. Every allocation vests
+/// linearly from the start time to the end time, nothing being released before the cliff time.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DistributorHeader {
+    pub merkle_root: [u8; 32],
+    pub mint_address: Pubkey,
+    pub is_initialized: bool,
+    pub bump_seed: u8,
+    pub time_basis: TimeBasis,
+    pub start_time: u64,
+    pub cliff_time: u64,
+    pub end_time: u64,
+    // Sum of the allocations of the tree, the distributor never releasing more
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+}
+
+impl DistributorHeader {
+    /// Whether the cliff time lies between the start and end times
+    pub fn is_valid_period(&self) -> bool {
+        self.start_time <= self.cliff_time && self.cliff_time <= self.end_time
+    }
+
+    /// Part of an allocation of `amount` tokens vested at the given time, in the time basis of
+    /// the distributor
+    pub fn vested_amount(&self, amount: u64, current_time: u64) -> u64 {
+        if current_time < self.cliff_time {
+            0
+        } else if current_time >= self.end_time {
+            amount
+        } else {
+            // The end time is after the current time, hence after the start time
+            (amount as u128 * (current_time - self.start_time) as u128
+                / (self.end_time - self.start_time) as u128) as u64
+        }
+    }
+}
+
+impl Sealed for DistributorHeader {}
+
+impl Pack for DistributorHeader {
+    const LEN: usize = 107;
+
+    fn pack_into_slice(&self, target: &mut [u8]) {
+        target[..32].copy_from_slice(&self.merkle_root);
+        target[32..64].copy_from_slice(&self.mint_address.to_bytes());
+        target[64] = AccountType::Distributor.pack(self.is_initialized);
+        target[65] = self.bump_seed;
+        target[66] = self.time_basis as u8;
+        target[67..75].copy_from_slice(&self.start_time.to_le_bytes());
+        target[75..83].copy_from_slice(&self.cliff_time.to_le_bytes());
+        target[83..91].copy_from_slice(&self.end_time.to_le_bytes());
+        target[91..99].copy_from_slice(&self.total_amount.to_le_bytes());
+        target[99..107].copy_from_slice(&self.claimed_amount.to_le_bytes());
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < 107 {
+            return Err(ProgramError::InvalidAccountData)
+        }
+        let is_initialized = AccountType::Distributor.unpack_is_initialized(src[64])?;
+        let time_basis = TimeBasis::from_u8(src[66]).ok_or(ProgramError::InvalidAccountData)?;
+        Ok(Self {
+            merkle_root: src[..32].try_into().unwrap(),
+            mint_address: Pubkey::new_from_array(src[32..64].try_into().unwrap()),
+            is_initialized,
+            bump_seed: src[65],
+            time_basis,
+            start_time: u64::from_le_bytes(src[67..75].try_into().unwrap()),
+            cliff_time: u64::from_le_bytes(src[75..83].try_into().unwrap()),
+            end_time: u64::from_le_bytes(src[83..91].try_into().unwrap()),
+            total_amount: u64::from_le_bytes(src[91..99].try_into().unwrap()),
+            claimed_amount: u64::from_le_bytes(src[99..107].try_into().unwrap()),
+        })
+    }
+}
+
+impl IsInitialized for DistributorHeader {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+/// Allocation of a claimant of a distributor, created by its first claim once its Merkle proof is
+/// verified
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DistributionClaim {
+    pub distributor_address: Pubkey,
+    // Wallet signing the claims
+    pub claimant: Pubkey,
+    pub is_initialized: bool,
+    pub bump_seed: u8,
+    pub amount: u64,
+    pub claimed_amount: u64,
+}
+
+impl Sealed for DistributionClaim {}
+
+impl Pack for DistributionClaim {
+    const LEN: usize = 82;
+
+    fn pack_into_slice(&self, target: &mut [u8]) {
+        target[..32].copy_from_slice(&self.distributor_address.to_bytes());
+        target[32..64].copy_from_slice(&self.claimant.to_bytes());
+        target[64] = AccountType::DistributionClaim.pack(self.is_initialized);
+        target[65] = self.bump_seed;
+        target[66..74].copy_from_slice(&self.amount.to_le_bytes());
+        target[74..82].copy_from_slice(&self.claimed_amount.to_le_bytes());
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < 82 {
+            return Err(ProgramError::InvalidAccountData)
+        }
+        Ok(Self {
+            distributor_address: Pubkey::new_from_array(src[..32].try_into().unwrap()),
+            claimant: Pubkey::new_from_array(src[32..64].try_into().unwrap()),
+            is_initialized: AccountType::DistributionClaim.unpack_is_initialized(src[64])?,
+            bump_seed: src[65],
+            amount: u64::from_le_bytes(src[66..74].try_into().unwrap()),
+            claimed_amount: u64::from_le_bytes(src[74..82].try_into().unwrap()),
+        })
+    }
+}
+
+impl IsInitialized for DistributionClaim {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+/// Finds the claim account of a claimant of a distributor, derived from the distributor account
+/// and the claimant wallet, with its bump seed
+pub fn find_distribution_claim_address(
+    program_id: &Pubkey,
+    distributor_address: &Pubkey,
+    claimant: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[distributor_address.as_ref(), claimant.as_ref()], program_id)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{unpack_schedules, VestingSchedule, VestingScheduleHeader};
-    use solana_program::{program_pack::Pack, pubkey::Pubkey};
+    use super::{
+        are_valid_splits, AccountType, claimable_amounts, unpack_schedules, Allocation, AllocationHeader,
+        ClaimableAmounts, DestinationSplit, DistributionClaim, DistributorHeader, PoolHeader,
+        TimeBasis, VestingContract, VestingSchedule, VestingScheduleHeader,
+    };
+    use solana_program::{clock::Clock, program_pack::Pack, pubkey::Pubkey};
 
     #[test]
     fn test_state_packing() {
@@ -120,6 +791,17 @@ mod tests {
             destination_address: Pubkey::new_unique(),
             mint_address: Pubkey::new_unique(),
             is_initialized: true,
+            bump_seed: 254,
+            time_basis: TimeBasis::Epoch,
+            approver: Some(Pubkey::new_unique()),
+            pause_authority: Some(Pubkey::new_unique()),
+            paused_at: Some(1609459200),
+            split_destinations: vec![DestinationSplit {
+                token_address: Pubkey::new_unique(),
+                basis_points: 3000,
+            }],
+            is_native: true,
+            is_legacy: false,
         };
         let schedule_state_0 = VestingSchedule {
             release_time: 30767976,
@@ -130,7 +812,7 @@ mod tests {
             amount: 420,
         };
         let state_size = VestingScheduleHeader::LEN + 2 * VestingSchedule::LEN;
-        let mut state_array = [0u8; 97];
+        let mut state_array = [0u8; 313];
         header_state.pack_into_slice(&mut state_array[..VestingScheduleHeader::LEN]);
         schedule_state_0.pack_into_slice(
             &mut state_array
@@ -142,7 +824,21 @@ mod tests {
         let mut expected = Vec::with_capacity(state_size);
         expected.extend_from_slice(&header_state.destination_address.to_bytes());
         expected.extend_from_slice(&header_state.mint_address.to_bytes());
-        expected.extend_from_slice(&[header_state.is_initialized as u8]);
+        expected.push(2);
+        expected.push(VestingScheduleHeader::LAYOUT_VERSION);
+        expected.push(header_state.bump_seed);
+        expected.push(2);
+        expected.push(1);
+        expected.extend_from_slice(&header_state.approver.unwrap().to_bytes());
+        expected.push(1);
+        expected.extend_from_slice(&header_state.pause_authority.unwrap().to_bytes());
+        expected.push(1);
+        expected.extend_from_slice(&1609459200u64.to_le_bytes());
+        expected.push(1);
+        expected.extend_from_slice(&header_state.split_destinations[0].token_address.to_bytes());
+        expected.extend_from_slice(&3000u16.to_le_bytes());
+        expected.extend_from_slice(&[0u8; 3 * 34]);
+        expected.push(1);
         expected.extend_from_slice(&schedule_state_0.release_time.to_le_bytes());
         expected.extend_from_slice(&schedule_state_0.amount.to_le_bytes());
         expected.extend_from_slice(&schedule_state_1.release_time.to_le_bytes());
@@ -157,4 +853,333 @@ mod tests {
         assert_eq!(unpacked_schedules[0], schedule_state_0);
         assert_eq!(unpacked_schedules[1], schedule_state_1);
     }
+
+    #[test]
+    fn test_claimable_amounts() {
+        let schedules = vec![
+            VestingSchedule {
+                release_time: 10,
+                amount: 0,
+            },
+            VestingSchedule {
+                release_time: 20,
+                amount: 42,
+            },
+            VestingSchedule {
+                release_time: 30,
+                amount: 69,
+            },
+        ];
+        let amounts = claimable_amounts(&schedules, 25);
+        assert_eq!(
+            amounts,
+            ClaimableAmounts {
+                claimable: 42,
+                locked: 69,
+                claimed_schedules: 1,
+            }
+        );
+        assert_eq!(claimable_amounts(&schedules, 5).locked, 111);
+        assert_eq!(claimable_amounts(&schedules, 30).claimable, 111);
+
+        let mut packed = [0u8; ClaimableAmounts::LEN];
+        amounts.pack_into_slice(&mut packed);
+        assert_eq!(ClaimableAmounts::unpack_from_slice(&packed).unwrap(), amounts);
+    }
+
+    #[test]
+    fn test_vesting_contract() {
+        let header = VestingScheduleHeader {
+            destination_address: Pubkey::new_unique(),
+            mint_address: Pubkey::new_unique(),
+            is_initialized: true,
+            bump_seed: 254,
+            time_basis: TimeBasis::Slot,
+            approver: None,
+            pause_authority: None,
+            paused_at: None,
+            split_destinations: vec![],
+            is_native: false,
+            is_legacy: false,
+        };
+        let schedules = [
+            VestingSchedule {
+                release_time: 10,
+                amount: 0,
+            },
+            VestingSchedule {
+                release_time: 30,
+                amount: 69,
+            },
+            VestingSchedule {
+                release_time: 20,
+                amount: 42,
+            },
+        ];
+        let mut packed = vec![0u8; VestingScheduleHeader::LEN + 3 * VestingSchedule::LEN];
+        header.pack_into_slice(&mut packed[..VestingScheduleHeader::LEN]);
+        for (i, s) in schedules.iter().enumerate() {
+            let offset = VestingScheduleHeader::LEN + i * VestingSchedule::LEN;
+            s.pack_into_slice(&mut packed[offset..offset + VestingSchedule::LEN]);
+        }
+
+        let mut contract = VestingContract::unpack(&packed).unwrap();
+        assert_eq!(contract.header, header);
+        assert_eq!(contract.total_locked(), Some(111));
+        assert_eq!(contract.vested_at(25), 42);
+        assert_eq!(contract.next_release(), Some(&schedules[2]));
+        assert!(!contract.is_fully_vested(25));
+        assert!(contract.is_fully_vested(30));
+        contract.header.paused_at = Some(20);
+        assert_eq!(contract.vested_at(25), 0);
+        assert!(!contract.is_fully_vested(30));
+        contract.schedules[0].amount = u64::MAX;
+        assert_eq!(contract.total_locked(), None);
+
+        let clock = Clock {
+            slot: 25,
+            epoch: 3,
+            unix_timestamp: 1609459200,
+            ..Clock::default()
+        };
+        assert_eq!(contract.current_time(&clock), 25);
+        assert_eq!(TimeBasis::Epoch.current_time(&clock), 3);
+        assert_eq!(TimeBasis::UnixTimestamp.current_time(&clock), 1609459200);
+
+        assert!(VestingContract::unpack(&packed[..packed.len() - 1]).is_err());
+        assert!(VestingContract::unpack(&packed[..VestingScheduleHeader::LEN - 1]).is_err());
+
+        // Unknown layout versions, time bases and invalid option flags are rejected
+        let mut unknown_version = packed.clone();
+        unknown_version[65] = VestingScheduleHeader::LAYOUT_VERSION + 1;
+        assert!(VestingContract::unpack(&unknown_version).is_err());
+        let mut invalid_time_basis = packed.clone();
+        invalid_time_basis[67] = 3;
+        assert!(VestingContract::unpack(&invalid_time_basis).is_err());
+        let mut invalid_approver = packed.clone();
+        invalid_approver[68] = 2;
+        assert!(VestingContract::unpack(&invalid_approver).is_err());
+        let mut invalid_pause = packed.clone();
+        invalid_pause[134] = 2;
+        assert!(VestingContract::unpack(&invalid_pause).is_err());
+        let mut invalid_native = packed.clone();
+        invalid_native[280] = 2;
+        assert!(VestingContract::unpack(&invalid_native).is_err());
+        packed[143] = 5;
+        assert!(VestingContract::unpack(&packed).is_err());
+    }
+
+    #[test]
+    fn test_legacy_contract() {
+        // Destination address, mint address and initialized flag, followed by the schedules
+        let destination_address = Pubkey::new_unique();
+        let mint_address = Pubkey::new_unique();
+        let mut packed = Vec::new();
+        packed.extend_from_slice(&destination_address.to_bytes());
+        packed.extend_from_slice(&mint_address.to_bytes());
+        packed.push(1);
+        packed.extend_from_slice(&20u64.to_le_bytes());
+        packed.extend_from_slice(&42u64.to_le_bytes());
+
+        let contract = VestingContract::unpack(&packed).unwrap();
+        assert!(contract.header.is_legacy);
+        assert!(contract.header.is_initialized);
+        assert_eq!(contract.header.destination_address, destination_address);
+        assert_eq!(contract.header.time_basis, TimeBasis::UnixTimestamp);
+        assert_eq!(
+            contract.header.packed_len(),
+            VestingScheduleHeader::LEGACY_LEN
+        );
+        assert_eq!(
+            contract.schedules,
+            vec![VestingSchedule {
+                release_time: 20,
+                amount: 42,
+            }]
+        );
+
+        // Legacy headers are written back in the legacy layout
+        let mut repacked = packed.clone();
+        repacked[..VestingScheduleHeader::LEGACY_LEN].fill(0);
+        contract.header.pack_into_slice(&mut repacked);
+        assert_eq!(repacked, packed);
+
+        assert!(VestingContract::unpack(&packed[..packed.len() - 1]).is_err());
+
+        // Accounts initialized by earlier versions of the program are zeroed
+        let zeroed = [0u8; VestingScheduleHeader::LEGACY_LEN + VestingSchedule::LEN];
+        let header = VestingScheduleHeader::unpack_from_slice(&zeroed).unwrap();
+        assert!(header.is_legacy);
+        assert!(!header.is_initialized);
+        assert!(header.fits_legacy_layout());
+    }
+
+    #[test]
+    fn test_destination_splits() {
+        let split = |basis_points| DestinationSplit {
+            token_address: Pubkey::new_unique(),
+            basis_points,
+        };
+        assert_eq!(split(3000).share_of(1001), 300);
+        assert_eq!(split(10_000).share_of(u64::MAX), u64::MAX);
+        assert_eq!(split(1).share_of(9_999), 0);
+
+        assert!(are_valid_splits(&[]));
+        assert!(are_valid_splits(&[split(2500), split(7500)]));
+        assert!(!are_valid_splits(&[split(2500), split(7501)]));
+        assert!(!are_valid_splits(&[split(0)]));
+        assert!(!are_valid_splits(&[split(1), split(1), split(1), split(1), split(1)]));
+    }
+
+    #[test]
+    fn test_pool_packing() {
+        let pool = PoolHeader {
+            pool_authority: Pubkey::new_unique(),
+            mint_address: Pubkey::new_unique(),
+            is_initialized: true,
+            bump_seed: 253,
+            time_basis: TimeBasis::Slot,
+            allocated_amount: 1_000_000,
+        };
+        let mut packed = [0u8; PoolHeader::LEN];
+        pool.pack_into_slice(&mut packed);
+        let mut expected = Vec::with_capacity(PoolHeader::LEN);
+        expected.extend_from_slice(&pool.pool_authority.to_bytes());
+        expected.extend_from_slice(&pool.mint_address.to_bytes());
+        expected.extend_from_slice(&[3, 253, 1]);
+        expected.extend_from_slice(&1_000_000u64.to_le_bytes());
+        assert_eq!(packed[..], expected[..]);
+        assert_eq!(PoolHeader::unpack(&packed).unwrap(), pool);
+
+        let header = AllocationHeader {
+            pool_address: Pubkey::new_unique(),
+            destination_address: Pubkey::new_unique(),
+            is_initialized: true,
+            bump_seed: 255,
+        };
+        let schedule = VestingSchedule {
+            release_time: 20,
+            amount: 42,
+        };
+        let mut packed = vec![0u8; AllocationHeader::LEN + VestingSchedule::LEN];
+        header.pack_into_slice(&mut packed[..AllocationHeader::LEN]);
+        schedule.pack_into_slice(&mut packed[AllocationHeader::LEN..]);
+        assert_eq!(packed[64..66], [4, 255]);
+        let allocation = Allocation::unpack(&packed).unwrap();
+        assert_eq!(allocation.header, header);
+        assert_eq!(allocation.schedules, vec![schedule]);
+        assert_eq!(allocation.total_locked(), Some(42));
+        assert!(Allocation::unpack(&packed[..packed.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_distributor_packing() {
+        let distributor = DistributorHeader {
+            merkle_root: [7; 32],
+            mint_address: Pubkey::new_unique(),
+            is_initialized: true,
+            bump_seed: 254,
+            time_basis: TimeBasis::UnixTimestamp,
+            start_time: 100,
+            cliff_time: 150,
+            end_time: 300,
+            total_amount: 1_000,
+            claimed_amount: 10,
+        };
+        let mut packed = [0u8; DistributorHeader::LEN];
+        distributor.pack_into_slice(&mut packed);
+        assert_eq!(packed[..32], [7; 32]);
+        assert_eq!(packed[64..67], [5, 254, 0]);
+        assert_eq!(packed[99..], 10u64.to_le_bytes());
+        assert_eq!(DistributorHeader::unpack(&packed).unwrap(), distributor);
+        assert!(distributor.is_valid_period());
+
+        // Nothing before the cliff, then linearly from the start time
+        assert_eq!(distributor.vested_amount(400, 149), 0);
+        assert_eq!(distributor.vested_amount(400, 150), 100);
+        assert_eq!(distributor.vested_amount(400, 250), 300);
+        assert_eq!(distributor.vested_amount(400, 300), 400);
+        assert_eq!(distributor.vested_amount(u64::MAX, 200), u64::MAX / 2);
+
+        // Everything at once when the period is empty
+        let instant = DistributorHeader {
+            start_time: 200,
+            cliff_time: 200,
+            end_time: 200,
+            ..distributor
+        };
+        assert_eq!(instant.vested_amount(400, 199), 0);
+        assert_eq!(instant.vested_amount(400, 200), 400);
+        let cliff_after_end = DistributorHeader {
+            cliff_time: 400,
+            ..instant
+        };
+        assert!(!cliff_after_end.is_valid_period());
+
+        let claim = DistributionClaim {
+            distributor_address: Pubkey::new_unique(),
+            claimant: Pubkey::new_unique(),
+            is_initialized: true,
+            bump_seed: 255,
+            amount: 400,
+            claimed_amount: 100,
+        };
+        let mut packed = [0u8; DistributionClaim::LEN];
+        claim.pack_into_slice(&mut packed);
+        assert_eq!(packed[64..66], [6, 255]);
+        assert_eq!(DistributionClaim::unpack(&packed).unwrap(), claim);
+
+        // A claim has the size of an allocation with a single schedule, only its type tells them
+        // apart
+        assert_eq!(
+            DistributionClaim::LEN,
+            AllocationHeader::LEN + VestingSchedule::LEN
+        );
+        assert!(Allocation::unpack(&packed).is_err());
+    }
+
+    #[test]
+    fn test_account_types() {
+        let account_types = [
+            AccountType::Uninitialized,
+            AccountType::LegacyVesting,
+            AccountType::Vesting,
+            AccountType::Pool,
+            AccountType::Allocation,
+            AccountType::Distributor,
+            AccountType::DistributionClaim,
+        ];
+        for account_type in account_types {
+            let mut data = [0u8; VestingScheduleHeader::LEN];
+            data[64] = account_type as u8;
+            data[65] = VestingScheduleHeader::LAYOUT_VERSION;
+            // Every decoder accepts the accounts of its type and the uninitialized ones only
+            let is_decoded_as = |decoded_type: AccountType| {
+                account_type == decoded_type || account_type == AccountType::Uninitialized
+            };
+            assert_eq!(
+                VestingScheduleHeader::unpack_from_slice(&data).is_ok(),
+                is_decoded_as(AccountType::Vesting) || account_type == AccountType::LegacyVesting
+            );
+            assert_eq!(
+                PoolHeader::unpack_from_slice(&data).is_ok(),
+                is_decoded_as(AccountType::Pool)
+            );
+            assert_eq!(
+                AllocationHeader::unpack_from_slice(&data).is_ok(),
+                is_decoded_as(AccountType::Allocation)
+            );
+            assert_eq!(
+                DistributorHeader::unpack_from_slice(&data).is_ok(),
+                is_decoded_as(AccountType::Distributor)
+            );
+            assert_eq!(
+                DistributionClaim::unpack_from_slice(&data).is_ok(),
+                is_decoded_as(AccountType::DistributionClaim)
+            );
+        }
+        let mut unknown_type = [0u8; VestingScheduleHeader::LEN];
+        unknown_type[64] = 7;
+        assert!(PoolHeader::unpack_from_slice(&unknown_type).is_err());
+    }
 }
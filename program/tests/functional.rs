@@ -1,17 +1,19 @@
 #![cfg(feature = "test-bpf")]
 use std::str::FromStr;
 
-use solana_program::{hash::Hash,
+use solana_program::{clock::Clock,
+    hash::Hash,
     pubkey::Pubkey,
     rent::Rent,
     sysvar,
     system_program
 };
-use solana_program_test::{processor, ProgramTest};
-use solana_sdk::{account::Account, keyed_account, signature::Keypair, signature::Signer, system_instruction, transaction::Transaction};
-use token_vesting::{entrypoint::process_instruction, instruction::Schedule};
-use token_vesting::instruction::{init, unlock, change_destination, create};
-use spl_token::{self, instruction::{initialize_mint, initialize_account, mint_to}};
+use solana_program_test::{processor, BanksClient, ProgramTest, ProgramTestBanksClientExt, ProgramTestContext};
+use solana_sdk::{account::{Account, AccountSharedData}, instruction::InstructionError, program_pack::Pack, signature::Keypair, signature::Signer, system_instruction, transaction::{Transaction, TransactionError}};
+use token_vesting::{entrypoint::process_instruction, error::VestingError, events::VestingEvent, instruction::Schedule, merkle::{leaf_hash, tree_levels, tree_proof, tree_root}, state::{find_allocation_address, find_distribution_claim_address, ClaimableAmounts, DestinationSplit, DistributionClaim, DistributorHeader, PoolHeader, TimeBasis, VestingContract, VestingSchedule, VestingScheduleHeader}};
+use token_vesting::instruction::{init, unlock, change_destination, change_split_destination, create, create_milestones, create_native, create_pausable, create_with_splits, create_with_time_basis, pause, release_milestone, resume, unlock_with_associated_destination, get_claimable, unlock_native, with_split_destinations, init_pool, add_allocation, remove_allocation, claim_allocation, init_distributor, claim_distribution};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{self, instruction::{initialize_mint, initialize_account, mint_to}, state::{Account as TokenAccount, AccountState, Mint}};
 
 #[tokio::test]
 async fn test_token_vesting() {
@@ -53,6 +55,33 @@ async fn test_token_vesting() {
     // Start and process transactions on the test network
     let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
 
+    // A non canonical bump seed derives another vesting account from the same seed
+    let mut non_canonical_seeds = seeds;
+    let non_canonical_vesting_account_key = (0..bump).rev().find_map(|non_canonical_bump| {
+        non_canonical_seeds[31] = non_canonical_bump;
+        Pubkey::create_program_address(&[&non_canonical_seeds], &program_id).ok()
+    }).unwrap();
+    let mut non_canonical_init_transaction = Transaction::new_with_payer(
+        &[init(
+            &system_program::id(),
+            &sysvar::rent::id(),
+            &program_id,
+            &payer.pubkey(),
+            &non_canonical_vesting_account_key,
+            non_canonical_seeds,
+            3
+        ).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    non_canonical_init_transaction.partial_sign(
+        &[&payer],
+        recent_blockhash
+    );
+    assert_eq!(
+        banks_client.process_transaction(non_canonical_init_transaction).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(VestingError::NonCanonicalBumpSeed as u32))
+    );
+
     // Initialize the vesting program account
     let init_instruction = [init(
         &system_program::id(),
@@ -125,7 +154,7 @@ async fn test_token_vesting() {
             &source_token_account.pubkey(),
             &destination_token_account.pubkey(),
             &mint.pubkey(),
-            schedules,
+            schedules.clone(),
             seeds.clone()
         ).unwrap(),
         unlock(
@@ -178,7 +207,31 @@ async fn test_token_vesting() {
         recent_blockhash
     );
     
-    banks_client.process_transaction(test_transaction).await.unwrap();
+
+    let test_events = process_transaction_events(&mut banks_client, &program_id, test_transaction).await;
+    let unix_timestamp = match test_events[..] {
+        [VestingEvent::Created {unix_timestamp, ..}, ..] => unix_timestamp,
+        _ => panic!("The creation event was not logged")
+    };
+    assert_eq!(
+        test_events,
+        vec![
+            VestingEvent::Created {
+                vesting_account: vesting_account_key,
+                mint_address: mint.pubkey(),
+                destination_token_address: destination_token_account.pubkey(),
+                total_amount: 60,
+                schedules,
+                unix_timestamp
+            },
+            VestingEvent::Unlocked {
+                vesting_account: vesting_account_key,
+                destination_token_address: destination_token_account.pubkey(),
+                amount: 60,
+                unix_timestamp
+            }
+        ]
+    );
     
     let mut change_destination_transaction = Transaction::new_with_payer(
         &change_destination_instructions, 
@@ -193,10 +246,1568 @@ async fn test_token_vesting() {
         recent_blockhash
     );
 
-    banks_client.process_transaction(change_destination_transaction).await.unwrap();
+    assert_eq!(
+        process_transaction_events(&mut banks_client, &program_id, change_destination_transaction).await,
+        vec![VestingEvent::DestinationChanged {
+            vesting_account: vesting_account_key,
+            old_destination_token_address: destination_token_account.pubkey(),
+            new_destination_token_address: new_destination_token_account.pubkey(),
+            unix_timestamp
+        }]
+    );
+
+    // The vesting account is modified by the instruction and has to be passed as writable
+    let mut read_only_vesting_instruction = change_destination(
+        &program_id,
+        &vesting_account_key,
+        &destination_account.pubkey(),
+        &new_destination_token_account.pubkey(),
+        &destination_token_account.pubkey(),
+        seeds
+    ).unwrap();
+    read_only_vesting_instruction.accounts[0].is_writable = false;
+    let mut read_only_vesting_transaction = Transaction::new_with_payer(
+        &[read_only_vesting_instruction],
+        Some(&payer.pubkey())
+    );
+    read_only_vesting_transaction.partial_sign(
+        &[
+            &payer,
+            &destination_account
+        ],
+        recent_blockhash
+    );
+    assert_eq!(
+        banks_client.process_transaction(read_only_vesting_transaction).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(VestingError::AccountNotWritable as u32))
+    );
     
 }
 
+#[tokio::test]
+async fn test_legacy_vesting_account() {
+    let program_id = Pubkey::from_str("VestingbGKPFXCWuBvfkegQfZyiNwAJb9Ss623VQ5DA").unwrap();
+    let mint = Pubkey::new_unique();
+    let destination_token_account = Pubkey::new_unique();
+    let vesting_token_account = Pubkey::new_unique();
+
+    let mut canonical_seeds = [42u8; 32];
+    let (_, bump) = Pubkey::find_program_address(&[&canonical_seeds[..31]], &program_id);
+    canonical_seeds[31] = bump;
+    // Legacy clients did not always end the seeds with the canonical bump seed
+    let non_canonical_seeds = (0..bump)
+        .rev()
+        .map(|bump| {
+            let mut seeds = canonical_seeds;
+            seeds[31] = bump;
+            seeds
+        })
+        .find(|seeds| Pubkey::create_program_address(&[seeds], &program_id).is_ok())
+        .unwrap();
+
+    for seeds in [canonical_seeds, non_canonical_seeds] {
+        let vesting_account_key = Pubkey::create_program_address(&[&seeds], &program_id).unwrap();
+
+        // Contract written before the layout of headers was versioned: destination address, mint
+        // address and initialized flag, followed by a matured and a locked schedule
+        let mut legacy_data = Vec::new();
+        legacy_data.extend_from_slice(&destination_token_account.to_bytes());
+        legacy_data.extend_from_slice(&mint.to_bytes());
+        legacy_data.push(1);
+        for (release_time, amount) in [(0u64, 60u64), (u64::MAX, 40)] {
+            legacy_data.extend_from_slice(&release_time.to_le_bytes());
+            legacy_data.extend_from_slice(&amount.to_le_bytes());
+        }
+
+        let mut program_test = ProgramTest::new(
+            "token_vesting",
+            program_id,
+            processor!(process_instruction),
+        );
+        program_test.add_account(
+            vesting_account_key,
+            Account {
+                lamports: Rent::default().minimum_balance(legacy_data.len()),
+                data: legacy_data.clone(),
+                owner: program_id,
+                ..Account::default()
+            },
+        );
+        program_test.add_account(
+            mint,
+            token_program_account(Mint {
+                supply: 100,
+                is_initialized: true,
+                ..Mint::default()
+            }),
+        );
+        for (token_account, owner, amount) in [
+            (vesting_token_account, vesting_account_key, 100),
+            (destination_token_account, Pubkey::new_unique(), 0),
+        ] {
+            program_test.add_account(
+                token_account,
+                token_program_account(TokenAccount {
+                    mint,
+                    owner,
+                    amount,
+                    state: AccountState::Initialized,
+                    ..TokenAccount::default()
+                }),
+            );
+        }
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let unlock_instruction = unlock(
+            &program_id,
+            &spl_token::id(),
+            &sysvar::clock::id(),
+            &vesting_account_key,
+            &vesting_token_account,
+            &destination_token_account,
+            seeds
+        ).unwrap();
+        let mut unlock_transaction = Transaction::new_with_payer(
+            &[unlock_instruction],
+            Some(&payer.pubkey()),
+        );
+        unlock_transaction.partial_sign(&[&payer], recent_blockhash);
+        banks_client.process_transaction(unlock_transaction).await.unwrap();
+
+        let destination = banks_client.get_account(destination_token_account).await.unwrap().unwrap();
+        assert_eq!(TokenAccount::unpack(&destination.data).unwrap().amount, 60);
+
+        // The released schedule is zeroed in place, the header keeping its legacy layout
+        let vesting_account = banks_client.get_account(vesting_account_key).await.unwrap().unwrap();
+        assert_eq!(vesting_account.data[..65], legacy_data[..65]);
+        let contract = VestingContract::unpack(&vesting_account.data).unwrap();
+        assert!(contract.header.is_legacy);
+        assert_eq!(contract.total_locked(), Some(40));
+        assert_eq!(contract.schedules[0].amount, 0);
+    }
+}
+
+#[tokio::test]
+async fn test_create_in_account_initialized_by_earlier_version() {
+    let TestFixture {
+        mut context,
+        program_id,
+        payer,
+        recent_blockhash,
+        mint,
+        source_account,
+        source_token_account,
+        ..
+    } = setup_fixture(&[]).await;
+
+    let mut seeds = [42u8; 32];
+    let (vesting_account_key, bump) = Pubkey::find_program_address(&[&seeds[..31]], &program_id);
+    seeds[31] = bump;
+    let vesting_token_account = Keypair::new();
+    let destination_token_account = Keypair::new();
+    for (token_account, owner) in [
+        (&vesting_token_account, vesting_account_key),
+        (&destination_token_account, Pubkey::new_unique()),
+    ] {
+        context.banks_client.process_transaction(
+            create_token_account(&payer, &mint, recent_blockhash, token_account, &owner)
+        ).await.unwrap();
+    }
+
+    // Earlier versions of `Init` left a zeroed account with room for a legacy header
+    let initialized_data = vec![0u8; VestingScheduleHeader::LEGACY_LEN + VestingSchedule::LEN];
+    context.set_account(
+        &vesting_account_key,
+        &AccountSharedData::from(Account {
+            lamports: Rent::default().minimum_balance(initialized_data.len()),
+            data: initialized_data,
+            owner: program_id,
+            ..Account::default()
+        }),
+    );
+    let schedules = vec![Schedule {amount: 42, release_time: 0}];
+
+    // The options of a contract do not fit in a legacy header
+    let mut create_with_time_basis_transaction = Transaction::new_with_payer(
+        &[create_with_time_basis(
+            &program_id,
+            &spl_token::id(),
+            &vesting_account_key,
+            &vesting_token_account.pubkey(),
+            &source_account.pubkey(),
+            &source_token_account.pubkey(),
+            &destination_token_account.pubkey(),
+            &mint.pubkey(),
+            TimeBasis::Slot,
+            schedules.clone(),
+            seeds
+        ).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    create_with_time_basis_transaction.partial_sign(&[&payer, &source_account], recent_blockhash);
+    assert_eq!(
+        context.banks_client.process_transaction(create_with_time_basis_transaction).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+
+    let mut create_transaction = Transaction::new_with_payer(
+        &[create(
+            &program_id,
+            &spl_token::id(),
+            &vesting_account_key,
+            &vesting_token_account.pubkey(),
+            &source_account.pubkey(),
+            &source_token_account.pubkey(),
+            &destination_token_account.pubkey(),
+            &mint.pubkey(),
+            schedules,
+            seeds
+        ).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    create_transaction.partial_sign(&[&payer, &source_account], recent_blockhash);
+    context.banks_client.process_transaction(create_transaction).await.unwrap();
+
+    // The contract is written in the legacy layout and unlocked as a legacy contract
+    let vesting_account = context.banks_client.get_account(vesting_account_key).await.unwrap().unwrap();
+    let contract = VestingContract::unpack(&vesting_account.data).unwrap();
+    assert!(contract.header.is_legacy);
+    assert_eq!(contract.header.destination_address, destination_token_account.pubkey());
+    assert_eq!(contract.total_locked(), Some(42));
+
+    let mut unlock_transaction = Transaction::new_with_payer(
+        &[unlock(
+            &program_id,
+            &spl_token::id(),
+            &sysvar::clock::id(),
+            &vesting_account_key,
+            &vesting_token_account.pubkey(),
+            &destination_token_account.pubkey(),
+            seeds
+        ).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    unlock_transaction.partial_sign(&[&payer], recent_blockhash);
+    context.banks_client.process_transaction(unlock_transaction).await.unwrap();
+    let destination = context.banks_client.get_account(destination_token_account.pubkey()).await.unwrap().unwrap();
+    assert_eq!(TokenAccount::unpack(&destination.data).unwrap().amount, 42);
+}
+
+#[tokio::test]
+async fn test_unlock_with_associated_destination() {
+
+    // Create program and test environment
+    let TestFixture {
+        mut context,
+        program_id,
+        payer,
+        recent_blockhash,
+        mint,
+        source_account,
+        source_token_account,
+        ..
+    } = setup_fixture(&[]).await;
+
+    // The destination associated token account does not exist yet
+    let destination_account = Keypair::new();
+    let destination_token_account_key = get_associated_token_address(
+        &destination_account.pubkey(),
+        &mint.pubkey()
+    );
+
+    let mut seeds = [42u8; 32];
+    let (vesting_account_key, bump) = Pubkey::find_program_address(&[&seeds[..31]], &program_id);
+    seeds[31] = bump;
+    let vesting_token_account = Keypair::new();
+
+    context.banks_client.process_transaction(
+        create_token_account(&payer, &mint, recent_blockhash, &vesting_token_account, &vesting_account_key)
+    ).await.unwrap();
+
+    let setup_instructions = [
+        init(
+            &system_program::id(),
+            &sysvar::rent::id(),
+            &program_id,
+            &payer.pubkey(),
+            &vesting_account_key,
+            seeds,
+            2
+        ).unwrap(),
+        create(
+            &program_id,
+            &spl_token::id(),
+            &vesting_account_key,
+            &vesting_token_account.pubkey(),
+            &source_account.pubkey(),
+            &source_token_account.pubkey(),
+            &destination_token_account_key,
+            &mint.pubkey(),
+            vec![
+                Schedule {amount: 20, release_time: 0},
+                Schedule {amount: 20, release_time: u64::MAX}
+            ],
+            seeds
+        ).unwrap()
+    ];
+    let mut setup_transaction = Transaction::new_with_payer(
+        &setup_instructions,
+        Some(&payer.pubkey()),
+    );
+    setup_transaction.partial_sign(
+        &[
+            &payer,
+            &source_account
+            ],
+        recent_blockhash
+    );
+    context.banks_client.process_transaction(setup_transaction).await.unwrap();
+
+    assert_eq!(
+        get_claimable_amounts(&mut context.banks_client, &program_id, &payer, &vesting_account_key, recent_blockhash).await,
+        ClaimableAmounts {claimable: 20, locked: 20, claimed_schedules: 0}
+    );
+
+    let unlock_instructions = [
+        unlock_with_associated_destination(
+            &program_id,
+            &spl_token::id(),
+            &sysvar::clock::id(),
+            &system_program::id(),
+            &spl_associated_token_account::id(),
+            &vesting_account_key,
+            &vesting_token_account.pubkey(),
+            &destination_account.pubkey(),
+            &mint.pubkey(),
+            &payer.pubkey(),
+            seeds
+        ).unwrap()
+    ];
+    let mut unlock_transaction = Transaction::new_with_payer(
+        &unlock_instructions,
+        Some(&payer.pubkey()),
+    );
+    unlock_transaction.partial_sign(
+        &[&payer],
+        recent_blockhash
+    );
+    context.banks_client.process_transaction(unlock_transaction).await.unwrap();
+
+    let destination_token_account = context.banks_client
+        .get_account(destination_token_account_key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        spl_token::state::Account::unpack(&destination_token_account.data).unwrap().amount,
+        20
+    );
+
+    assert_eq!(
+        get_claimable_amounts(&mut context.banks_client, &program_id, &payer, &vesting_account_key, recent_blockhash).await,
+        ClaimableAmounts {claimable: 0, locked: 20, claimed_schedules: 1}
+    );
+}
+
+#[tokio::test]
+async fn test_slot_time_basis() {
+
+    // Create program and test environment
+    let TestFixture {
+        mut context,
+        program_id,
+        payer,
+        recent_blockhash,
+        mint,
+        source_account,
+        source_token_account,
+        ..
+    } = setup_fixture(&[]).await;
+
+    let destination_account = Keypair::new();
+
+    let mut seeds = [42u8; 32];
+    let (vesting_account_key, bump) = Pubkey::find_program_address(&[&seeds[..31]], &program_id);
+    seeds[31] = bump;
+    let vesting_token_account = Keypair::new();
+
+    context.banks_client.process_transaction(
+        create_token_account(&payer, &mint, recent_blockhash, &vesting_token_account, &vesting_account_key)
+    ).await.unwrap();
+
+    // The release times are slots, far below the unix timestamp of the test cluster
+    let setup_instructions = [
+        init(
+            &system_program::id(),
+            &sysvar::rent::id(),
+            &program_id,
+            &payer.pubkey(),
+            &vesting_account_key,
+            seeds,
+            2
+        ).unwrap(),
+        create_with_time_basis(
+            &program_id,
+            &spl_token::id(),
+            &vesting_account_key,
+            &vesting_token_account.pubkey(),
+            &source_account.pubkey(),
+            &source_token_account.pubkey(),
+            &get_associated_token_address(&destination_account.pubkey(), &mint.pubkey()),
+            &mint.pubkey(),
+            TimeBasis::Slot,
+            vec![
+                Schedule {amount: 20, release_time: 0},
+                Schedule {amount: 20, release_time: 1000}
+            ],
+            seeds
+        ).unwrap()
+    ];
+    let mut setup_transaction = Transaction::new_with_payer(
+        &setup_instructions,
+        Some(&payer.pubkey()),
+    );
+    setup_transaction.partial_sign(
+        &[
+            &payer,
+            &source_account
+            ],
+        recent_blockhash
+    );
+    context.banks_client.process_transaction(setup_transaction).await.unwrap();
+
+    assert_eq!(
+        get_claimable_amounts(&mut context.banks_client, &program_id, &payer, &vesting_account_key, recent_blockhash).await,
+        ClaimableAmounts {claimable: 20, locked: 20, claimed_schedules: 0}
+    );
+
+    context.warp_to_slot(1000).unwrap();
+    let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    assert_eq!(
+        get_claimable_amounts(&mut context.banks_client, &program_id, &payer, &vesting_account_key, recent_blockhash).await,
+        ClaimableAmounts {claimable: 40, locked: 0, claimed_schedules: 0}
+    );
+
+    let mut unlock_transaction = Transaction::new_with_payer(
+        &[unlock_with_associated_destination(
+            &program_id,
+            &spl_token::id(),
+            &sysvar::clock::id(),
+            &system_program::id(),
+            &spl_associated_token_account::id(),
+            &vesting_account_key,
+            &vesting_token_account.pubkey(),
+            &destination_account.pubkey(),
+            &mint.pubkey(),
+            &payer.pubkey(),
+            seeds
+        ).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    unlock_transaction.partial_sign(
+        &[&payer],
+        recent_blockhash
+    );
+    assert_eq!(
+        process_transaction_events(&mut context.banks_client, &program_id, unlock_transaction).await.len(),
+        1
+    );
+    assert_eq!(
+        get_claimable_amounts(&mut context.banks_client, &program_id, &payer, &vesting_account_key, recent_blockhash).await,
+        ClaimableAmounts {claimable: 0, locked: 0, claimed_schedules: 2}
+    );
+}
+
+#[tokio::test]
+async fn test_milestones() {
+
+    // Create program and test environment
+    let TestFixture {
+        mut context,
+        program_id,
+        payer,
+        recent_blockhash,
+        mint,
+        source_account,
+        source_token_account,
+        ..
+    } = setup_fixture(&[]).await;
+
+    let destination_account = Keypair::new();
+    let approver = Keypair::new();
+
+    let mut seeds = [42u8; 32];
+    let (vesting_account_key, bump) = Pubkey::find_program_address(&[&seeds[..31]], &program_id);
+    seeds[31] = bump;
+    let vesting_token_account = Keypair::new();
+
+    context.banks_client.process_transaction(
+        create_token_account(&payer, &mint, recent_blockhash, &vesting_token_account, &vesting_account_key)
+    ).await.unwrap();
+
+    // The first milestone has no fallback, the second one falls back to a release in the far future
+    let setup_instructions = [
+        init(
+            &system_program::id(),
+            &sysvar::rent::id(),
+            &program_id,
+            &payer.pubkey(),
+            &vesting_account_key,
+            seeds,
+            2
+        ).unwrap(),
+        create_milestones(
+            &program_id,
+            &spl_token::id(),
+            &vesting_account_key,
+            &vesting_token_account.pubkey(),
+            &source_account.pubkey(),
+            &source_token_account.pubkey(),
+            &get_associated_token_address(&destination_account.pubkey(), &mint.pubkey()),
+            &mint.pubkey(),
+            TimeBasis::UnixTimestamp,
+            &approver.pubkey(),
+            vec![
+                Schedule {amount: 20, release_time: u64::MAX},
+                Schedule {amount: 30, release_time: u64::MAX - 1}
+            ],
+            seeds
+        ).unwrap()
+    ];
+    let mut setup_transaction = Transaction::new_with_payer(
+        &setup_instructions,
+        Some(&payer.pubkey()),
+    );
+    setup_transaction.partial_sign(
+        &[
+            &payer,
+            &source_account
+            ],
+        recent_blockhash
+    );
+    context.banks_client.process_transaction(setup_transaction).await.unwrap();
+
+    assert_eq!(
+        get_claimable_amounts(&mut context.banks_client, &program_id, &payer, &vesting_account_key, recent_blockhash).await,
+        ClaimableAmounts {claimable: 0, locked: 50, claimed_schedules: 0}
+    );
+
+    // Only the approver can release a milestone
+    let mut unapproved_transaction = Transaction::new_with_payer(
+        &[release_milestone(&program_id, &vesting_account_key, &payer.pubkey(), 0).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    unapproved_transaction.partial_sign(&[&payer], recent_blockhash);
+    assert_eq!(
+        context.banks_client.process_transaction(unapproved_transaction).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(VestingError::IncorrectApprover as u32))
+    );
+
+    let mut release_transaction = Transaction::new_with_payer(
+        &[release_milestone(&program_id, &vesting_account_key, &approver.pubkey(), 1).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    release_transaction.partial_sign(&[&payer, &approver], recent_blockhash);
+    assert_eq!(
+        process_transaction_events(&mut context.banks_client, &program_id, release_transaction).await,
+        vec![VestingEvent::MilestoneReleased {
+            vesting_account: vesting_account_key,
+            schedule_index: 1,
+            unix_timestamp: context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp
+        }]
+    );
+    assert_eq!(
+        get_claimable_amounts(&mut context.banks_client, &program_id, &payer, &vesting_account_key, recent_blockhash).await,
+        ClaimableAmounts {claimable: 30, locked: 20, claimed_schedules: 0}
+    );
+
+    let mut unlock_transaction = Transaction::new_with_payer(
+        &[unlock_with_associated_destination(
+            &program_id,
+            &spl_token::id(),
+            &sysvar::clock::id(),
+            &system_program::id(),
+            &spl_associated_token_account::id(),
+            &vesting_account_key,
+            &vesting_token_account.pubkey(),
+            &destination_account.pubkey(),
+            &mint.pubkey(),
+            &payer.pubkey(),
+            seeds
+        ).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    unlock_transaction.partial_sign(&[&payer], recent_blockhash);
+    context.banks_client.process_transaction(unlock_transaction).await.unwrap();
+    assert_eq!(
+        get_claimable_amounts(&mut context.banks_client, &program_id, &payer, &vesting_account_key, recent_blockhash).await,
+        ClaimableAmounts {claimable: 0, locked: 20, claimed_schedules: 1}
+    );
+
+    // An unlocked milestone cannot be released again
+    let mut released_again_transaction = Transaction::new_with_payer(
+        &[release_milestone(&program_id, &vesting_account_key, &approver.pubkey(), 1).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    // A new blockhash keeps the transaction from being deduplicated with the first release
+    let recent_blockhash = context.banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+    released_again_transaction.partial_sign(&[&payer, &approver], recent_blockhash);
+    assert_eq!(
+        context.banks_client.process_transaction(released_again_transaction).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn test_pause_and_resume() {
+
+    // Create program and test environment
+    let TestFixture {
+        mut context,
+        program_id,
+        payer,
+        recent_blockhash,
+        mint,
+        source_account,
+        source_token_account,
+        ..
+    } = setup_fixture(&[]).await;
+
+    let destination_account = Keypair::new();
+    let pause_authority = Keypair::new();
+
+    let mut seeds = [42u8; 32];
+    let (vesting_account_key, bump) = Pubkey::find_program_address(&[&seeds[..31]], &program_id);
+    seeds[31] = bump;
+    let vesting_token_account = Keypair::new();
+
+    context.banks_client.process_transaction(
+        create_token_account(&payer, &mint, recent_blockhash, &vesting_token_account, &vesting_account_key)
+    ).await.unwrap();
+
+    let setup_instructions = [
+        init(
+            &system_program::id(),
+            &sysvar::rent::id(),
+            &program_id,
+            &payer.pubkey(),
+            &vesting_account_key,
+            seeds,
+            2
+        ).unwrap(),
+        create_pausable(
+            &program_id,
+            &spl_token::id(),
+            &vesting_account_key,
+            &vesting_token_account.pubkey(),
+            &source_account.pubkey(),
+            &source_token_account.pubkey(),
+            &get_associated_token_address(&destination_account.pubkey(), &mint.pubkey()),
+            &mint.pubkey(),
+            TimeBasis::Slot,
+            None,
+            &pause_authority.pubkey(),
+            vec![
+                Schedule {amount: 20, release_time: 0},
+                Schedule {amount: 20, release_time: 1000}
+            ],
+            seeds
+        ).unwrap()
+    ];
+    let mut setup_transaction = Transaction::new_with_payer(
+        &setup_instructions,
+        Some(&payer.pubkey()),
+    );
+    setup_transaction.partial_sign(
+        &[
+            &payer,
+            &source_account
+            ],
+        recent_blockhash
+    );
+    context.banks_client.process_transaction(setup_transaction).await.unwrap();
+
+    // Only the pause authority can pause the contract
+    let mut unauthorized_transaction = Transaction::new_with_payer(
+        &[pause(&program_id, &vesting_account_key, &payer.pubkey()).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    unauthorized_transaction.partial_sign(&[&payer], recent_blockhash);
+    assert_eq!(
+        context.banks_client.process_transaction(unauthorized_transaction).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(VestingError::IncorrectPauseAuthority as u32))
+    );
+
+    let mut pause_transaction = Transaction::new_with_payer(
+        &[pause(&program_id, &vesting_account_key, &pause_authority.pubkey()).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    pause_transaction.partial_sign(&[&payer, &pause_authority], recent_blockhash);
+    let paused_clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    assert_eq!(
+        process_transaction_events(&mut context.banks_client, &program_id, pause_transaction).await,
+        vec![VestingEvent::Paused {
+            vesting_account: vesting_account_key,
+            unix_timestamp: paused_clock.unix_timestamp
+        }]
+    );
+    assert_eq!(
+        get_claimable_amounts(&mut context.banks_client, &program_id, &payer, &vesting_account_key, recent_blockhash).await,
+        ClaimableAmounts {claimable: 0, locked: 40, claimed_schedules: 0}
+    );
+
+    // Matured schedules cannot be unlocked while the contract is paused
+    let unlock_instruction = unlock_with_associated_destination(
+        &program_id,
+        &spl_token::id(),
+        &sysvar::clock::id(),
+        &system_program::id(),
+        &spl_associated_token_account::id(),
+        &vesting_account_key,
+        &vesting_token_account.pubkey(),
+        &destination_account.pubkey(),
+        &mint.pubkey(),
+        &payer.pubkey(),
+        seeds
+    ).unwrap();
+    let mut paused_unlock_transaction = Transaction::new_with_payer(
+        std::slice::from_ref(&unlock_instruction),
+        Some(&payer.pubkey()),
+    );
+    paused_unlock_transaction.partial_sign(&[&payer], recent_blockhash);
+    assert_eq!(
+        context.banks_client.process_transaction(paused_unlock_transaction).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(VestingError::ContractPaused as u32))
+    );
+
+    // Resuming delays the schedule that had not matured by the paused duration
+    context.warp_to_slot(500).unwrap();
+    let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let mut resume_transaction = Transaction::new_with_payer(
+        &[resume(&program_id, &vesting_account_key, &pause_authority.pubkey(), true).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    resume_transaction.partial_sign(&[&payer, &pause_authority], recent_blockhash);
+    let resumed_clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let paused_duration = resumed_clock.slot - paused_clock.slot;
+    assert_eq!(
+        process_transaction_events(&mut context.banks_client, &program_id, resume_transaction).await,
+        vec![VestingEvent::Resumed {
+            vesting_account: vesting_account_key,
+            paused_duration,
+            shifted_release_times: true,
+            unix_timestamp: resumed_clock.unix_timestamp
+        }]
+    );
+    let vesting_account = context.banks_client.get_account(vesting_account_key).await.unwrap().unwrap();
+    let contract = VestingContract::unpack(&vesting_account.data).unwrap();
+    assert_eq!(contract.header.paused_at, None);
+    assert_eq!(contract.schedules[0].release_time, 0);
+    assert_eq!(contract.schedules[1].release_time, 1000 + paused_duration);
+
+    let mut unlock_transaction = Transaction::new_with_payer(
+        &[unlock_instruction],
+        Some(&payer.pubkey()),
+    );
+    unlock_transaction.partial_sign(&[&payer], recent_blockhash);
+    context.banks_client.process_transaction(unlock_transaction).await.unwrap();
+    assert_eq!(
+        get_claimable_amounts(&mut context.banks_client, &program_id, &payer, &vesting_account_key, recent_blockhash).await,
+        ClaimableAmounts {claimable: 0, locked: 20, claimed_schedules: 1}
+    );
+}
+
+#[tokio::test]
+async fn test_split_destinations() {
+
+    // Create program and test environment
+    let TestFixture {
+        mut context,
+        program_id,
+        payer,
+        recent_blockhash,
+        mint,
+        source_account,
+        source_token_account,
+        ..
+    } = setup_fixture(&[]).await;
+
+    let destination_token_account = Keypair::new();
+    let cold_wallet_token_account = Keypair::new();
+    let tax_owner = Keypair::new();
+    let tax_token_account = Keypair::new();
+    let new_tax_token_account = Keypair::new();
+    let other_mint = Keypair::new();
+    let other_mint_token_account = Keypair::new();
+
+    let mut seeds = [42u8; 32];
+    let (vesting_account_key, bump) = Pubkey::find_program_address(&[&seeds[..31]], &program_id);
+    seeds[31] = bump;
+    let vesting_token_account = Keypair::new();
+
+    for (token_account, owner) in [
+        (&vesting_token_account, vesting_account_key),
+        (&destination_token_account, Pubkey::new_unique()),
+        (&cold_wallet_token_account, Pubkey::new_unique()),
+        (&tax_token_account, tax_owner.pubkey()),
+        (&new_tax_token_account, Pubkey::new_unique()),
+    ] {
+        context.banks_client.process_transaction(
+            create_token_account(&payer, &mint, recent_blockhash, token_account, &owner)
+        ).await.unwrap();
+    }
+    context.banks_client.process_transaction(
+        mint_init_transaction(&payer, &other_mint, &payer, recent_blockhash)
+    ).await.unwrap();
+    context.banks_client.process_transaction(
+        create_token_account(&payer, &other_mint, recent_blockhash, &other_mint_token_account, &tax_owner.pubkey())
+    ).await.unwrap();
+
+    let create_instruction = |split_destinations: Vec<DestinationSplit>| create_with_splits(
+        &program_id,
+        &spl_token::id(),
+        &vesting_account_key,
+        &vesting_token_account.pubkey(),
+        &source_account.pubkey(),
+        &source_token_account.pubkey(),
+        &destination_token_account.pubkey(),
+        &mint.pubkey(),
+        TimeBasis::UnixTimestamp,
+        None,
+        None,
+        split_destinations,
+        vec![Schedule {amount: 99, release_time: 0}],
+        seeds
+    ).unwrap();
+    let init_instruction = init(
+        &system_program::id(),
+        &sysvar::rent::id(),
+        &program_id,
+        &payer.pubkey(),
+        &vesting_account_key,
+        seeds,
+        1
+    ).unwrap();
+
+    // The split destinations have to be token accounts of the mint of the contract
+    let mut other_mint_split_transaction = Transaction::new_with_payer(
+        &[
+            init_instruction.clone(),
+            create_instruction(vec![
+                DestinationSplit {token_address: other_mint_token_account.pubkey(), basis_points: 5000},
+            ]),
+        ],
+        Some(&payer.pubkey()),
+    );
+    other_mint_split_transaction.partial_sign(&[&payer, &source_account], recent_blockhash);
+    assert_eq!(
+        context.banks_client.process_transaction(other_mint_split_transaction).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(1, InstructionError::InvalidArgument)
+    );
+
+    let split_destinations = vec![
+        DestinationSplit {token_address: cold_wallet_token_account.pubkey(), basis_points: 5000},
+        DestinationSplit {token_address: tax_token_account.pubkey(), basis_points: 3333},
+    ];
+    let setup_instructions = [
+        init_instruction,
+        create_instruction(split_destinations.clone()),
+    ];
+    let mut setup_transaction = Transaction::new_with_payer(
+        &setup_instructions,
+        Some(&payer.pubkey()),
+    );
+    setup_transaction.partial_sign(
+        &[
+            &payer,
+            &source_account
+            ],
+        recent_blockhash
+    );
+    context.banks_client.process_transaction(setup_transaction).await.unwrap();
+
+    let unlock_instruction = unlock(
+        &program_id,
+        &spl_token::id(),
+        &sysvar::clock::id(),
+        &vesting_account_key,
+        &vesting_token_account.pubkey(),
+        &destination_token_account.pubkey(),
+        seeds
+    ).unwrap();
+
+    // Every split destination account has to be given
+    let mut missing_split_transaction = Transaction::new_with_payer(
+        &[with_split_destinations(unlock_instruction.clone(), &split_destinations[..1])],
+        Some(&payer.pubkey()),
+    );
+    missing_split_transaction.partial_sign(&[&payer], recent_blockhash);
+    assert_eq!(
+        context.banks_client.process_transaction(missing_split_transaction).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
+
+    // The shares are rounded down, the destination receiving the rest
+    let mut unlock_transaction = Transaction::new_with_payer(
+        &[with_split_destinations(unlock_instruction, &split_destinations)],
+        Some(&payer.pubkey()),
+    );
+    unlock_transaction.partial_sign(&[&payer], recent_blockhash);
+    let unix_timestamp = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    assert_eq!(
+        process_transaction_events(&mut context.banks_client, &program_id, unlock_transaction).await,
+        [
+            (destination_token_account.pubkey(), 18),
+            (cold_wallet_token_account.pubkey(), 49),
+            (tax_token_account.pubkey(), 32),
+        ].iter().map(|(destination_token_address, amount)| VestingEvent::Unlocked {
+            vesting_account: vesting_account_key,
+            destination_token_address: *destination_token_address,
+            amount: *amount,
+            unix_timestamp
+        }).collect::<Vec<_>>()
+    );
+    for (token_account, amount) in [
+        (&destination_token_account, 18),
+        (&cold_wallet_token_account, 49),
+        (&tax_token_account, 32),
+        (&vesting_token_account, 0),
+    ] {
+        let account = context.banks_client.get_account(token_account.pubkey()).await.unwrap().unwrap();
+        assert_eq!(spl_token::state::Account::unpack(&account.data).unwrap().amount, amount);
+    }
+
+    // The owner of a split destination can move it to another token account of the mint
+    let change_split_instruction = |new_split_token_account: &Pubkey| change_split_destination(
+        &program_id,
+        &vesting_account_key,
+        &tax_owner.pubkey(),
+        &tax_token_account.pubkey(),
+        new_split_token_account,
+        seeds
+    ).unwrap();
+    let mut other_mint_change_transaction = Transaction::new_with_payer(
+        &[change_split_instruction(&other_mint_token_account.pubkey())],
+        Some(&payer.pubkey()),
+    );
+    other_mint_change_transaction.partial_sign(&[&payer, &tax_owner], recent_blockhash);
+    assert_eq!(
+        context.banks_client.process_transaction(other_mint_change_transaction).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+
+    let mut change_split_transaction = Transaction::new_with_payer(
+        &[change_split_instruction(&new_tax_token_account.pubkey())],
+        Some(&payer.pubkey()),
+    );
+    change_split_transaction.partial_sign(&[&payer, &tax_owner], recent_blockhash);
+    let unix_timestamp = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    assert_eq!(
+        process_transaction_events(&mut context.banks_client, &program_id, change_split_transaction).await,
+        vec![VestingEvent::SplitDestinationChanged {
+            vesting_account: vesting_account_key,
+            old_split_token_address: tax_token_account.pubkey(),
+            new_split_token_address: new_tax_token_account.pubkey(),
+            unix_timestamp
+        }]
+    );
+    let vesting_account = context.banks_client.get_account(vesting_account_key).await.unwrap().unwrap();
+    let contract = VestingContract::unpack(&vesting_account.data).unwrap();
+    assert_eq!(
+        contract.header.split_destinations,
+        vec![
+            split_destinations[0],
+            DestinationSplit {token_address: new_tax_token_account.pubkey(), basis_points: 3333},
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_native_vesting() {
+
+    // Create program and test environment
+    let destination_account = Keypair::new();
+    let TestFixture {
+        mut context,
+        program_id,
+        payer,
+        recent_blockhash,
+        source_account,
+        ..
+    } = setup_fixture(&[destination_account.pubkey()]).await;
+    let new_destination_account = Pubkey::new_unique();
+
+    let mut seeds = [42u8; 32];
+    let (vesting_account_key, bump) = Pubkey::find_program_address(&[&seeds[..31]], &program_id);
+    seeds[31] = bump;
+
+    let setup_instructions = [
+        init(
+            &system_program::id(),
+            &sysvar::rent::id(),
+            &program_id,
+            &payer.pubkey(),
+            &vesting_account_key,
+            seeds,
+            2
+        ).unwrap(),
+        create_native(
+            &program_id,
+            &system_program::id(),
+            &vesting_account_key,
+            &source_account.pubkey(),
+            &destination_account.pubkey(),
+            TimeBasis::UnixTimestamp,
+            None,
+            None,
+            vec![
+                Schedule {amount: 1000000, release_time: 0},
+                Schedule {amount: 2000000, release_time: u64::MAX},
+            ],
+            seeds
+        ).unwrap()
+    ];
+    let mut setup_transaction = Transaction::new_with_payer(
+        &setup_instructions,
+        Some(&payer.pubkey()),
+    );
+    setup_transaction.partial_sign(
+        &[
+            &payer,
+            &source_account
+            ],
+        recent_blockhash
+    );
+    context.banks_client.process_transaction(setup_transaction).await.unwrap();
+    assert_eq!(context.banks_client.get_balance(source_account.pubkey()).await.unwrap(), 2000000);
+
+    // The destination wallet of a native contract signs for itself
+    let change_destination_instructions = [change_destination(
+        &program_id,
+        &vesting_account_key,
+        &destination_account.pubkey(),
+        &destination_account.pubkey(),
+        &new_destination_account,
+        seeds
+    ).unwrap()];
+    let mut change_destination_transaction = Transaction::new_with_payer(
+        &change_destination_instructions,
+        Some(&payer.pubkey()),
+    );
+    change_destination_transaction.partial_sign(
+        &[&payer, &destination_account],
+        recent_blockhash
+    );
+    context.banks_client.process_transaction(change_destination_transaction).await.unwrap();
+
+    // The lamports of the matured schedule are moved out of the vesting account
+    let unlock_instructions = [unlock_native(
+        &program_id,
+        &system_program::id(),
+        &sysvar::clock::id(),
+        &vesting_account_key,
+        &new_destination_account,
+        seeds
+    ).unwrap()];
+    let mut unlock_transaction = Transaction::new_with_payer(
+        &unlock_instructions,
+        Some(&payer.pubkey()),
+    );
+    unlock_transaction.partial_sign(&[&payer], recent_blockhash);
+    let unix_timestamp = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    assert_eq!(
+        process_transaction_events(&mut context.banks_client, &program_id, unlock_transaction).await,
+        [VestingEvent::Unlocked {
+            vesting_account: vesting_account_key,
+            destination_token_address: new_destination_account,
+            amount: 1000000,
+            unix_timestamp
+        }]
+    );
+    assert_eq!(context.banks_client.get_balance(new_destination_account).await.unwrap(), 1000000);
+    let vesting_account = context.banks_client.get_account(vesting_account_key).await.unwrap().unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    assert_eq!(
+        vesting_account.lamports,
+        rent.minimum_balance(vesting_account.data.len()) + 2000000
+    );
+    let contract = VestingContract::unpack(&vesting_account.data).unwrap();
+    assert!(contract.header.is_native);
+    assert_eq!(contract.schedules[0].amount, 0);
+}
+
+#[tokio::test]
+async fn test_treasury_pool() {
+
+    // Create program and test environment
+    let TestFixture {
+        mut context,
+        program_id,
+        payer,
+        recent_blockhash,
+        mint_authority,
+        mint,
+        ..
+    } = setup_fixture(&[]).await;
+
+    let pool_authority = Keypair::new();
+
+    let first_token_account = Keypair::new();
+    let second_token_account = Keypair::new();
+
+    let mut seeds = [42u8; 32];
+    let (pool_account_key, bump) = Pubkey::find_program_address(&[&seeds[..31]], &program_id);
+    seeds[31] = bump;
+    let pool_token_account_key = get_associated_token_address(&pool_account_key, &mint.pubkey());
+
+    for token_account in [&first_token_account, &second_token_account] {
+        context.banks_client.process_transaction(
+            create_token_account(&payer, &mint, recent_blockhash, token_account, &Pubkey::new_unique())
+        ).await.unwrap();
+    }
+
+    let setup_instructions = [
+        init_pool(
+            &program_id,
+            &system_program::id(),
+            &sysvar::rent::id(),
+            &payer.pubkey(),
+            &pool_account_key,
+            &mint.pubkey(),
+            &pool_authority.pubkey(),
+            TimeBasis::UnixTimestamp,
+            seeds
+        ).unwrap(),
+        spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(),
+            &pool_account_key,
+            &mint.pubkey(),
+            &spl_token::id()
+        ),
+        mint_to(
+            &spl_token::id(),
+            &mint.pubkey(),
+            &pool_token_account_key,
+            &mint_authority.pubkey(),
+            &[],
+            100
+        ).unwrap(),
+        add_allocation(
+            &program_id,
+            &system_program::id(),
+            &payer.pubkey(),
+            &pool_account_key,
+            &pool_authority.pubkey(),
+            &pool_token_account_key,
+            &first_token_account.pubkey(),
+            vec![
+                Schedule {amount: 30, release_time: 0},
+                Schedule {amount: 40, release_time: u64::MAX},
+            ]
+        ).unwrap()
+    ];
+    let mut setup_transaction = Transaction::new_with_payer(
+        &setup_instructions,
+        Some(&payer.pubkey()),
+    );
+    setup_transaction.partial_sign(
+        &[
+            &payer,
+            &mint_authority,
+            &pool_authority
+            ],
+        recent_blockhash
+    );
+    context.banks_client.process_transaction(setup_transaction).await.unwrap();
+
+    // Only 30 tokens of the pool are not allocated yet
+    let second_allocation = |pool_authority: &Keypair, amount: u64| {
+        let mut transaction = Transaction::new_with_payer(
+            &[add_allocation(
+                &program_id,
+                &system_program::id(),
+                &payer.pubkey(),
+                &pool_account_key,
+                &pool_authority.pubkey(),
+                &pool_token_account_key,
+                &second_token_account.pubkey(),
+                vec![Schedule {amount, release_time: 0}]
+            ).unwrap()],
+            Some(&payer.pubkey()),
+        );
+        transaction.partial_sign(&[&payer, pool_authority], recent_blockhash);
+        transaction
+    };
+    assert_eq!(
+        context.banks_client.process_transaction(second_allocation(&pool_authority, 31)).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(VestingError::PoolOverAllocated as u32)
+        )
+    );
+    assert_eq!(
+        context.banks_client.process_transaction(second_allocation(&Keypair::new(), 30)).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(VestingError::IncorrectPoolAuthority as u32)
+        )
+    );
+
+    // The matured schedule is claimed from the pool token account
+    let (first_allocation_key, _) = find_allocation_address(
+        &program_id,
+        &pool_account_key,
+        &first_token_account.pubkey()
+    );
+    let mut claim_transaction = Transaction::new_with_payer(
+        &[claim_allocation(
+            &program_id,
+            &spl_token::id(),
+            &sysvar::clock::id(),
+            &pool_account_key,
+            &pool_token_account_key,
+            &first_token_account.pubkey(),
+            seeds
+        ).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    claim_transaction.partial_sign(&[&payer], recent_blockhash);
+    let unix_timestamp = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    assert_eq!(
+        process_transaction_events(&mut context.banks_client, &program_id, claim_transaction).await,
+        [VestingEvent::AllocationClaimed {
+            pool_account: pool_account_key,
+            allocation_account: first_allocation_key,
+            destination_token_address: first_token_account.pubkey(),
+            amount: 30,
+            unix_timestamp
+        }]
+    );
+
+    // The locked schedule returns to the pool and the allocation account is closed
+    let mut remove_transaction = Transaction::new_with_payer(
+        &[remove_allocation(
+            &program_id,
+            &pool_account_key,
+            &pool_authority.pubkey(),
+            &first_token_account.pubkey(),
+            &payer.pubkey()
+        ).unwrap()],
+        Some(&payer.pubkey()),
+    );
+    remove_transaction.partial_sign(&[&payer, &pool_authority], recent_blockhash);
+    assert_eq!(
+        process_transaction_events(&mut context.banks_client, &program_id, remove_transaction).await,
+        [VestingEvent::AllocationRemoved {
+            pool_account: pool_account_key,
+            allocation_account: first_allocation_key,
+            cancelled_amount: 40,
+            unix_timestamp
+        }]
+    );
+    assert!(context.banks_client.get_account(first_allocation_key).await.unwrap().is_none());
+    let pool_account = context.banks_client.get_account(pool_account_key).await.unwrap().unwrap();
+    assert_eq!(PoolHeader::unpack(&pool_account.data).unwrap().allocated_amount, 0);
+
+    // The whole remaining balance of the pool can be allocated again
+    context.banks_client.process_transaction(second_allocation(&pool_authority, 70)).await.unwrap();
+    for (token_account, amount) in [
+        (first_token_account.pubkey(), 30),
+        (pool_token_account_key, 70),
+    ] {
+        let account = context.banks_client.get_account(token_account).await.unwrap().unwrap();
+        assert_eq!(spl_token::state::Account::unpack(&account.data).unwrap().amount, amount);
+    }
+}
+
+#[tokio::test]
+async fn test_merkle_distributor() {
+
+    // Create program and test environment
+    let claimant = Keypair::new();
+    let TestFixture {
+        mut context,
+        program_id,
+        payer,
+        recent_blockhash,
+        mint_authority,
+        mint,
+        ..
+    } = setup_fixture(&[claimant.pubkey()]).await;
+
+    let destination_token_account = Keypair::new();
+
+    let mut seeds = [42u8; 32];
+    let (distributor_account_key, bump) = Pubkey::find_program_address(&[&seeds[..31]], &program_id);
+    seeds[31] = bump;
+    let distributor_token_account_key = get_associated_token_address(&distributor_account_key, &mint.pubkey());
+
+    // The claimant is one of three leaves of the tree
+    let claims = [
+        (Pubkey::new_unique(), 100),
+        (claimant.pubkey(), 400),
+        (Pubkey::new_unique(), 500),
+    ];
+    let levels = tree_levels(claims.iter().map(|(wallet, amount)| leaf_hash(wallet, *amount)).collect());
+    let merkle_root = tree_root(&levels).unwrap();
+    let proof = tree_proof(&levels, 1);
+
+    context.banks_client.process_transaction(
+        create_token_account(&payer, &mint, recent_blockhash, &destination_token_account, &claimant.pubkey())
+    ).await.unwrap();
+
+    // The allocations vest from slot 100 to slot 300, with a cliff at slot 200
+    let setup_instructions = [
+        init_distributor(
+            &program_id,
+            &system_program::id(),
+            &sysvar::rent::id(),
+            &payer.pubkey(),
+            &distributor_account_key,
+            &mint.pubkey(),
+            merkle_root,
+            TimeBasis::Slot,
+            100,
+            200,
+            300,
+            1_000,
+            seeds
+        ).unwrap(),
+        spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(),
+            &distributor_account_key,
+            &mint.pubkey(),
+            &spl_token::id()
+        ),
+        mint_to(
+            &spl_token::id(),
+            &mint.pubkey(),
+            &distributor_token_account_key,
+            &mint_authority.pubkey(),
+            &[],
+            1_000
+        ).unwrap()
+    ];
+    let mut setup_transaction = Transaction::new_with_payer(
+        &setup_instructions,
+        Some(&payer.pubkey()),
+    );
+    setup_transaction.partial_sign(
+        &[
+            &payer,
+            &mint_authority
+            ],
+        recent_blockhash
+    );
+    context.banks_client.process_transaction(setup_transaction).await.unwrap();
+
+    let claim_transaction = |amount: u64, proof: Vec<[u8; 32]>, recent_blockhash: Hash| {
+        let mut transaction = Transaction::new_with_payer(
+            &[claim_distribution(
+                &program_id,
+                &spl_token::id(),
+                &system_program::id(),
+                &sysvar::clock::id(),
+                &distributor_account_key,
+                &distributor_token_account_key,
+                &claimant.pubkey(),
+                &destination_token_account.pubkey(),
+                amount,
+                proof,
+                seeds
+            ).unwrap()],
+            Some(&payer.pubkey()),
+        );
+        transaction.partial_sign(&[&payer, &claimant], recent_blockhash);
+        transaction
+    };
+
+    // The proof has to lead from the allocation of the claimant to the root
+    assert_eq!(
+        context.banks_client.process_transaction(claim_transaction(500, proof.clone(), recent_blockhash)).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(VestingError::InvalidMerkleProof as u32)
+        )
+    );
+
+    // The first claim creates the claim account even though nothing vested before the cliff
+    let (claim_account_key, _) = find_distribution_claim_address(
+        &program_id,
+        &distributor_account_key,
+        &claimant.pubkey()
+    );
+    let unix_timestamp = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    assert_eq!(
+        process_transaction_events(&mut context.banks_client, &program_id, claim_transaction(400, proof.clone(), recent_blockhash)).await,
+        [VestingEvent::DistributionClaimed {
+            distributor_account: distributor_account_key,
+            claimant: claimant.pubkey(),
+            destination_token_address: destination_token_account.pubkey(),
+            amount: 0,
+            unix_timestamp
+        }]
+    );
+    let claim_account = context.banks_client.get_account(claim_account_key).await.unwrap().unwrap();
+    assert_eq!(DistributionClaim::unpack(&claim_account.data).unwrap().amount, 400);
+
+    // Three quarters of the period have elapsed, the proof is not needed anymore
+    context.warp_to_slot(250).unwrap();
+    let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    context.banks_client.process_transaction(claim_transaction(400, vec![], recent_blockhash)).await.unwrap();
+    assert_eq!(
+        context.banks_client.process_transaction(claim_transaction(400, proof, recent_blockhash)).await.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+
+    context.warp_to_slot(1000).unwrap();
+    let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    context.banks_client.process_transaction(claim_transaction(400, vec![], recent_blockhash)).await.unwrap();
+
+    let destination = context.banks_client.get_account(destination_token_account.pubkey()).await.unwrap().unwrap();
+    assert_eq!(spl_token::state::Account::unpack(&destination.data).unwrap().amount, 400);
+    let distributor_account = context.banks_client.get_account(distributor_account_key).await.unwrap().unwrap();
+    assert_eq!(DistributorHeader::unpack(&distributor_account.data).unwrap().claimed_amount, 400);
+}
+
+// Program test environment shared by the tests, with a mint and a source token account holding
+// 100 of its tokens
+struct TestFixture {
+    context: ProgramTestContext,
+    program_id: Pubkey,
+    payer: Keypair,
+    recent_blockhash: Hash,
+    mint_authority: Keypair,
+    mint: Keypair,
+    source_account: Keypair,
+    source_token_account: Keypair,
+}
+
+// Starts the program, the source account and the given wallets being funded with lamports
+async fn setup_fixture(funded_wallets: &[Pubkey]) -> TestFixture {
+    let program_id = Pubkey::from_str("VestingbGKPFXCWuBvfkegQfZyiNwAJb9Ss623VQ5DA").unwrap();
+    let mint_authority = Keypair::new();
+    let mint = Keypair::new();
+
+    let source_account = Keypair::new();
+    let source_token_account = Keypair::new();
+
+    let mut program_test =
+        ProgramTest::new("token_vesting", program_id, processor!(process_instruction));
+    for wallet in std::iter::once(&source_account.pubkey()).chain(funded_wallets) {
+        program_test.add_account(
+            *wallet,
+            Account {
+                lamports: 5000000,
+                ..Account::default()
+            },
+        );
+    }
+    let mut context = program_test.start_with_context().await;
+    let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    let recent_blockhash = context.last_blockhash;
+
+    context
+        .banks_client
+        .process_transaction(mint_init_transaction(
+            &payer,
+            &mint,
+            &mint_authority,
+            recent_blockhash,
+        ))
+        .await
+        .unwrap();
+    context
+        .banks_client
+        .process_transaction(create_token_account(
+            &payer,
+            &mint,
+            recent_blockhash,
+            &source_token_account,
+            &source_account.pubkey(),
+        ))
+        .await
+        .unwrap();
+    let mut mint_to_transaction = Transaction::new_with_payer(
+        &[mint_to(
+            &spl_token::id(),
+            &mint.pubkey(),
+            &source_token_account.pubkey(),
+            &mint_authority.pubkey(),
+            &[],
+            100,
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+    );
+    mint_to_transaction.partial_sign(&[&payer, &mint_authority], recent_blockhash);
+    context
+        .banks_client
+        .process_transaction(mint_to_transaction)
+        .await
+        .unwrap();
+
+    TestFixture {
+        context,
+        program_id,
+        payer,
+        recent_blockhash,
+        mint_authority,
+        mint,
+        source_account,
+        source_token_account,
+    }
+}
+
+async fn process_transaction_events(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    transaction: Transaction
+) -> Vec<VestingEvent> {
+    let processed = banks_client.process_transaction_with_metadata(transaction).await.unwrap();
+    processed.result.unwrap();
+    VestingEvent::from_logs(program_id, &processed.metadata.unwrap().log_messages)
+}
+
+async fn get_claimable_amounts(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    vesting_account_key: &Pubkey,
+    recent_blockhash: Hash
+) -> ClaimableAmounts {
+    let get_claimable_instruction = [get_claimable(
+        program_id,
+        &sysvar::clock::id(),
+        vesting_account_key
+    ).unwrap()];
+    let mut get_claimable_transaction = Transaction::new_with_payer(
+        &get_claimable_instruction,
+        Some(&payer.pubkey()),
+    );
+    get_claimable_transaction.partial_sign(
+        &[payer],
+        recent_blockhash
+    );
+    let simulation = banks_client.simulate_transaction(get_claimable_transaction).await.unwrap();
+    simulation.result.unwrap().unwrap();
+    let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+    assert_eq!(return_data.program_id, *program_id);
+    ClaimableAmounts::unpack_from_slice(&return_data.data).unwrap()
+}
+
+// Account of the token program holding the given state
+fn token_program_account<T: Pack>(state: T) -> Account {
+    let mut data = vec![0; T::LEN];
+    state.pack_into_slice(&mut data);
+    Account {
+        lamports: Rent::default().minimum_balance(T::LEN),
+        data,
+        owner: spl_token::id(),
+        ..Account::default()
+    }
+}
+
 fn mint_init_transaction(
     payer: &Keypair, 
     mint:&Keypair, 
@@ -0,0 +1,951 @@
+// Checks that idl/token_vesting.json describes the instructions, state layouts, events and errors
+// of the program: values are encoded following the IDL and compared with the Rust encoding.
+use std::str::FromStr;
+
+use num_traits::FromPrimitive;
+use serde_json::{json, Value};
+use solana_program::{instruction::Instruction, program_pack::Pack, pubkey::Pubkey};
+use token_vesting::{
+    error::VestingError,
+    events::VestingEvent,
+    instruction::{
+        add_allocation, change_destination, change_split_destination, claim_allocation,
+        claim_distribution, create, create_milestones, create_native, create_pausable,
+        create_with_splits, create_with_time_basis, get_claimable, init, init_distributor,
+        init_pool, pause, release_milestone, remove_allocation, resume, unlock,
+        unlock_with_associated_destination, Schedule, VestingInstruction,
+    },
+    state::{
+        AllocationHeader, ClaimableAmounts, DestinationSplit, DistributionClaim,
+        DistributorHeader, PoolHeader, TimeBasis, VestingSchedule, VestingScheduleHeader,
+    },
+};
+
+fn load_idl() -> Value {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/idl/token_vesting.json");
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+// Borsh encoding of a value, following an IDL type
+fn encode(idl: &Value, ty: &Value, value: &Value, buf: &mut Vec<u8>) {
+    if let Some(name) = ty.as_str() {
+        match name {
+            "u8" => buf.push(value.as_u64().unwrap() as u8),
+            "u16" => buf.extend_from_slice(&(value.as_u64().unwrap() as u16).to_le_bytes()),
+            "u32" => buf.extend_from_slice(&(value.as_u64().unwrap() as u32).to_le_bytes()),
+            "u64" => buf.extend_from_slice(&value.as_u64().unwrap().to_le_bytes()),
+            "i64" => buf.extend_from_slice(&value.as_i64().unwrap().to_le_bytes()),
+            "bool" => buf.push(value.as_bool().unwrap() as u8),
+            "pubkey" => buf.extend_from_slice(
+                &Pubkey::from_str(value.as_str().unwrap()).unwrap().to_bytes(),
+            ),
+            _ => panic!("Unsupported IDL type {}", name),
+        }
+    } else if let Some(array) = ty.get("array") {
+        let items = value.as_array().unwrap();
+        assert_eq!(items.len() as u64, array[1].as_u64().unwrap());
+        for item in items {
+            encode(idl, &array[0], item, buf);
+        }
+    } else if let Some(item_type) = ty.get("option") {
+        if value.is_null() {
+            buf.push(0);
+        } else {
+            buf.push(1);
+            encode(idl, item_type, value, buf);
+        }
+    } else if let Some(item_type) = ty.get("vec") {
+        let items = value.as_array().unwrap();
+        buf.extend_from_slice(&(items.len() as u32).to_le_bytes());
+        for item in items {
+            encode(idl, item_type, item, buf);
+        }
+    } else if let Some(defined) = ty.get("defined") {
+        let name = defined["name"].as_str().unwrap();
+        let definition = idl["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|definition| definition["name"] == name)
+            .unwrap_or_else(|| panic!("Missing IDL type {}", name));
+        let ty = &definition["type"];
+        match ty["kind"].as_str().unwrap() {
+            "struct" => encode_fields(idl, &ty["fields"], value, buf),
+            "enum" => {
+                let (variant_name, fields) = value.as_object().unwrap().iter().next().unwrap();
+                let variants = ty["variants"].as_array().unwrap();
+                let index = variants
+                    .iter()
+                    .position(|variant| variant["name"] == *variant_name)
+                    .unwrap_or_else(|| panic!("Missing IDL variant {}", variant_name));
+                buf.push(index as u8);
+                // Unit variants have no fields
+                let variant_fields = variants[index].get("fields").cloned().unwrap_or(json!([]));
+                encode_fields(idl, &variant_fields, fields, buf);
+            }
+            kind => panic!("Unsupported IDL type kind {}", kind),
+        }
+    } else {
+        panic!("Unsupported IDL type {}", ty);
+    }
+}
+
+fn encode_fields(idl: &Value, fields: &Value, value: &Value, buf: &mut Vec<u8>) {
+    let fields = fields.as_array().unwrap();
+    // Every value has to be described by the IDL
+    assert_eq!(fields.len(), value.as_object().unwrap().len());
+    for field in fields {
+        let name = field["name"].as_str().unwrap();
+        let field_value = value
+            .get(name)
+            .unwrap_or_else(|| panic!("Missing value for IDL field {}", name));
+        encode(idl, &field["type"], field_value, buf);
+    }
+}
+
+fn encode_defined(idl: &Value, name: &str, value: &Value) -> Vec<u8> {
+    let mut buf = vec![];
+    encode(idl, &json!({ "defined": { "name": name } }), value, &mut buf);
+    buf
+}
+
+// A new instruction has to be added to the IDL and to the samples of `test_idl_instructions`
+fn instruction_name(instruction: &VestingInstruction) -> &'static str {
+    match instruction {
+        VestingInstruction::Init { .. } => "init",
+        VestingInstruction::Create { .. } => "create",
+        VestingInstruction::Unlock { .. } => "unlock",
+        VestingInstruction::ChangeDestination { .. } => "change_destination",
+        VestingInstruction::UnlockWithAssociatedDestination { .. } => {
+            "unlock_with_associated_destination"
+        }
+        VestingInstruction::GetClaimable => "get_claimable",
+        VestingInstruction::CreateWithTimeBasis { .. } => "create_with_time_basis",
+        VestingInstruction::CreateMilestones { .. } => "create_milestones",
+        VestingInstruction::ReleaseMilestone { .. } => "release_milestone",
+        VestingInstruction::CreatePausable { .. } => "create_pausable",
+        VestingInstruction::Pause => "pause",
+        VestingInstruction::Resume { .. } => "resume",
+        VestingInstruction::CreateWithSplits { .. } => "create_with_splits",
+        VestingInstruction::CreateNative { .. } => "create_native",
+        VestingInstruction::InitPool { .. } => "init_pool",
+        VestingInstruction::AddAllocation { .. } => "add_allocation",
+        VestingInstruction::RemoveAllocation => "remove_allocation",
+        VestingInstruction::ClaimAllocation { .. } => "claim_allocation",
+        VestingInstruction::InitDistributor { .. } => "init_distributor",
+        VestingInstruction::ClaimDistribution { .. } => "claim_distribution",
+        VestingInstruction::ChangeSplitDestination { .. } => "change_split_destination",
+    }
+}
+
+#[test]
+fn test_idl_instructions() {
+    let idl = load_idl();
+    let program_id = Pubkey::from_str(idl["address"].as_str().unwrap()).unwrap();
+    let key = || Pubkey::new_unique();
+    let seeds = [42u8; 32];
+    let mint_address = key();
+    let destination_token_address = key();
+    let approver = key();
+    let pause_authority = key();
+    let split_token_address = key();
+    let merkle_root = [7u8; 32];
+    let proof = vec![[1u8; 32], [2u8; 32]];
+    let schedules = vec![
+        Schedule {
+            release_time: 1,
+            amount: 2,
+        },
+        Schedule {
+            release_time: 3,
+            amount: 4,
+        },
+    ];
+
+    let samples: Vec<(Instruction, Value)> = vec![
+        (
+            init(&key(), &key(), &program_id, &key(), &key(), seeds, 7).unwrap(),
+            json!({ "seeds": seeds, "number_of_schedules": 7 }),
+        ),
+        (
+            create(
+                &program_id,
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &destination_token_address,
+                &mint_address,
+                schedules.clone(),
+                seeds,
+            )
+            .unwrap(),
+            json!({
+                "seeds": seeds,
+                "mint_address": mint_address.to_string(),
+                "destination_token_address": destination_token_address.to_string(),
+                "schedules": [
+                    { "release_time": 1, "amount": 2 },
+                    { "release_time": 3, "amount": 4 },
+                ],
+            }),
+        ),
+        (
+            unlock(&program_id, &key(), &key(), &key(), &key(), &key(), seeds).unwrap(),
+            json!({ "seeds": seeds }),
+        ),
+        (
+            change_destination(&program_id, &key(), &key(), &key(), &key(), seeds).unwrap(),
+            json!({ "seeds": seeds }),
+        ),
+        (
+            unlock_with_associated_destination(
+                &program_id,
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &mint_address,
+                &key(),
+                seeds,
+            )
+            .unwrap(),
+            json!({ "seeds": seeds }),
+        ),
+        (
+            get_claimable(&program_id, &key(), &key()).unwrap(),
+            json!({}),
+        ),
+        (
+            create_with_time_basis(
+                &program_id,
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &destination_token_address,
+                &mint_address,
+                TimeBasis::Epoch,
+                schedules.clone(),
+                seeds,
+            )
+            .unwrap(),
+            json!({
+                "seeds": seeds,
+                "mint_address": mint_address.to_string(),
+                "destination_token_address": destination_token_address.to_string(),
+                "time_basis": { "Epoch": {} },
+                "schedules": [
+                    { "release_time": 1, "amount": 2 },
+                    { "release_time": 3, "amount": 4 },
+                ],
+            }),
+        ),
+        (
+            create_milestones(
+                &program_id,
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &destination_token_address,
+                &mint_address,
+                TimeBasis::Slot,
+                &approver,
+                schedules.clone(),
+                seeds,
+            )
+            .unwrap(),
+            json!({
+                "seeds": seeds,
+                "mint_address": mint_address.to_string(),
+                "destination_token_address": destination_token_address.to_string(),
+                "time_basis": { "Slot": {} },
+                "approver": approver.to_string(),
+                "schedules": [
+                    { "release_time": 1, "amount": 2 },
+                    { "release_time": 3, "amount": 4 },
+                ],
+            }),
+        ),
+        (
+            release_milestone(&program_id, &key(), &approver, 7).unwrap(),
+            json!({ "schedule_index": 7 }),
+        ),
+        (
+            create_pausable(
+                &program_id,
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &destination_token_address,
+                &mint_address,
+                TimeBasis::UnixTimestamp,
+                Some(&approver),
+                &pause_authority,
+                schedules.clone(),
+                seeds,
+            )
+            .unwrap(),
+            json!({
+                "seeds": seeds,
+                "mint_address": mint_address.to_string(),
+                "destination_token_address": destination_token_address.to_string(),
+                "time_basis": { "UnixTimestamp": {} },
+                "approver": approver.to_string(),
+                "pause_authority": pause_authority.to_string(),
+                "schedules": [
+                    { "release_time": 1, "amount": 2 },
+                    { "release_time": 3, "amount": 4 },
+                ],
+            }),
+        ),
+        (
+            pause(&program_id, &key(), &pause_authority).unwrap(),
+            json!({}),
+        ),
+        (
+            resume(&program_id, &key(), &pause_authority, true).unwrap(),
+            json!({ "shift_release_times": true }),
+        ),
+        (
+            {
+                let mut instruction = create_with_splits(
+                    &program_id,
+                    &key(),
+                    &key(),
+                    &key(),
+                    &key(),
+                    &key(),
+                    &destination_token_address,
+                    &mint_address,
+                    TimeBasis::UnixTimestamp,
+                    None,
+                    Some(&pause_authority),
+                    vec![DestinationSplit {
+                        token_address: split_token_address,
+                        basis_points: 3000,
+                    }],
+                    schedules.clone(),
+                    seeds,
+                )
+                .unwrap();
+                // The split destination accounts following the accounts of the IDL are in its docs
+                instruction.accounts.truncate(5);
+                instruction
+            },
+            json!({
+                "seeds": seeds,
+                "mint_address": mint_address.to_string(),
+                "destination_token_address": destination_token_address.to_string(),
+                "time_basis": { "UnixTimestamp": {} },
+                "approver": null,
+                "pause_authority": pause_authority.to_string(),
+                "split_destinations": [
+                    { "token_address": split_token_address.to_string(), "basis_points": 3000 },
+                ],
+                "schedules": [
+                    { "release_time": 1, "amount": 2 },
+                    { "release_time": 3, "amount": 4 },
+                ],
+            }),
+        ),
+        (
+            create_native(
+                &program_id,
+                &key(),
+                &key(),
+                &key(),
+                &destination_token_address,
+                TimeBasis::Epoch,
+                Some(&approver),
+                None,
+                schedules.clone(),
+                seeds,
+            )
+            .unwrap(),
+            json!({
+                "seeds": seeds,
+                "destination_address": destination_token_address.to_string(),
+                "time_basis": { "Epoch": {} },
+                "approver": approver.to_string(),
+                "pause_authority": null,
+                "schedules": [
+                    { "release_time": 1, "amount": 2 },
+                    { "release_time": 3, "amount": 4 },
+                ],
+            }),
+        ),
+        (
+            init_pool(
+                &program_id,
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &mint_address,
+                &pause_authority,
+                TimeBasis::Slot,
+                seeds,
+            )
+            .unwrap(),
+            json!({
+                "seeds": seeds,
+                "mint_address": mint_address.to_string(),
+                "pool_authority": pause_authority.to_string(),
+                "time_basis": { "Slot": {} },
+            }),
+        ),
+        (
+            add_allocation(
+                &program_id,
+                &key(),
+                &key(),
+                &key(),
+                &pause_authority,
+                &key(),
+                &destination_token_address,
+                schedules,
+            )
+            .unwrap(),
+            json!({
+                "schedules": [
+                    { "release_time": 1, "amount": 2 },
+                    { "release_time": 3, "amount": 4 },
+                ],
+            }),
+        ),
+        (
+            remove_allocation(
+                &program_id,
+                &key(),
+                &pause_authority,
+                &destination_token_address,
+                &key(),
+            )
+            .unwrap(),
+            json!({}),
+        ),
+        (
+            claim_allocation(
+                &program_id,
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &destination_token_address,
+                seeds,
+            )
+            .unwrap(),
+            json!({ "seeds": seeds }),
+        ),
+        (
+            init_distributor(
+                &program_id,
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &mint_address,
+                merkle_root,
+                TimeBasis::UnixTimestamp,
+                100,
+                150,
+                300,
+                1_000,
+                seeds,
+            )
+            .unwrap(),
+            json!({
+                "seeds": seeds,
+                "mint_address": mint_address.to_string(),
+                "merkle_root": merkle_root,
+                "time_basis": { "UnixTimestamp": {} },
+                "start_time": 100,
+                "cliff_time": 150,
+                "end_time": 300,
+                "total_amount": 1_000,
+            }),
+        ),
+        (
+            claim_distribution(
+                &program_id,
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &key(),
+                &approver,
+                &destination_token_address,
+                400,
+                proof.clone(),
+                seeds,
+            )
+            .unwrap(),
+            json!({
+                "seeds": seeds,
+                "amount": 400,
+                "proof": proof,
+            }),
+        ),
+        (
+            change_split_destination(&program_id, &key(), &key(), &key(), &key(), seeds).unwrap(),
+            json!({ "seeds": seeds }),
+        ),
+    ];
+
+    let idl_instructions = idl["instructions"].as_array().unwrap();
+    assert_eq!(idl_instructions.len(), samples.len());
+    for (idl_instruction, (instruction, args)) in idl_instructions.iter().zip(samples.iter()) {
+        let name = idl_instruction["name"].as_str().unwrap();
+        assert_eq!(
+            name,
+            instruction_name(&VestingInstruction::unpack(&instruction.data).unwrap())
+        );
+
+        let mut data: Vec<u8> = idl_instruction["discriminator"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|byte| byte.as_u64().unwrap() as u8)
+            .collect();
+        encode_fields(&idl, &idl_instruction["args"], args, &mut data);
+        assert_eq!(data, instruction.data, "Arguments of {}", name);
+
+        let idl_accounts = idl_instruction["accounts"].as_array().unwrap();
+        assert_eq!(idl_accounts.len(), instruction.accounts.len(), "Accounts of {}", name);
+        for (idl_account, account) in idl_accounts.iter().zip(instruction.accounts.iter()) {
+            let flag = |flag: &str| idl_account.get(flag).and_then(Value::as_bool) == Some(true);
+            assert_eq!(flag("writable"), account.is_writable, "{} {}", name, idl_account);
+            assert_eq!(flag("signer"), account.is_signer, "{} {}", name, idl_account);
+        }
+    }
+}
+
+#[test]
+fn test_idl_types() {
+    let idl = load_idl();
+
+    let header = VestingScheduleHeader {
+        destination_address: Pubkey::new_unique(),
+        mint_address: Pubkey::new_unique(),
+        is_initialized: true,
+        bump_seed: 254,
+        time_basis: TimeBasis::Slot,
+        approver: Some(Pubkey::new_unique()),
+        pause_authority: None,
+        paused_at: Some(1609459200),
+        split_destinations: vec![DestinationSplit {
+            token_address: Pubkey::new_unique(),
+            basis_points: 10_000,
+        }],
+        is_native: true,
+        is_legacy: false,
+    };
+    let unused_split = json!({ "token_address": Pubkey::default().to_string(), "basis_points": 0 });
+    let mut packed_header = [0u8; VestingScheduleHeader::LEN];
+    header.pack_into_slice(&mut packed_header);
+    let encoded_header = encode_defined(
+        &idl,
+        "VestingScheduleHeader",
+        &json!({
+            "destination_address": header.destination_address.to_string(),
+            "mint_address": header.mint_address.to_string(),
+            "account_type": { "Vesting": {} },
+            "layout_version": VestingScheduleHeader::LAYOUT_VERSION,
+            "bump_seed": 254,
+            "time_basis": { "Slot": {} },
+            "has_approver": true,
+            "approver": header.approver.unwrap().to_string(),
+            "has_pause_authority": false,
+            "pause_authority": Pubkey::default().to_string(),
+            "is_paused": true,
+            "paused_at": 1609459200,
+            "number_of_split_destinations": 1,
+            "split_destinations": [
+                {
+                    "token_address": header.split_destinations[0].token_address.to_string(),
+                    "basis_points": 10_000,
+                },
+                unused_split,
+                unused_split,
+                unused_split,
+            ],
+            "is_native": true,
+        }),
+    );
+    assert_eq!(encoded_header, packed_header);
+
+    let schedule = VestingSchedule {
+        release_time: 1609459200,
+        amount: 42,
+    };
+    let mut packed_schedule = [0u8; VestingSchedule::LEN];
+    schedule.pack_into_slice(&mut packed_schedule);
+    let schedule_value = json!({ "release_time": 1609459200, "amount": 42 });
+    assert_eq!(
+        encode_defined(&idl, "VestingSchedule", &schedule_value),
+        packed_schedule
+    );
+    assert_eq!(
+        encode_defined(&idl, "Schedule", &schedule_value),
+        packed_schedule
+    );
+
+    let amounts = ClaimableAmounts {
+        claimable: 1,
+        locked: 2,
+        claimed_schedules: 3,
+    };
+    let mut packed_amounts = [0u8; ClaimableAmounts::LEN];
+    amounts.pack_into_slice(&mut packed_amounts);
+    assert_eq!(
+        encode_defined(
+            &idl,
+            "ClaimableAmounts",
+            &json!({ "claimable": 1, "locked": 2, "claimed_schedules": 3 })
+        ),
+        packed_amounts
+    );
+
+    let pool = PoolHeader {
+        pool_authority: Pubkey::new_unique(),
+        mint_address: header.mint_address,
+        is_initialized: true,
+        bump_seed: 253,
+        time_basis: TimeBasis::Epoch,
+        allocated_amount: 1_000_000,
+    };
+    let mut packed_pool = [0u8; PoolHeader::LEN];
+    pool.pack_into_slice(&mut packed_pool);
+    assert_eq!(
+        encode_defined(
+            &idl,
+            "PoolHeader",
+            &json!({
+                "pool_authority": pool.pool_authority.to_string(),
+                "mint_address": pool.mint_address.to_string(),
+                "account_type": { "Pool": {} },
+                "bump_seed": 253,
+                "time_basis": { "Epoch": {} },
+                "allocated_amount": 1_000_000,
+            })
+        ),
+        packed_pool
+    );
+
+    let allocation = AllocationHeader {
+        pool_address: Pubkey::new_unique(),
+        destination_address: header.destination_address,
+        is_initialized: true,
+        bump_seed: 252,
+    };
+    let mut packed_allocation = [0u8; AllocationHeader::LEN];
+    allocation.pack_into_slice(&mut packed_allocation);
+    assert_eq!(
+        encode_defined(
+            &idl,
+            "AllocationHeader",
+            &json!({
+                "pool_address": allocation.pool_address.to_string(),
+                "destination_address": allocation.destination_address.to_string(),
+                "account_type": { "Allocation": {} },
+                "bump_seed": 252,
+            })
+        ),
+        packed_allocation
+    );
+
+    let merkle_root = [7u8; 32];
+    let distributor = DistributorHeader {
+        merkle_root,
+        mint_address: header.mint_address,
+        is_initialized: true,
+        bump_seed: 251,
+        time_basis: TimeBasis::Slot,
+        start_time: 100,
+        cliff_time: 150,
+        end_time: 300,
+        total_amount: 1_000,
+        claimed_amount: 10,
+    };
+    let mut packed_distributor = [0u8; DistributorHeader::LEN];
+    distributor.pack_into_slice(&mut packed_distributor);
+    assert_eq!(
+        encode_defined(
+            &idl,
+            "DistributorHeader",
+            &json!({
+                "merkle_root": merkle_root,
+                "mint_address": distributor.mint_address.to_string(),
+                "account_type": { "Distributor": {} },
+                "bump_seed": 251,
+                "time_basis": { "Slot": {} },
+                "start_time": 100,
+                "cliff_time": 150,
+                "end_time": 300,
+                "total_amount": 1_000,
+                "claimed_amount": 10,
+            })
+        ),
+        packed_distributor
+    );
+
+    let claim = DistributionClaim {
+        distributor_address: Pubkey::new_unique(),
+        claimant: Pubkey::new_unique(),
+        is_initialized: true,
+        bump_seed: 250,
+        amount: 400,
+        claimed_amount: 100,
+    };
+    let mut packed_claim = [0u8; DistributionClaim::LEN];
+    claim.pack_into_slice(&mut packed_claim);
+    assert_eq!(
+        encode_defined(
+            &idl,
+            "DistributionClaim",
+            &json!({
+                "distributor_address": claim.distributor_address.to_string(),
+                "claimant": claim.claimant.to_string(),
+                "account_type": { "DistributionClaim": {} },
+                "bump_seed": 250,
+                "amount": 400,
+                "claimed_amount": 100,
+            })
+        ),
+        packed_claim
+    );
+
+    let vesting_account = Pubkey::new_unique();
+    let old_destination = Pubkey::new_unique();
+    let new_destination = Pubkey::new_unique();
+    let events = vec![
+        (
+            VestingEvent::Created {
+                vesting_account,
+                mint_address: header.mint_address,
+                destination_token_address: old_destination,
+                total_amount: 42,
+                schedules: vec![Schedule {
+                    release_time: 1609459200,
+                    amount: 42,
+                }],
+                unix_timestamp: -1,
+            },
+            json!({ "Created": {
+                "vesting_account": vesting_account.to_string(),
+                "mint_address": header.mint_address.to_string(),
+                "destination_token_address": old_destination.to_string(),
+                "total_amount": 42,
+                "schedules": [schedule_value],
+                "unix_timestamp": -1,
+            }}),
+        ),
+        (
+            VestingEvent::Unlocked {
+                vesting_account,
+                destination_token_address: old_destination,
+                amount: 42,
+                unix_timestamp: 1609459200,
+            },
+            json!({ "Unlocked": {
+                "vesting_account": vesting_account.to_string(),
+                "destination_token_address": old_destination.to_string(),
+                "amount": 42,
+                "unix_timestamp": 1609459200,
+            }}),
+        ),
+        (
+            VestingEvent::DestinationChanged {
+                vesting_account,
+                old_destination_token_address: old_destination,
+                new_destination_token_address: new_destination,
+                unix_timestamp: 1609459200,
+            },
+            json!({ "DestinationChanged": {
+                "vesting_account": vesting_account.to_string(),
+                "old_destination_token_address": old_destination.to_string(),
+                "new_destination_token_address": new_destination.to_string(),
+                "unix_timestamp": 1609459200,
+            }}),
+        ),
+        (
+            VestingEvent::MilestoneReleased {
+                vesting_account,
+                schedule_index: 2,
+                unix_timestamp: 1609459200,
+            },
+            json!({ "MilestoneReleased": {
+                "vesting_account": vesting_account.to_string(),
+                "schedule_index": 2,
+                "unix_timestamp": 1609459200,
+            }}),
+        ),
+        (
+            VestingEvent::Paused {
+                vesting_account,
+                unix_timestamp: 1609459200,
+            },
+            json!({ "Paused": {
+                "vesting_account": vesting_account.to_string(),
+                "unix_timestamp": 1609459200,
+            }}),
+        ),
+        (
+            VestingEvent::Resumed {
+                vesting_account,
+                paused_duration: 86400,
+                shifted_release_times: true,
+                unix_timestamp: 1609545600,
+            },
+            json!({ "Resumed": {
+                "vesting_account": vesting_account.to_string(),
+                "paused_duration": 86400,
+                "shifted_release_times": true,
+                "unix_timestamp": 1609545600,
+            }}),
+        ),
+        (
+            VestingEvent::PoolCreated {
+                pool_account: allocation.pool_address,
+                mint_address: pool.mint_address,
+                pool_authority: pool.pool_authority,
+                unix_timestamp: 1609459200,
+            },
+            json!({ "PoolCreated": {
+                "pool_account": allocation.pool_address.to_string(),
+                "mint_address": pool.mint_address.to_string(),
+                "pool_authority": pool.pool_authority.to_string(),
+                "unix_timestamp": 1609459200,
+            }}),
+        ),
+        (
+            VestingEvent::AllocationAdded {
+                pool_account: allocation.pool_address,
+                allocation_account: vesting_account,
+                destination_token_address: old_destination,
+                total_amount: 42,
+                schedules: vec![Schedule {
+                    release_time: 1609459200,
+                    amount: 42,
+                }],
+                unix_timestamp: 1609459200,
+            },
+            json!({ "AllocationAdded": {
+                "pool_account": allocation.pool_address.to_string(),
+                "allocation_account": vesting_account.to_string(),
+                "destination_token_address": old_destination.to_string(),
+                "total_amount": 42,
+                "schedules": [schedule_value],
+                "unix_timestamp": 1609459200,
+            }}),
+        ),
+        (
+            VestingEvent::AllocationRemoved {
+                pool_account: allocation.pool_address,
+                allocation_account: vesting_account,
+                cancelled_amount: 21,
+                unix_timestamp: 1609459200,
+            },
+            json!({ "AllocationRemoved": {
+                "pool_account": allocation.pool_address.to_string(),
+                "allocation_account": vesting_account.to_string(),
+                "cancelled_amount": 21,
+                "unix_timestamp": 1609459200,
+            }}),
+        ),
+        (
+            VestingEvent::AllocationClaimed {
+                pool_account: allocation.pool_address,
+                allocation_account: vesting_account,
+                destination_token_address: old_destination,
+                amount: 21,
+                unix_timestamp: 1609545600,
+            },
+            json!({ "AllocationClaimed": {
+                "pool_account": allocation.pool_address.to_string(),
+                "allocation_account": vesting_account.to_string(),
+                "destination_token_address": old_destination.to_string(),
+                "amount": 21,
+                "unix_timestamp": 1609545600,
+            }}),
+        ),
+        (
+            VestingEvent::DistributorCreated {
+                distributor_account: vesting_account,
+                mint_address: header.mint_address,
+                merkle_root,
+                total_amount: 1_000,
+                unix_timestamp: 1609459200,
+            },
+            json!({ "DistributorCreated": {
+                "distributor_account": vesting_account.to_string(),
+                "mint_address": header.mint_address.to_string(),
+                "merkle_root": merkle_root,
+                "total_amount": 1_000,
+                "unix_timestamp": 1609459200,
+            }}),
+        ),
+        (
+            VestingEvent::DistributionClaimed {
+                distributor_account: vesting_account,
+                claimant: new_destination,
+                destination_token_address: old_destination,
+                amount: 100,
+                unix_timestamp: 1609545600,
+            },
+            json!({ "DistributionClaimed": {
+                "distributor_account": vesting_account.to_string(),
+                "claimant": new_destination.to_string(),
+                "destination_token_address": old_destination.to_string(),
+                "amount": 100,
+                "unix_timestamp": 1609545600,
+            }}),
+        ),
+        (
+            VestingEvent::SplitDestinationChanged {
+                vesting_account,
+                old_split_token_address: old_destination,
+                new_split_token_address: new_destination,
+                unix_timestamp: 1609459200,
+            },
+            json!({ "SplitDestinationChanged": {
+                "vesting_account": vesting_account.to_string(),
+                "old_split_token_address": old_destination.to_string(),
+                "new_split_token_address": new_destination.to_string(),
+                "unix_timestamp": 1609459200,
+            }}),
+        ),
+    ];
+    for (event, value) in events.iter() {
+        // Packed events start with their version
+        assert_eq!(
+            encode_defined(&idl, "VestingEvent", value),
+            event.pack()[1..]
+        );
+    }
+}
+
+#[test]
+fn test_idl_errors() {
+    let idl = load_idl();
+    let idl_errors = idl["errors"].as_array().unwrap();
+    let errors: Vec<VestingError> = (0..)
+        .map_while(VestingError::from_u32)
+        .collect();
+    assert_eq!(idl_errors.len(), errors.len());
+    for (code, (idl_error, error)) in idl_errors.iter().zip(errors.iter()).enumerate() {
+        assert_eq!(idl_error["code"], code);
+        assert_eq!(idl_error["name"], format!("{:?}", error));
+        assert_eq!(idl_error["msg"], error.to_string());
+    }
+}